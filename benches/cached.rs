@@ -0,0 +1,45 @@
+#![allow(missing_docs)]
+
+use std::{env, hint::black_box};
+
+use bytesize::{ByteSize, CachedDisplay, FormatOptions};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+static POOL: CachedDisplay = CachedDisplay::new(64);
+
+/// A handful of sizes, as repeatedly logged by e.g. a buffer pool.
+const SIZES: [u64; 8] = [
+    512,
+    4_096,
+    8_192,
+    65_536,
+    1_048_576,
+    4_194_304,
+    16_777_216,
+    1 << 30,
+];
+
+#[divan::bench]
+fn to_string_with() {
+    let options = FormatOptions::new();
+
+    for size in SIZES {
+        black_box(black_box(ByteSize(size)).to_string_with(&options));
+    }
+}
+
+#[divan::bench]
+fn cached_display_get() {
+    let options = FormatOptions::new();
+
+    for size in SIZES {
+        black_box(POOL.get(black_box(ByteSize(size)), &options));
+    }
+}
+
+fn main() {
+    env::set_var("DIVAN_SAMPLE_COUNT", "1000");
+    divan::main();
+}
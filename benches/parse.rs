@@ -0,0 +1,23 @@
+#![allow(missing_docs)]
+
+use std::{env, hint::black_box};
+
+#[divan::bench]
+fn parse_integer() {
+    black_box(black_box("1048576").parse::<bytesize::ByteSize>()).unwrap();
+}
+
+#[divan::bench]
+fn parse_with_unit() {
+    black_box(black_box("521 TiB").parse::<bytesize::ByteSize>()).unwrap();
+}
+
+#[divan::bench]
+fn parse_with_fraction() {
+    black_box(black_box("1.5KiB").parse::<bytesize::ByteSize>()).unwrap();
+}
+
+fn main() {
+    env::set_var("DIVAN_SAMPLE_COUNT", "1000");
+    divan::main();
+}
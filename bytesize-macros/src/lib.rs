@@ -0,0 +1,104 @@
+//! Procedural macros for the [`bytesize`] crate.
+//!
+//! Use through the `macros` feature of `bytesize` rather than depending on this crate directly.
+//!
+//! [`bytesize`]: https://docs.rs/bytesize
+
+use proc_macro::{Group, Literal, Span, TokenStream, TokenTree};
+
+/// Constructs a `ByteSize` from a string literal, validated and folded at compile time.
+///
+/// Expands to a constant evaluated with the same parser as the declarative `bytesize!` macro, so
+/// the accepted syntax is identical. Invalid sizes, including sizes that do not fit in a `u64`,
+/// are compile errors pointing at the offending literal.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{byte_size, ByteSize};
+///
+/// const CHUNK: ByteSize = byte_size!("12.5MiB");
+/// assert_eq!(ByteSize::kib(12_800), CHUNK);
+/// ```
+///
+/// ```compile_fail
+/// let size = bytesize::byte_size!("12.5MiBB");
+/// ```
+///
+/// ```compile_fail
+/// let size = bytesize::byte_size!("16 EiB");
+/// ```
+#[proc_macro]
+pub fn byte_size(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+
+    let lit = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(lit)), None) => lit,
+        (Some(tt), _) => return compile_error("expected a single string literal", tt.span()),
+        (None, _) => return compile_error("expected a string literal", Span::call_site()),
+    };
+
+    let span = lit.span();
+
+    if !is_string_literal(&lit.to_string()) {
+        return compile_error("expected a string literal", span);
+    }
+
+    // parsing happens in const evaluation, so there is only one parser to keep in sync
+    let tokens = format!(
+        "{{ const SIZE: ::bytesize::ByteSize = ::bytesize::__private::parse_const({lit}); SIZE }}"
+    )
+    .parse::<TokenStream>()
+    .expect("generated code is valid");
+
+    respan(tokens, span)
+}
+
+/// Returns true for plain and raw string literals.
+fn is_string_literal(lit: &str) -> bool {
+    lit.starts_with('"') || lit.starts_with("r\"") || lit.starts_with("r#")
+}
+
+/// Sets the span of every token, so errors point at the macro's input.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                TokenTree::Group(respanned)
+            }
+            mut tt => {
+                tt.set_span(span);
+                tt
+            }
+        })
+        .collect()
+}
+
+fn compile_error(msg: &str, span: Span) -> TokenStream {
+    let tokens = format!("::core::compile_error!({})", Literal::string(msg))
+        .parse::<TokenStream>()
+        .expect("generated code is valid");
+
+    respan(tokens, span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_literals() {
+        assert!(is_string_literal(r#""1 KiB""#));
+        assert!(is_string_literal(r#""1\tKiB""#));
+        assert!(is_string_literal(r##"r"1 KiB""##));
+        assert!(is_string_literal(r###"r#"1 KiB"#"###));
+
+        assert!(!is_string_literal("42"));
+        assert!(!is_string_literal("1.5"));
+        assert!(!is_string_literal(r#"b"1 KiB""#));
+        assert!(!is_string_literal("'K'"));
+    }
+}
@@ -1,7 +1,42 @@
-use alloc::string::ToString as _;
+use alloc::string::{String, ToString as _};
+use core::fmt::{self, Write as _};
 
-use bytesize::ByteSize;
+use bytesize::{ByteSize, ParseOptions, Unit};
 
 pub fn create_byte_size() {
     ByteSize::kib(44).to_string();
 }
+
+pub fn parse_byte_size() -> Result<ByteSize, String> {
+    let size = "1.5 GiB".parse::<ByteSize>()?;
+    let unit = "KiB".parse::<Unit>().map_err(|err| err.to_string())?;
+    let lenient = ParseOptions::new().lenient().parse("5 mB")?;
+
+    Ok(size + ByteSize(unit * 1) + lenient)
+}
+
+/// Formats into a fixed buffer, without allocating.
+pub fn write_byte_size(buf: &mut [u8]) -> fmt::Result {
+    struct Buf<'a> {
+        bytes: &'a mut [u8],
+        len: usize,
+    }
+
+    impl fmt::Write for Buf<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let mut buf = Buf { bytes: buf, len: 0 };
+
+    ByteSize::mib(42).write_to(&mut buf)?;
+    buf.write_char(' ')?;
+    ByteSize::mib(42).display().si().trim_zeros().write_to(&mut buf)
+}
@@ -0,0 +1,89 @@
+#ifndef BYTESIZE_H
+#define BYTESIZE_H
+
+/* Generated with cbindgen. Regenerate with `just ffi-header` instead of editing by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Buffer length that fits any string from [`bytesize_format_iec()`] or [`bytesize_format_si()`],
+ * including the NUL terminator.
+ */
+#define BYTESIZE_FORMAT_LEN 16
+
+#ifdef __cplusplus
+extern "C" {
+#endif  // __cplusplus
+
+/**
+ * Parses the size in `input`, e.g., `"1.5 GiB"`, and stores its number of bytes in `out`.
+ *
+ * Returns `false` if `input` isn't a valid size, or either pointer is null. `out` is left
+ * unchanged in that case. See [`bytesize_parse_error()`] for a description of the problem.
+ *
+ * # Safety
+ *
+ * `input` must be null or point to a NUL-terminated string, and `out` must be null or valid for
+ * writes.
+ */
+bool bytesize_parse(const char *input, uint64_t *out);
+
+/**
+ * Writes a description of why `input` isn't a valid size to `buf`, e.g., for logging after
+ * [`bytesize_parse()`] failed.
+ *
+ * Returns the length of the description, or 0 if `input` is a valid size, in which case an empty
+ * string is written.
+ *
+ * # Safety
+ *
+ * `input` must be null or point to a NUL-terminated string, and `buf` must be valid for writes of
+ * `len` bytes, or null if `len` is 0.
+ */
+size_t bytesize_parse_error(const char *input, char *buf, size_t len);
+
+/**
+ * Formats `bytes` in IEC (binary) units, e.g., `1.5 KiB`.
+ *
+ * Returns the length of the string, not counting the NUL terminator. At most `len` bytes are
+ * written, always including a NUL terminator if `len` isn't 0.
+ *
+ * # Safety
+ *
+ * `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+ */
+size_t bytesize_format_iec(uint64_t bytes, char *buf, size_t len);
+
+/**
+ * Formats `bytes` in SI (decimal) units, e.g., `1.5 kB`.
+ *
+ * Returns the length of the string, not counting the NUL terminator. At most `len` bytes are
+ * written, always including a NUL terminator if `len` isn't 0.
+ *
+ * # Safety
+ *
+ * `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+ */
+size_t bytesize_format_si(uint64_t bytes, char *buf, size_t len);
+
+/**
+ * Formats `bytes` exactly, in the largest unit that divides it, e.g., `1536 KiB`.
+ *
+ * Returns the length of the string, not counting the NUL terminator. At most `len` bytes are
+ * written, always including a NUL terminator if `len` isn't 0.
+ *
+ * # Safety
+ *
+ * `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+ */
+size_t bytesize_format_exact(uint64_t bytes, char *buf, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BYTESIZE_H */
@@ -2,6 +2,7 @@ use arbitrary::{Arbitrary, Unstructured};
 
 use crate::ByteSize;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
 impl Arbitrary<'_> for ByteSize {
     fn arbitrary(u: &mut Unstructured<'_>) -> arbitrary::Result<Self> {
         Ok(ByteSize(u64::arbitrary(u)?))
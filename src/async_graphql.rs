@@ -0,0 +1,170 @@
+//! GraphQL scalars for `async-graphql`.
+//!
+//! `ByteSize` is a `ByteSize` scalar that outputs a plain number of bytes, e.g., `1536`. Where
+//! clients show sizes to people, [`HumanReadable`] is a `HumanByteSize` scalar that outputs a
+//! formatted string, e.g., `"1.5 KiB"`, instead. Its format follows `ByteSize`'s `Display` impl,
+//! so it can be switched to SI units with [`set_default_format()`](crate::set_default_format).
+//! Both accept either form as input: a whole, non-negative number of bytes, or a string like
+//! `"1.5 KiB"`.
+//!
+//! # Examples
+//!
+//! ```
+//! use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+//! use bytesize::{async_graphql::HumanReadable, ByteSize};
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn quota(&self) -> ByteSize {
+//!         ByteSize::kib(1536)
+//!     }
+//!
+//!     async fn display_quota(&self) -> HumanReadable {
+//!         ByteSize::kib(1536).into()
+//!     }
+//!
+//!     async fn double(&self, size: ByteSize) -> ByteSize {
+//!         size * 2_u32
+//!     }
+//! }
+//!
+//! # bytesize::set_default_format(bytesize::Format::Iec);
+//! let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+//! let query = r#"{ quota displayQuota double(size: "1 KiB") }"#;
+//! let response = futures::executor::block_on(schema.execute(query));
+//!
+//! assert_eq!(
+//!     serde_json::json!({ "quota": 1572864, "displayQuota": "1.5 MiB", "double": 2048 }),
+//!     response.data.into_json().unwrap(),
+//! );
+//! ```
+
+use alloc::string::ToString as _;
+
+use async_graphql::{InputValueError, InputValueResult, Number, Scalar, ScalarType, Value};
+
+use crate::{parse::whole_bytes, ByteSize};
+
+/// A size that's output as a formatted string, e.g., `"1.5 KiB"`, like `ByteSize`'s `Display`
+/// impl.
+///
+/// Input is accepted in the same forms as `ByteSize`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanReadable(pub ByteSize);
+
+impl From<ByteSize> for HumanReadable {
+    fn from(size: ByteSize) -> Self {
+        Self(size)
+    }
+}
+
+impl From<HumanReadable> for ByteSize {
+    fn from(size: HumanReadable) -> Self {
+        size.0
+    }
+}
+
+/// A size in bytes. Accepts a whole, non-negative number of bytes, or a string like `"1.5 KiB"`.
+#[Scalar(name = "ByteSize")]
+impl ScalarType for ByteSize {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match value {
+            Value::Number(number) => from_number(&number),
+            Value::String(string) => string.parse().map_err(InputValueError::custom),
+            value => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::Number(_) | Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Number(self.0.into())
+    }
+}
+
+/// A size, formatted like `"1.5 KiB"`. Accepts a whole, non-negative number of bytes, or a string
+/// like `"1.5 KiB"`.
+#[Scalar(name = "HumanByteSize")]
+impl ScalarType for HumanReadable {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        <ByteSize as ScalarType>::parse(value)
+            .map(Self)
+            .map_err(InputValueError::propagate)
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        <ByteSize as ScalarType>::is_valid(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+fn from_number(number: &Number) -> InputValueResult<ByteSize> {
+    if let Some(bytes) = number.as_u64() {
+        Ok(ByteSize(bytes))
+    } else if let Some(bytes) = number.as_f64() {
+        whole_bytes(bytes).map_err(InputValueError::custom)
+    } else {
+        Err(InputValueError::custom("size is not a valid number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::InputType;
+
+    use super::*;
+
+    #[track_caller]
+    fn parse(value: Value) -> InputValueResult<ByteSize> {
+        <ByteSize as ScalarType>::parse(value)
+    }
+
+    #[test]
+    fn input() {
+        assert_eq!(ByteSize(1536), parse(Value::from(1536)).unwrap());
+        assert_eq!(ByteSize(u64::MAX), parse(Value::from(u64::MAX)).unwrap());
+        assert_eq!(ByteSize(1536), parse(Value::from(1536.0)).unwrap());
+        assert_eq!(ByteSize::kib(1536), parse(Value::from("1.5 MiB")).unwrap());
+
+        assert!(parse(Value::from(-1)).is_err());
+        assert!(parse(Value::from(1.5)).is_err());
+        assert!(parse(Value::from("1.5 parsecs")).is_err());
+        assert!(parse(Value::Boolean(true)).is_err());
+        assert!(parse(Value::Null).is_err());
+
+        assert!(<ByteSize as ScalarType>::is_valid(&Value::from("1 KiB")));
+        assert!(!<ByteSize as ScalarType>::is_valid(&Value::Boolean(true)));
+
+        assert_eq!(
+            HumanReadable(ByteSize::kib(1)),
+            <HumanReadable as ScalarType>::parse(Value::from("1 KiB")).unwrap()
+        );
+        assert!(<HumanReadable as ScalarType>::parse(Value::from(-1)).is_err());
+    }
+
+    #[test]
+    fn output() {
+        assert_eq!(Value::from(1536), ScalarType::to_value(&ByteSize(1536)));
+        assert_eq!(
+            Value::from(u64::MAX),
+            ScalarType::to_value(&ByteSize(u64::MAX))
+        );
+        assert_eq!(
+            Value::from("1.5 KiB"),
+            ScalarType::to_value(&HumanReadable(ByteSize(1536)))
+        );
+    }
+
+    #[test]
+    fn type_names() {
+        assert_eq!("ByteSize", ByteSize::type_name());
+        assert_eq!("HumanByteSize", HumanReadable::type_name());
+    }
+}
@@ -0,0 +1,161 @@
+//! Converts byte sizes between representations.
+//!
+//! ```console
+//! $ bytesize 1.5GiB --to mb
+//! 1610.6 MB
+//! $ bytesize --parse "3 TB" --raw
+//! 3000000000000
+//! ```
+
+use std::{
+    env,
+    io::{self, BufRead as _},
+    process::ExitCode,
+};
+
+use bytesize::{ByteSize, Unit};
+
+const USAGE: &str = "\
+Usage: bytesize [OPTIONS] [SIZE]...
+
+Converts sizes between representations. Sizes are read from stdin, one per line, if none are
+given as arguments.
+
+Options:
+      --to <UNIT>     Show sizes in UNIT, e.g., mb or GiB
+      --si            Use SI (1000-based) units
+      --raw           Print the plain byte count
+      --parse <SIZE>  Convert SIZE, even if it looks like an option
+  -h, --help          Print help
+  -V, --version       Print version
+";
+
+enum Command {
+    Help,
+    Version,
+    Convert(Options),
+}
+
+#[derive(Default)]
+struct Options {
+    unit: Option<Unit>,
+    si: bool,
+    raw: bool,
+    sizes: Vec<String>,
+}
+
+impl Options {
+    /// Parses `value` and formats it according to the options.
+    fn convert(&self, value: &str) -> Result<String, String> {
+        let size = value.trim().parse::<ByteSize>()?;
+
+        if self.raw {
+            return Ok(size.as_u64().to_string());
+        }
+
+        let mut display = size.display();
+
+        if self.si {
+            display = display.si();
+        }
+
+        if let Some(unit) = self.unit {
+            display = display.in_unit(unit);
+        }
+
+        Ok(display.to_string())
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_owned())),
+            _ => (arg.as_str(), None),
+        };
+
+        let mut value = |flag: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{flag} requires a value"))
+        };
+
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--si" => options.si = true,
+            "--raw" => options.raw = true,
+            "--to" => {
+                let unit = value(flag)?;
+                options.unit = Some(unit.parse().map_err(|err| format!("--to: {err}"))?);
+            }
+            "--parse" => options.sizes.push(value(flag)?),
+            "--" => {
+                options.sizes.extend(args.by_ref());
+            }
+            _ if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(format!("unknown option {flag:?}"));
+            }
+            _ => options.sizes.push(arg),
+        }
+    }
+
+    if options.raw && options.unit.is_some() {
+        return Err("--raw cannot be combined with --to".to_owned());
+    }
+
+    Ok(Command::Convert(options))
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Command::Convert(options)) => options,
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("bytesize {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+
+    let mut convert = |value: &str| match options.convert(value) {
+        Ok(output) => println!("{output}"),
+        Err(err) => {
+            eprintln!("error: {err}");
+            status = ExitCode::FAILURE;
+        }
+    };
+
+    if options.sizes.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if !line.trim().is_empty() {
+                convert(&line);
+            }
+        }
+    } else {
+        for value in &options.sizes {
+            convert(value);
+        }
+    }
+
+    status
+}
@@ -0,0 +1,70 @@
+use bincode::{
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
+
+use crate::ByteSize;
+
+/// Encodes the byte count as a `u64`, using the configured integer encoding.
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+impl Encode for ByteSize {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+/// Decodes the byte count as a `u64`, using the configured integer encoding.
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+impl<Context> Decode<Context> for ByteSize {
+    #[inline]
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u64::decode(decoder).map(ByteSize)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+impl<'de, Context> BorrowDecode<'de, Context> for ByteSize {
+    #[inline]
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        u64::borrow_decode(decoder).map(ByteSize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let sizes = [ByteSize::b(42), ByteSize::mib(42), ByteSize(u64::MAX)];
+        let mut buf = [0; 32];
+
+        let len = bincode::encode_into_slice(sizes, &mut buf, config::standard()).unwrap();
+        // varint encoding, 1 byte for small values
+        assert_eq!(42, buf[0]);
+        assert_eq!(
+            (sizes, len),
+            bincode::decode_from_slice(&buf[..len], config::standard()).unwrap()
+        );
+
+        let config = config::legacy();
+        let len = bincode::encode_into_slice(sizes[1], &mut buf, config).unwrap();
+        assert_eq!(
+            ByteSize::mib(42).as_u64().to_le_bytes().as_slice(),
+            &buf[..len]
+        );
+        assert_eq!(
+            (sizes[1], len),
+            bincode::borrow_decode_from_slice(&buf[..len], config).unwrap()
+        );
+
+        assert!(bincode::decode_from_slice::<ByteSize, _>(&buf[..7], config).is_err());
+    }
+}
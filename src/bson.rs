@@ -0,0 +1,168 @@
+//! Conversions to and from BSON values, as stored in MongoDB documents.
+//!
+//! Sizes are written as `Int64` numbers of bytes. Documents written by other tools often store
+//! them differently, so reading accepts any of the common forms:
+//!
+//! - `Int32` or `Int64` numbers of bytes;
+//! - `Double` numbers of bytes, as written by JavaScript clients, if they're whole numbers;
+//! - strings like `"1.5 GiB"`, parsed like [`ByteSize::from_str()`](core::str::FromStr).
+//!
+//! # Examples
+//!
+//! ```
+//! use bson::{doc, Bson};
+//! use bytesize::ByteSize;
+//!
+//! let doc = doc! {
+//!     "quota": ByteSize::gib(2),
+//!     "used": 1_048_576.0,
+//!     "reserved": "512 MiB",
+//! };
+//!
+//! assert_eq!(Some(&Bson::Int64(2_147_483_648)), doc.get("quota"));
+//!
+//! assert_eq!(Ok(Some(ByteSize::gib(2))), bytesize::bson::get(&doc, "quota"));
+//! assert_eq!(Ok(Some(ByteSize::mib(1))), bytesize::bson::get(&doc, "used"));
+//! assert_eq!(Ok(Some(ByteSize::mib(512))), bytesize::bson::get(&doc, "reserved"));
+//! assert_eq!(Ok(None), bytesize::bson::get(&doc, "limit"));
+//! ```
+
+use alloc::{format, string::String};
+
+use ::bson::{Bson, Document};
+
+use crate::{parse::whole_bytes, ByteSize};
+
+/// Converts to an `Int64` number of bytes.
+///
+/// Sizes above [`i64::MAX`] bytes (8 EiB) don't fit, and are converted to an exact string instead,
+/// e.g., `"8 EiB"`, which [`from_bson()`] reads back.
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+impl From<ByteSize> for Bson {
+    fn from(size: ByteSize) -> Self {
+        match i64::try_from(size.0) {
+            Ok(bytes) => Bson::Int64(bytes),
+            Err(_) => Bson::String(size.to_string_exact()),
+        }
+    }
+}
+
+/// Reads a size from any of the forms described in the [module docs](self).
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+impl TryFrom<&Bson> for ByteSize {
+    type Error = String;
+
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        from_bson(value)
+    }
+}
+
+/// Reads a size from any of the forms described in the [module docs](self).
+///
+/// Fails for negative or fractional numbers, unparsable strings, and values of other types.
+pub fn from_bson(value: &Bson) -> Result<ByteSize, String> {
+    match *value {
+        Bson::Int32(bytes) => from_int(bytes.into()),
+        Bson::Int64(bytes) => from_int(bytes),
+        Bson::Double(bytes) => whole_bytes(bytes),
+        Bson::String(ref size) => size.parse(),
+        ref value => Err(format!(
+            "expected a number of bytes or a size string, found {:?}",
+            value.element_type()
+        )),
+    }
+}
+
+/// Reads the size stored under `key` in `doc`.
+///
+/// Returns `None` if the key is missing or its value is `null`.
+pub fn get(doc: &Document, key: &str) -> Result<Option<ByteSize>, String> {
+    match doc.get(key) {
+        None | Some(Bson::Null) => Ok(None),
+        Some(value) => from_bson(value)
+            .map(Some)
+            .map_err(|err| format!("invalid size in field `{key}`: {err}")),
+    }
+}
+
+fn from_int(bytes: i64) -> Result<ByteSize, String> {
+    u64::try_from(bytes)
+        .map(ByteSize)
+        .map_err(|_| format!("{bytes} is not a valid size, sizes can't be negative"))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use ::bson::doc;
+
+    use super::*;
+
+    #[test]
+    fn to_bson() {
+        assert_eq!(Bson::Int64(1536), Bson::from(ByteSize::b(1536)));
+        assert_eq!(Bson::Int64(i64::MAX), Bson::from(ByteSize(i64::MAX as u64)));
+        assert_eq!(
+            Bson::String("8 EiB".to_string()),
+            Bson::from(ByteSize::eib(8))
+        );
+        assert_eq!(
+            Ok(ByteSize(u64::MAX)),
+            from_bson(&Bson::from(ByteSize(u64::MAX)))
+        );
+    }
+
+    #[test]
+    fn from_forms() {
+        assert_eq!(Ok(ByteSize::kib(1)), from_bson(&Bson::Int32(1024)));
+        assert_eq!(Ok(ByteSize::kib(1)), from_bson(&Bson::Int64(1024)));
+        assert_eq!(Ok(ByteSize::kib(1)), from_bson(&Bson::Double(1024.0)));
+        assert_eq!(Ok(ByteSize(0)), from_bson(&Bson::Double(-0.0)));
+        assert_eq!(
+            Ok(ByteSize::kib(1536)),
+            from_bson(&Bson::String("1.5 MiB".to_string()))
+        );
+        assert_eq!(Ok(ByteSize::kib(1)), ByteSize::try_from(&Bson::Int64(1024)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err("-1 is not a valid size, sizes can't be negative".to_string()),
+            from_bson(&Bson::Int32(-1))
+        );
+        assert_eq!(
+            Err("-1 is not a valid size, sizes can't be negative".to_string()),
+            from_bson(&Bson::Double(-1.0))
+        );
+        assert_eq!(
+            Err("1.5 is not a whole number of bytes".to_string()),
+            from_bson(&Bson::Double(1.5))
+        );
+        assert!(from_bson(&Bson::Double(f64::NAN)).is_err());
+        assert!(from_bson(&Bson::Double(f64::INFINITY)).is_err());
+        assert!(from_bson(&Bson::Double(1e20)).is_err());
+        assert!(from_bson(&Bson::String("12 parsecs".to_string())).is_err());
+        assert_eq!(
+            Err("expected a number of bytes or a size string, found Boolean".to_string()),
+            from_bson(&Bson::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn documents() {
+        let doc = doc! {
+            "int": 1024,
+            "null": Bson::Null,
+            "bad": "lots",
+        };
+
+        assert_eq!(Ok(Some(ByteSize::kib(1))), get(&doc, "int"));
+        assert_eq!(Ok(None), get(&doc, "null"));
+        assert_eq!(Ok(None), get(&doc, "missing"));
+
+        let err = get(&doc, "bad").unwrap_err();
+        assert!(err.starts_with("invalid size in field `bad`: "), "{err}");
+    }
+}
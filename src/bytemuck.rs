@@ -0,0 +1,30 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::ByteSize;
+
+// SAFETY: `ByteSize` is `repr(transparent)` over `u64`, which is `Zeroable`.
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+unsafe impl Zeroable for ByteSize {}
+
+// SAFETY: `ByteSize` is `repr(transparent)` over `u64`, which is `Pod`.
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+unsafe impl Pod for ByteSize {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cast() {
+        let sizes = [ByteSize::kib(4), ByteSize::mib(1)];
+
+        let words: &[u64] = bytemuck::cast_slice(&sizes);
+        assert_eq!(&[4096, 1_048_576], words);
+
+        let bytes = bytemuck::bytes_of(&sizes[0]);
+        assert_eq!(&4096_u64.to_ne_bytes(), bytes);
+        assert_eq!(sizes[0], bytemuck::pod_read_unaligned::<ByteSize>(bytes));
+
+        assert_eq!(ByteSize(0), ByteSize::zeroed());
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{ByteSize, FormatOptions};
+
+/// Bounded memoization pool for formatted sizes.
+///
+/// Logging and metrics code often formats the same handful of sizes over and over. A
+/// `CachedDisplay` formats each distinct size and [`FormatOptions`] pair once and hands out shared
+/// [`Arc<str>`] copies afterwards, so repeated lookups only clone a reference count instead of
+/// allocating a new string.
+///
+/// The pool holds at most `capacity` strings. When it is full, the least recently used entry is
+/// evicted, so memory use stays bounded however many distinct sizes pass through it. Since
+/// [`new()`](Self::new) is `const`, a pool can be stored in a `static` and shared by all threads.
+///
+/// Entries are keyed by exact byte count, so a pool suits workloads that repeat the same values
+/// (e.g., buffer or chunk sizes) rather than ones where every value is different.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use bytesize::{ByteSize, CachedDisplay, FormatOptions};
+///
+/// static SIZES: CachedDisplay = CachedDisplay::new(64);
+///
+/// let options = FormatOptions::new();
+///
+/// let first = SIZES.get(ByteSize::kib(4), &options);
+/// let second = SIZES.get(ByteSize::kib(4), &options);
+///
+/// assert_eq!("4.0 KiB", &*first);
+/// assert!(Arc::ptr_eq(&first, &second));
+/// ```
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct CachedDisplay {
+    capacity: usize,
+    cache: Mutex<Cache>,
+}
+
+#[derive(Debug)]
+struct Cache {
+    /// Lazily created, since `HashMap::new()` is not `const`.
+    entries: Option<HashMap<(u64, FormatOptions), Entry>>,
+
+    /// Incremented on each lookup, used to find the least recently used entry.
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    string: Arc<str>,
+    last_used: u64,
+}
+
+impl CachedDisplay {
+    /// Constructs an empty pool which holds at most `capacity` strings.
+    ///
+    /// A capacity of zero disables caching; every lookup formats a new string.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            cache: Mutex::new(Cache {
+                entries: None,
+                clock: 0,
+            }),
+        }
+    }
+
+    /// Returns the maximum number of strings held by the pool.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of strings currently held by the pool.
+    pub fn len(&self) -> usize {
+        self.lock().entries.as_ref().map_or(0, HashMap::len)
+    }
+
+    /// Returns true if the pool holds no strings.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `size` formatted with `options`, reusing a previously formatted string if possible.
+    ///
+    /// The returned string is equal to [`ByteSize::to_string_with()`].
+    pub fn get(&self, size: ByteSize, options: &FormatOptions) -> Arc<str> {
+        if self.capacity == 0 {
+            return Arc::from(size.to_string_with(options));
+        }
+
+        let key = (size.as_u64(), *options);
+
+        let mut cache = self.lock();
+        cache.clock += 1;
+        let clock = cache.clock;
+
+        let entries = cache.entries.get_or_insert_with(HashMap::new);
+
+        if let Some(entry) = entries.get_mut(&key) {
+            entry.last_used = clock;
+            return Arc::clone(&entry.string);
+        }
+
+        if entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key);
+
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        let string = Arc::<str>::from(size.to_string_with(options));
+
+        entries.insert(
+            key,
+            Entry {
+                string: Arc::clone(&string),
+                last_used: clock,
+            },
+        );
+
+        string
+    }
+
+    /// Removes all strings from the pool.
+    ///
+    /// Strings already handed out stay valid; they are freed once their last reference is dropped.
+    pub fn clear(&self) {
+        self.lock().entries = None;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache> {
+        // the cache is always left in a consistent state, so a panic while it was held is harmless
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Format;
+
+    #[test]
+    fn reuses_strings() {
+        let pool = CachedDisplay::new(4);
+        let iec = FormatOptions::new();
+        let si = FormatOptions::new().format(Format::Si);
+
+        let first = pool.get(ByteSize::kib(4), &iec);
+        let second = pool.get(ByteSize::kib(4), &iec);
+        let si_string = pool.get(ByteSize::kib(4), &si);
+
+        assert_eq!("4.0 KiB", &*first);
+        assert_eq!("4.1 kB", &*si_string);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(2, pool.len());
+
+        pool.clear();
+        assert!(pool.is_empty());
+        assert_eq!("4.0 KiB", &*first);
+        assert!(!Arc::ptr_eq(&first, &pool.get(ByteSize::kib(4), &iec)));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let pool = CachedDisplay::new(2);
+        let options = FormatOptions::new();
+
+        let one = pool.get(ByteSize::kib(1), &options);
+        pool.get(ByteSize::kib(2), &options);
+
+        // touch 1 KiB so that 2 KiB is the oldest entry
+        pool.get(ByteSize::kib(1), &options);
+        pool.get(ByteSize::kib(3), &options);
+
+        assert_eq!(2, pool.len());
+        assert!(Arc::ptr_eq(&one, &pool.get(ByteSize::kib(1), &options)));
+        assert_eq!("2.0 KiB", &*pool.get(ByteSize::kib(2), &options));
+        assert_eq!(2, pool.len());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let pool = CachedDisplay::new(0);
+        let options = FormatOptions::new();
+
+        let first = pool.get(ByteSize::mib(1), &options);
+        let second = pool.get(ByteSize::mib(1), &options);
+
+        assert_eq!("1.0 MiB", &*first);
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert!(pool.is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{fmt, fmt::Write as _};
+
+use crate::{ByteSize, Display};
+
+/// Serializes a map of sizes to canonical JSON, with each size formatted by `style`.
+///
+/// The output follows the JSON Canonicalization Scheme (RFC 8785): no insignificant whitespace,
+/// object keys sorted by their UTF-16 code units, and minimal string escaping. Sizes are written as
+/// strings. Formatting only uses integer math, so the output is byte-for-byte identical across
+/// platforms and suitable for signing.
+///
+/// Use `|size| size.display().in_unit(Unit::Byte)` to keep sizes exact.
+///
+/// # Errors
+///
+/// Returns an error if `style` fails to format a size, e.g., due to
+/// [`ExcessPrecision::Error`](crate::ExcessPrecision::Error).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use bytesize::{ByteSize, Unit};
+///
+/// let sizes = BTreeMap::from([
+///     ("kernel".to_owned(), ByteSize::mib(12)),
+///     ("initrd".to_owned(), ByteSize::kib(48_640)),
+/// ]);
+///
+/// assert_eq!(
+///     r#"{"initrd":"47.5 MiB","kernel":"12.0 MiB"}"#,
+///     bytesize::to_canonical_json(&sizes, |size| size.display())?,
+/// );
+///
+/// assert_eq!(
+///     r#"{"initrd":"49807360 B","kernel":"12582912 B"}"#,
+///     bytesize::to_canonical_json(&sizes, |size| size.display().in_unit(Unit::Byte))?,
+/// );
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+pub fn to_canonical_json<F>(
+    sizes: &BTreeMap<String, ByteSize>,
+    style: F,
+) -> Result<String, fmt::Error>
+where
+    F: Fn(&ByteSize) -> Display,
+{
+    // BTreeMap orders by UTF-8 bytes, which differs from UTF-16 order for some characters
+    let mut entries = sizes.iter().collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+    let mut out = String::from("{");
+    let mut value = String::new();
+
+    for (idx, (key, size)) in entries.into_iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+
+        value.clear();
+        style(size).write_to(&mut value)?;
+
+        write_json_string(&mut out, key);
+        out.push(':');
+        write_json_string(&mut out, &value);
+    }
+
+    out.push('}');
+    Ok(out)
+}
+
+/// Writes `value` as a JSON string, escaping only what RFC 8785 requires.
+fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0'..='\u{1f}' => {
+                let _ = write!(out, "\\u{:04x}", u32::from(ch));
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::ToOwned as _;
+
+    use super::*;
+    use crate::{ExcessPrecision, Unit};
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            "{}",
+            to_canonical_json(&BTreeMap::new(), |size| size.display()).unwrap()
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        let sizes = BTreeMap::from([("a\"b\\c\n\u{1}é".to_owned(), ByteSize(1))]);
+
+        assert_eq!(
+            r#"{"a\"b\\c\n\u0001é":"1 B"}"#,
+            to_canonical_json(&sizes, |size| size.display()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn utf16_key_order() {
+        // U+FF61 sorts before U+1F600 in UTF-8 but after it in UTF-16
+        let sizes = BTreeMap::from([
+            ("\u{1f600}".to_owned(), ByteSize(1)),
+            ("\u{ff61}".to_owned(), ByteSize(2)),
+            ("a".to_owned(), ByteSize(3)),
+        ]);
+
+        assert_eq!(
+            "{\"a\":\"3B\",\"\u{1f600}\":\"1B\",\"\u{ff61}\":\"2B\"}",
+            to_canonical_json(&sizes, |size| size.display().iec_short()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn style_error() {
+        let sizes = BTreeMap::from([("disk".to_owned(), ByteSize::gb(512))]);
+
+        let result = to_canonical_json(&sizes, |size| {
+            size.display()
+                .si()
+                .significant_figures(20)
+                .excess_precision(ExcessPrecision::Error)
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn styles() {
+        let sizes = BTreeMap::from([("disk".to_owned(), ByteSize::gb(512))]);
+
+        assert_eq!(
+            r#"{"disk":"512.0 GB"}"#,
+            to_canonical_json(&sizes, |size| size.display().si()).unwrap(),
+        );
+        assert_eq!(
+            r#"{"disk":"512000000000 B"}"#,
+            to_canonical_json(&sizes, |size| size.display().in_unit(Unit::Byte)).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{ByteSize, Display};
+
+/// Helpers for explaining the difference between advertised and OS-reported drive capacities.
+///
+/// Drive manufacturers advertise capacities using SI (decimal) units, while some operating
+/// systems compute sizes using binary math but still label them with SI unit names. A drive sold
+/// as 2 TB is therefore shown as 1.8 TB.
+impl ByteSize {
+    /// Constructs the size of a drive advertised as `gb` gigabytes.
+    ///
+    /// Equivalent to [`ByteSize::gb()`].
+    #[inline(always)]
+    pub const fn advertised_gb(gb: u64) -> ByteSize {
+        ByteSize::gb(gb)
+    }
+
+    /// Constructs the size of a drive advertised as `tb` terabytes.
+    ///
+    /// Equivalent to [`ByteSize::tb()`].
+    #[inline(always)]
+    pub const fn advertised_tb(tb: u64) -> ByteSize {
+        ByteSize::tb(tb)
+    }
+
+    /// Constructs a size from a figure shown as `gb` "GB" by an OS which uses binary math.
+    ///
+    /// Fractional bytes are truncated.
+    pub fn os_reported_gb(gb: f64) -> ByteSize {
+        ByteSize::gib(1).mul_f64(gb)
+    }
+
+    /// Constructs a size from a figure shown as `tb` "TB" by an OS which uses binary math.
+    ///
+    /// Fractional bytes are truncated.
+    pub fn os_reported_tb(tb: f64) -> ByteSize {
+        ByteSize::tib(1).mul_f64(tb)
+    }
+
+    /// Returns a display wrapper showing this size the way an OS which uses binary math with SI
+    /// unit names (e.g., Windows) would.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let drive = ByteSize::advertised_tb(2);
+    /// assert_eq!("1.82 TB", format!("{:.2}", drive.os_visible()));
+    /// ```
+    pub fn os_visible(&self) -> Display {
+        self.display().jedec()
+    }
+
+    /// Returns a display wrapper showing this size the way drive manufacturers advertise it,
+    /// using SI (decimal) units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let drive = ByteSize::os_reported_gb(931.5);
+    /// assert_eq!("1.0 TB", drive.advertised().to_string());
+    /// ```
+    pub fn advertised(&self) -> Display {
+        self.display().si()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString as _};
+
+    use super::*;
+
+    #[test]
+    fn advertised_to_os_visible() {
+        assert_eq!(
+            "931.3 GB",
+            ByteSize::advertised_gb(1000).os_visible().to_string()
+        );
+        assert_eq!(
+            "931.3 GB",
+            ByteSize::advertised_tb(1).os_visible().to_string()
+        );
+        assert_eq!(
+            "1.82 TB",
+            format!("{:.2}", ByteSize::advertised_tb(2).os_visible())
+        );
+        assert_eq!("500 B", ByteSize::b(500).os_visible().to_string());
+        assert_eq!("1.0 KB", ByteSize::kib(1).os_visible().to_string());
+    }
+
+    #[test]
+    fn os_reported_to_advertised() {
+        assert_eq!(ByteSize::os_reported_gb(1.0), ByteSize::gib(1));
+        assert_eq!(ByteSize::os_reported_tb(1.5), ByteSize::gib(1536));
+        assert_eq!(
+            "1.0 TB",
+            ByteSize::os_reported_gb(931.5).advertised().to_string()
+        );
+        assert_eq!(
+            "2.00 TB",
+            format!("{:.2}", ByteSize::os_reported_tb(1.82).advertised())
+        );
+    }
+}
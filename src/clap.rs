@@ -0,0 +1,159 @@
+//! Integration with `clap` command-line parsers.
+//!
+//! `ByteSize` implements `clap`'s `ValueParserFactory`, so size arguments work in derive-based
+//! CLIs without any attributes. Defaults can be given as typed values, since sizes are printed in a
+//! form that parses back.
+//!
+//! Use [`parser()`] to configure parsing, e.g., to accept common mistakes in unit case.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::ByteSize;
+//! use clap::Parser;
+//!
+//! #[derive(Debug, Parser)]
+//! struct Args {
+//!     #[arg(long, default_value = "64 MiB")]
+//!     max_size: ByteSize,
+//!
+//!     #[arg(long, value_parser = bytesize::clap::parser().lenient())]
+//!     min_size: Option<ByteSize>,
+//! }
+//!
+//! let args = Args::parse_from(["app", "--max-size", "1.5 GiB", "--min-size", "5 mB"]);
+//! assert_eq!(ByteSize::mib(1536), args.max_size);
+//! assert_eq!(Some(ByteSize::mb(5)), args.min_size);
+//!
+//! let args = Args::parse_from(["app"]);
+//! assert_eq!(ByteSize::mib(64), args.max_size);
+//!
+//! let err = Args::try_parse_from(["app", "--max-size", "12 parsecs"]).unwrap_err();
+//! assert!(err.to_string().contains("invalid value '12 parsecs' for '--max-size <MAX_SIZE>'"));
+//! ```
+
+use alloc::{format, string::String};
+use std::ffi::OsStr;
+
+use ::clap::{
+    builder::{StringValueParser, TypedValueParser, ValueParserFactory},
+    Arg, Command, Error,
+};
+
+use crate::{ByteSize, ParseOptions};
+
+/// Hint appended to parse errors.
+const EXPECTED: &str = "expected a number of bytes or a size with a unit, e.g., \"1.5 MiB\"";
+
+/// Returns a value parser for `ByteSize` arguments.
+///
+/// Equivalent to the parser used by default for `ByteSize` arguments.
+pub fn parser() -> ByteSizeValueParser {
+    ByteSizeValueParser::new()
+}
+
+/// Value parser for `ByteSize` arguments, using [`ParseOptions`].
+///
+/// Invalid values are reported with the parse error and a hint about the expected form.
+#[derive(Debug, Clone, Default)]
+pub struct ByteSizeValueParser {
+    options: ParseOptions,
+}
+
+impl ByteSizeValueParser {
+    /// Constructs a parser with the default parsing options.
+    pub const fn new() -> Self {
+        Self {
+            options: ParseOptions::new(),
+        }
+    }
+
+    /// Accept common mistakes in unit case, treating `mB` (millibytes) as `MB` (megabytes).
+    ///
+    /// See [`ParseOptions::lenient()`].
+    #[must_use]
+    pub const fn lenient(mut self) -> Self {
+        self.options = self.options.lenient();
+        self
+    }
+}
+
+impl TypedValueParser for ByteSizeValueParser {
+    type Value = ByteSize;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let options = self.options.clone();
+
+        StringValueParser::new()
+            .try_map(move |value: String| {
+                options
+                    .parse(&value)
+                    .map_err(|err| format!("{err}; {EXPECTED}"))
+            })
+            .parse_ref(cmd, arg, value)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+impl ValueParserFactory for ByteSize {
+    type Parser = ByteSizeValueParser;
+
+    fn value_parser() -> Self::Parser {
+        ByteSizeValueParser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use ::clap::{error::ErrorKind, value_parser};
+
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("app")
+            .arg(
+                Arg::new("size")
+                    .long("size")
+                    .value_parser(value_parser!(ByteSize)),
+            )
+            .arg(
+                Arg::new("lenient")
+                    .long("lenient")
+                    .value_parser(parser().lenient()),
+            )
+    }
+
+    #[test]
+    fn parse() {
+        let matches = command()
+            .try_get_matches_from(["app", "--size", "1.5KiB", "--lenient", "2 mB"])
+            .unwrap();
+
+        assert_eq!(Some(&ByteSize::b(1536)), matches.get_one("size"));
+        assert_eq!(Some(&ByteSize::mb(2)), matches.get_one("lenient"));
+    }
+
+    #[test]
+    fn errors() {
+        let err = command()
+            .try_get_matches_from(["app", "--size", "2 mB"])
+            .unwrap_err();
+
+        assert_eq!(ErrorKind::ValueValidation, err.kind());
+
+        let message = err.to_string();
+        assert!(
+            message.contains("invalid value '2 mB' for '--size <size>'"),
+            "{message}"
+        );
+        assert!(message.contains("fraction of a byte"), "{message}");
+        assert!(message.contains("e.g., \"1.5 MiB\""), "{message}");
+    }
+}
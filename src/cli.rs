@@ -0,0 +1,333 @@
+//! Parsers for the human-readable output of common command-line tools.
+//!
+//! Covers `df -h`, `du -h`, `free -h`, and `ls -lh` as printed by GNU coreutils and procps-ng.
+//! These tools use 1024-based units, so `4.0K` is 4096 bytes. Output produced with `--si` uses
+//! 1000-based units and can be parsed with [`ByteSize`]'s `FromStr` impl instead.
+//!
+//! The tools round their output, so parsed sizes are approximate.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::{cli, ByteSize};
+//!
+//! let entry = cli::parse_du_line("4.0K\t./target").unwrap();
+//! assert_eq!(ByteSize::kib(4), entry.size);
+//! assert_eq!("./target", entry.path);
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString as _},
+};
+
+use crate::{parse::Decimal, ByteSize};
+
+/// Parses a single size as printed by coreutils' `-h` option, e.g., `4.0K`, `15Gi`, or `0B`.
+///
+/// Suffixes are 1024-based regardless of case, and may be followed by `i` and/or `B`. A comma is
+/// accepted as the decimal separator, as printed in some locales.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{cli, ByteSize};
+///
+/// assert_eq!(ByteSize::kib(4), cli::parse_size("4.0K").unwrap());
+/// assert_eq!(ByteSize::mib(1536), cli::parse_size("1,5Gi").unwrap());
+/// assert_eq!(ByteSize::b(512), cli::parse_size("512").unwrap());
+/// ```
+pub fn parse_size(value: &str) -> Result<ByteSize, String> {
+    let number_len = value
+        .bytes()
+        .position(|b| !(b.is_ascii_digit() || b == b'.' || b == b','))
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(number_len);
+
+    let exp = match suffix.as_bytes() {
+        [] | [b'B'] => 0,
+        [prefix] | [prefix, b'i' | b'B'] | [prefix, b'i', b'B'] => {
+            match prefix.to_ascii_uppercase() {
+                b'K' => 1,
+                b'M' => 2,
+                b'G' => 3,
+                b'T' => 4,
+                b'P' => 5,
+                b'E' => 6,
+                _ => return Err(format!("couldn't parse {suffix:?} into a known unit")),
+            }
+        }
+        _ => return Err(format!("couldn't parse {suffix:?} into a known unit")),
+    };
+
+    // coreutils always prints a digit after the decimal separator
+    if number.ends_with(['.', ',']) {
+        return Err(format!("couldn't parse {value:?} into a ByteSize"));
+    }
+
+    let number = number.replace(',', ".");
+    match Decimal::parse(number.as_bytes()) {
+        Ok(decimal) => Ok(ByteSize(decimal.scale(crate::KIB.pow(exp)))),
+        Err(error) => Err(format!("couldn't parse {value:?} into a ByteSize, {error}")),
+    }
+}
+
+/// A line of `df -h` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DfEntry {
+    /// Device or filesystem name.
+    pub filesystem: String,
+
+    /// Total size.
+    pub size: ByteSize,
+
+    /// Used space.
+    pub used: ByteSize,
+
+    /// Space available to unprivileged users.
+    pub available: ByteSize,
+
+    /// Mount point.
+    pub mounted_on: String,
+}
+
+/// Parses a line of `df -h` output.
+///
+/// The header line is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{cli, ByteSize};
+///
+/// let entry = cli::parse_df_line("/dev/sda1        20G  5.0G   14G  27% /").unwrap();
+/// assert_eq!("/dev/sda1", entry.filesystem);
+/// assert_eq!(ByteSize::gib(20), entry.size);
+/// assert_eq!(ByteSize::gib(5), entry.used);
+/// assert_eq!(ByteSize::gib(14), entry.available);
+/// assert_eq!("/", entry.mounted_on);
+/// ```
+pub fn parse_df_line(line: &str) -> Result<DfEntry, String> {
+    let ([filesystem, size, used, available, _use_percent], mounted_on) =
+        split_fields(line).ok_or_else(|| format!("expected 6 columns in df line {line:?}"))?;
+
+    Ok(DfEntry {
+        filesystem: filesystem.to_string(),
+        size: parse_size(size)?,
+        used: parse_size(used)?,
+        available: parse_size(available)?,
+        mounted_on: mounted_on.to_string(),
+    })
+}
+
+/// A line of `du -h` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DuEntry {
+    /// Disk usage.
+    pub size: ByteSize,
+
+    /// File or directory path.
+    pub path: String,
+}
+
+/// Parses a line of `du -h` output.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{cli, ByteSize};
+///
+/// let entry = cli::parse_du_line("1.5M\tsrc").unwrap();
+/// assert_eq!(ByteSize::kib(1536), entry.size);
+/// assert_eq!("src", entry.path);
+/// ```
+pub fn parse_du_line(line: &str) -> Result<DuEntry, String> {
+    // du separates columns with a tab, so paths may contain spaces
+    let ([size], path) =
+        split_fields(line).ok_or_else(|| format!("expected 2 columns in du line {line:?}"))?;
+
+    Ok(DuEntry {
+        size: parse_size(size)?,
+        path: path.to_string(),
+    })
+}
+
+/// A line of `free -h` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FreeEntry {
+    /// Row label without the trailing colon, e.g., `Mem` or `Swap`.
+    pub label: String,
+
+    /// Total size.
+    pub total: ByteSize,
+
+    /// Used memory.
+    pub used: ByteSize,
+
+    /// Unused memory.
+    pub free: ByteSize,
+
+    /// Shared memory. Only present on the `Mem` row.
+    pub shared: Option<ByteSize>,
+
+    /// Buffers and page cache. Only present on the `Mem` row.
+    pub buff_cache: Option<ByteSize>,
+
+    /// Estimated memory available for new applications. Only present on the `Mem` row.
+    pub available: Option<ByteSize>,
+}
+
+/// Parses a `Mem:` or `Swap:` line of `free -h` output, as printed by procps-ng 3.3.10 and later.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{cli, ByteSize};
+///
+/// let entry = cli::parse_free_line("Swap:          2.0Gi          0B       2.0Gi").unwrap();
+/// assert_eq!("Swap", entry.label);
+/// assert_eq!(ByteSize::gib(2), entry.total);
+/// assert_eq!(ByteSize::b(0), entry.used);
+/// assert_eq!(None, entry.available);
+/// ```
+pub fn parse_free_line(line: &str) -> Result<FreeEntry, String> {
+    let mut fields = line.split_ascii_whitespace();
+
+    let label = fields
+        .next()
+        .and_then(|label| label.strip_suffix(':'))
+        .ok_or_else(|| format!("expected row label in free line {line:?}"))?;
+
+    let mut sizes = [None; 6];
+
+    for (idx, field) in fields.enumerate() {
+        let size = sizes
+            .get_mut(idx)
+            .ok_or_else(|| format!("too many columns in free line {line:?}"))?;
+
+        *size = Some(parse_size(field)?);
+    }
+
+    match sizes {
+        [Some(total), Some(used), Some(free), shared, buff_cache, available] => Ok(FreeEntry {
+            label: label.to_string(),
+            total,
+            used,
+            free,
+            shared,
+            buff_cache,
+            available,
+        }),
+        _ => Err(format!("expected at least 3 sizes in free line {line:?}")),
+    }
+}
+
+/// A line of `ls -lh` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LsEntry {
+    /// File size.
+    pub size: ByteSize,
+
+    /// File name, including any symlink target.
+    pub name: String,
+}
+
+/// Parses a file line of `ls -lh` output.
+///
+/// Device files, which show major and minor numbers instead of a size, and the `total` line are
+/// rejected.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{cli, ByteSize};
+///
+/// let entry =
+///     cli::parse_ls_line("-rw-r--r-- 1 alice staff 4.0K Jan  2 12:34 notes.txt").unwrap();
+/// assert_eq!(ByteSize::kib(4), entry.size);
+/// assert_eq!("notes.txt", entry.name);
+/// ```
+pub fn parse_ls_line(line: &str) -> Result<LsEntry, String> {
+    let ([_mode, _links, _owner, _group, size, _month, _day, _time], name) =
+        split_fields(line).ok_or_else(|| format!("expected 9 columns in ls line {line:?}"))?;
+
+    Ok(LsEntry {
+        size: parse_size(size)?,
+        name: name.to_string(),
+    })
+}
+
+/// Splits off the first `N` whitespace-separated fields of `line`.
+///
+/// Returns the fields and the non-empty remainder of the line, with surrounding whitespace
+/// removed, so that the last column may contain spaces.
+fn split_fields<const N: usize>(line: &str) -> Option<([&str; N], &str)> {
+    let mut fields = [""; N];
+    let mut rest = line.trim();
+
+    for field in &mut fields {
+        let end = rest.find(char::is_whitespace)?;
+        *field = &rest[..end];
+        rest = rest[end..].trim_start();
+    }
+
+    if rest.is_empty() {
+        None
+    } else {
+        Some((fields, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(ByteSize(0), parse_size("0").unwrap());
+        assert_eq!(ByteSize(0), parse_size("0B").unwrap());
+        assert_eq!(ByteSize(123), parse_size("123").unwrap());
+        assert_eq!(ByteSize::kib(4), parse_size("4.0K").unwrap());
+        assert_eq!(ByteSize::kib(4), parse_size("4.0k").unwrap());
+        assert_eq!(ByteSize::kib(4), parse_size("4,0K").unwrap());
+        assert_eq!(ByteSize::mib(15), parse_size("15Mi").unwrap());
+        assert_eq!(ByteSize::gib(2), parse_size("2GiB").unwrap());
+        assert_eq!(ByteSize::tib(3), parse_size("3TB").unwrap());
+        assert_eq!(ByteSize::eib(1), parse_size("1E").unwrap());
+
+        assert!(parse_size("").is_err());
+        assert!(parse_size("K").is_err());
+        assert!(parse_size("4.0X").is_err());
+        assert!(parse_size("4.0KK").is_err());
+        assert!(parse_size("4.0 K").is_err());
+    }
+
+    #[test]
+    fn fields() {
+        assert_eq!(Some((["a", "b"], "c d")), split_fields("  a \t b  c d "));
+        assert_eq!(None, split_fields::<2>("a b"));
+        assert_eq!(None, split_fields::<2>("a b   "));
+    }
+
+    #[test]
+    fn rejects_headers() {
+        let df = "Filesystem      Size  Used Avail Use% Mounted on";
+        assert!(parse_df_line(df).is_err());
+
+        let free =
+            "               total        used        free      shared  buff/cache   available";
+        assert!(parse_free_line(free).is_err());
+
+        assert!(parse_ls_line("total 12K").is_err());
+    }
+
+    #[test]
+    fn device_files() {
+        let line = "crw-rw-rw- 1 root root 1, 3 Jan  2 12:34 /dev/null";
+        assert!(parse_ls_line(line).is_err());
+    }
+}
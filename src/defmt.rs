@@ -0,0 +1,17 @@
+use defmt::{Format, Formatter};
+
+use crate::{stack_str::iec, ByteSize};
+
+/// Logs the size in IEC units, e.g., `1.5 KiB`, like [`ByteSize::display()`].
+///
+/// The size is formatted into a stack buffer on the device and logged as a `str`, without
+/// allocating.
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl Format for ByteSize {
+    fn format(&self, f: Formatter<'_>) {
+        match iec(*self) {
+            Some(buf) => defmt::write!(f, "{=str}", buf.as_str()),
+            None => defmt::write!(f, "{=u64} B", self.0),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql},
+    serialize::{self, Output, ToSql},
+    sql_types::BigInt,
+};
+
+use crate::{
+    sql::{from_i64, to_i64},
+    ByteSize,
+};
+
+/// Fails for sizes above [`i64::MAX`] bytes (8 EiB).
+#[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
+impl<DB> ToSql<BigInt, DB> for ByteSize
+where
+    DB: Backend,
+    i64: ToSql<BigInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        // `i64`'s impl needs a reference that lives as long as `self`, so rather than converting
+        // to a temporary, the range is checked and `self` is reinterpreted in place
+        to_i64(*self)?;
+
+        // SAFETY: `ByteSize` is `repr(transparent)` over `u64`, which has the same size and
+        // alignment as `i64`. The value is at most `i64::MAX`, so it reads back unchanged.
+        let value = unsafe { &*(self as *const ByteSize).cast::<i64>() };
+
+        value.to_sql(out)
+    }
+}
+
+/// Fails for negative values.
+#[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
+impl<DB> FromSql<BigInt, DB> for ByteSize
+where
+    DB: Backend,
+    i64: FromSql<BigInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        from_i64(<i64 as FromSql<BigInt, DB>>::from_sql(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU32;
+
+    use diesel::{
+        deserialize::Queryable,
+        expression::AsExpression,
+        pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue},
+        query_builder::bind_collector::{BindCollector as _, RawBytesBindCollector},
+        sql_types::Nullable,
+    };
+
+    use super::*;
+
+    struct NoLookup;
+
+    impl PgMetadataLookup for NoLookup {
+        fn lookup_type(&mut self, _type_name: &str, _schema: Option<&str>) -> PgTypeMetadata {
+            unreachable!("BIGINT is a built-in type")
+        }
+    }
+
+    fn to_sql(size: ByteSize) -> diesel::QueryResult<Vec<u8>> {
+        let mut collector = RawBytesBindCollector::<Pg>::new();
+        collector.push_bound_value::<BigInt, _>(&size, &mut NoLookup)?;
+        Ok(collector.binds.remove(0).unwrap())
+    }
+
+    fn from_sql(value: i64) -> deserialize::Result<ByteSize> {
+        const INT8_OID: NonZeroU32 = NonZeroU32::new(20).unwrap();
+
+        let bytes = value.to_be_bytes();
+        ByteSize::from_sql(PgValue::new(&bytes, &INT8_OID))
+    }
+
+    #[test]
+    fn round_trip() {
+        for size in [ByteSize(0), ByteSize::mib(3), ByteSize(i64::MAX as u64)] {
+            let bytes = to_sql(size).unwrap();
+            assert_eq!(size.0.to_be_bytes().as_slice(), bytes);
+            assert_eq!(size, from_sql(size.0 as i64).unwrap());
+        }
+    }
+
+    #[test]
+    fn derived_traits() {
+        fn assert_expression<T: AsExpression<BigInt> + AsExpression<Nullable<BigInt>>>() {}
+        fn assert_queryable<T: Queryable<BigInt, Pg>>() {}
+
+        assert_expression::<ByteSize>();
+        assert_expression::<&ByteSize>();
+        assert_queryable::<ByteSize>();
+    }
+
+    #[test]
+    fn out_of_range() {
+        let err = to_sql(ByteSize::eib(8)).unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        let err = from_sql(-1).unwrap_err();
+        assert_eq!(
+            "-1 is not a valid size, sizes can't be negative",
+            err.to_string()
+        );
+    }
+}
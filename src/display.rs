@@ -1,52 +1,639 @@
-use core::{fmt, write};
+use alloc::string::String;
+use core::{
+    cmp::Ordering,
+    fmt,
+    fmt::Write as _,
+    mem,
+    sync::atomic::{AtomicU8, Ordering as AtomicOrdering},
+    write,
+};
 
-use crate::ByteSize;
+use crate::{ByteSize, Unit, UnitPreference};
+
+/// Long-form IEC (binary) unit prefixes.
+const LONG_PREFIXES_IEC: [&str; 6] = ["kibi", "mebi", "gibi", "tebi", "pebi", "exbi"];
+
+/// Long-form SI (decimal) unit prefixes.
+const LONG_PREFIXES_SI: [&str; 6] = ["kilo", "mega", "giga", "tera", "peta", "exa"];
 
 /// Format / style to use when displaying a [`ByteSize`].
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum Format {
+///
+/// Each variant corresponds to a [`Display`] style method. Used to change the process-wide
+/// default with [`set_default_format()`](crate::set_default_format).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Format {
+    /// IEC (binary) units, e.g., `1.5 MiB`. See [`Display::iec()`].
     Iec,
+
+    /// Short IEC (binary) units, e.g., `1.5M`. See [`Display::iec_short()`].
     IecShort,
+
+    /// SI (decimal) units, e.g., `1.5 MB`. See [`Display::si()`].
     Si,
+
+    /// Short SI (decimal) units, e.g., `1.5M`. See [`Display::si_short()`].
     SiShort,
+
+    /// Bits with IEC (binary) units, e.g., `94.4 Mibit`. See [`Display::iec_bits()`].
+    IecBits,
+
+    /// Bits with SI (decimal) units, e.g., `99.0 Mbit`. See [`Display::si_bits()`].
+    SiBits,
+
+    /// Short bits with IEC (binary) units, e.g., `94.4M`. See [`Display::iec_bits_short()`].
+    IecBitsShort,
+
+    /// Short bits with SI (decimal) units, e.g., `99.0M`. See [`Display::si_bits_short()`].
+    SiBitsShort,
+
+    /// Binary math with SI-style labels, e.g., `1.8 TB` for 2 terabytes. See
+    /// [`Display::jedec()`].
+    Jedec,
+
+    /// GNU coreutils' `-h` output, e.g., `1.1K` or `12M`. See [`Display::coreutils()`].
+    Coreutils,
+
+    /// Windows Explorer's output, e.g., `1.43 MB` for 1.43 MiB. See
+    /// [`Display::windows_explorer()`].
+    WindowsExplorer,
+}
+
+/// Process-wide default format, stored as the `Format` discriminant.
+///
+/// Unit tests always start from IEC units, so they check the same output with every feature set.
+/// The `display-si-default` feature is tested in `tests/display_si_default.rs`.
+#[cfg(any(not(feature = "display-si-default"), test))]
+static DEFAULT_FORMAT: AtomicU8 = AtomicU8::new(Format::Iec as u8);
+
+/// Process-wide default format, stored as the `Format` discriminant.
+#[cfg(all(feature = "display-si-default", not(test)))]
+static DEFAULT_FORMAT: AtomicU8 = AtomicU8::new(Format::Si as u8);
+
+/// Sets the format used by `ByteSize`'s `Display` and `Debug` impls, process-wide.
+///
+/// Useful for changing how sizes are shown by code that formats them with `{}`, like
+/// dependencies. To use SI units from the start, enable the `display-si-default` crate feature
+/// instead. Explicitly styled output from [`ByteSize::display()`] is not affected. Sizes written
+/// with lossy formats, like [`Format::Coreutils`], won't parse back to the same value.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, Format};
+///
+/// bytesize::set_default_format(Format::Si);
+/// assert_eq!("1.5 MB", ByteSize::kb(1500).to_string());
+/// assert_eq!("1.4 MiB", ByteSize::kb(1500).display().to_string());
+/// # bytesize::set_default_format(Format::Iec);
+/// ```
+pub fn set_default_format(format: Format) {
+    DEFAULT_FORMAT.store(format as u8, AtomicOrdering::Relaxed);
+}
+
+/// Returns the format used by `ByteSize`'s `Display` and `Debug` impls.
+///
+/// This is [`Format::Iec`], or [`Format::Si`] with the `display-si-default` crate feature, unless
+/// changed with [`set_default_format()`].
+pub fn default_format() -> Format {
+    const FORMATS: [Format; 11] = [
+        Format::Iec,
+        Format::IecShort,
+        Format::Si,
+        Format::SiShort,
+        Format::IecBits,
+        Format::SiBits,
+        Format::IecBitsShort,
+        Format::SiBitsShort,
+        Format::Jedec,
+        Format::Coreutils,
+        Format::WindowsExplorer,
+    ];
+
+    let format = DEFAULT_FORMAT.load(AtomicOrdering::Relaxed);
+    FORMATS
+        .into_iter()
+        .find(|candidate| *candidate as u8 == format)
+        .unwrap_or(Format::Iec)
+}
+
+/// Returns a formatting display wrapper for a number of bytes.
+///
+/// Shorthand for `ByteSize(bytes.into()).display()`, for quick debugging output.
+///
+/// # Examples
+///
+/// ```
+/// let buf = vec![0_u8; 3 * 1024];
+/// assert_eq!("3.0 KiB", bytesize::display_of(buf.len() as u64).to_string());
+/// assert_eq!("4.1 kB", bytesize::display_of(4096_u32).si().to_string());
+/// ```
+pub fn display_of(bytes: impl Into<u64>) -> Display {
+    ByteSize(bytes.into()).display()
+}
+
+/// Returns a formatting display wrapper for the size of the pointed-to value.
+///
+/// Uses [`size_of_val()`](core::mem::size_of_val), so for slices and `str` this is the size of
+/// the contents, while for other types it is the size of the value itself, not including any heap
+/// allocations it owns.
+///
+/// # Examples
+///
+/// ```
+/// let table = [0_u64; 512];
+/// assert_eq!("4.0 KiB", bytesize::display_size_of_val(&table).to_string());
+/// assert_eq!("12 B", bytesize::display_size_of_val("hello, world").to_string());
+/// ```
+pub fn display_size_of_val<T: ?Sized>(val: &T) -> Display {
+    // usize is at most 64 bits on all supported platforms
+    ByteSize(mem::size_of_val(val) as u64).display()
 }
 
 impl Format {
     fn unit(self) -> u64 {
         match self {
-            Format::Iec | Format::IecShort => crate::KIB,
-            Format::Si | Format::SiShort => crate::KB,
+            Format::Iec
+            | Format::IecShort
+            | Format::IecBits
+            | Format::IecBitsShort
+            | Format::Jedec
+            | Format::Coreutils
+            | Format::WindowsExplorer => crate::KIB,
+            Format::Si | Format::SiShort | Format::SiBits | Format::SiBitsShort => crate::KB,
         }
     }
 
-    fn unit_base(self) -> f64 {
+    fn unit_prefixes(self) -> &'static [u8] {
         match self {
-            Format::Iec | Format::IecShort => crate::LN_KIB,
-            Format::Si | Format::SiShort => crate::LN_KB,
+            Format::Iec
+            | Format::IecShort
+            | Format::IecBits
+            | Format::IecBitsShort
+            | Format::Jedec
+            | Format::Coreutils
+            | Format::WindowsExplorer => crate::UNITS_IEC.as_bytes(),
+            Format::Si | Format::SiShort | Format::SiBits | Format::SiBitsShort => {
+                crate::UNITS_SI.as_bytes()
+            }
         }
     }
 
-    fn unit_prefixes(self) -> &'static [u8] {
+    fn unit_separator(self) -> &'static str {
         match self {
-            Format::Iec | Format::IecShort => crate::UNITS_IEC.as_bytes(),
-            Format::Si | Format::SiShort => crate::UNITS_SI.as_bytes(),
+            Format::Iec
+            | Format::Si
+            | Format::IecBits
+            | Format::SiBits
+            | Format::Jedec
+            | Format::WindowsExplorer => " ",
+            Format::IecShort
+            | Format::SiShort
+            | Format::IecBitsShort
+            | Format::SiBitsShort
+            | Format::Coreutils => "",
         }
     }
 
-    fn unit_separator(self) -> &'static str {
+    fn is_bits(self) -> bool {
+        matches!(
+            self,
+            Format::IecBits | Format::SiBits | Format::IecBitsShort | Format::SiBitsShort
+        )
+    }
+
+    /// Returns true if units are labelled with IEC (binary) prefixes.
+    fn has_iec_labels(self) -> bool {
+        matches!(
+            self,
+            Format::Iec
+                | Format::IecShort
+                | Format::IecBits
+                | Format::IecBitsShort
+                | Format::Coreutils
+        )
+    }
+
+    fn long_prefixes(self) -> &'static [&'static str] {
+        if self.has_iec_labels() {
+            &LONG_PREFIXES_IEC
+        } else {
+            &LONG_PREFIXES_SI
+        }
+    }
+
+    /// Returns the number of fractional digits needed to show a single byte exactly when
+    /// displaying in the unit with prefix exponent `exp`.
+    ///
+    /// Since `1 / 1024^exp = 5^(10 * exp) / 10^(10 * exp)`, IEC units need `10 * exp` digits,
+    /// while SI units need `3 * exp`.
+    fn byte_resolution_digits(self, exp: usize) -> usize {
         match self {
-            Format::Iec | Format::Si => " ",
-            Format::IecShort | Format::SiShort => "",
+            Format::Iec
+            | Format::IecShort
+            | Format::IecBits
+            | Format::IecBitsShort
+            | Format::Jedec
+            | Format::Coreutils
+            | Format::WindowsExplorer => 10 * exp,
+            Format::Si | Format::SiShort | Format::SiBits | Format::SiBitsShort => 3 * exp,
         }
     }
 
     fn unit_suffix(self) -> &'static str {
         match self {
             Format::Iec => "iB",
-            Format::Si => "B",
-            Format::IecShort | Format::SiShort => "",
+            Format::Si | Format::Jedec | Format::WindowsExplorer => "B",
+            Format::IecShort
+            | Format::SiShort
+            | Format::IecBitsShort
+            | Format::SiBitsShort
+            | Format::Coreutils => "",
+            Format::IecBits => "ibit",
+            Format::SiBits => "bit",
+        }
+    }
+
+    /// Returns the equivalent format, keeping the same style, that uses `unit`'s unit system.
+    fn with_system_of(self, unit: Unit) -> Self {
+        match (self, unit.is_binary()) {
+            (Format::Iec | Format::Si, true) => Format::Iec,
+            (Format::IecShort | Format::SiShort, true) => Format::IecShort,
+            (Format::IecBits | Format::SiBits, true) => Format::IecBits,
+            (Format::IecBitsShort | Format::SiBitsShort, true) => Format::IecBitsShort,
+            (Format::Jedec, true) => Format::Jedec,
+            (Format::Iec | Format::Si, false) => Format::Si,
+            (Format::IecShort | Format::SiShort, false) => Format::SiShort,
+            (Format::IecBits | Format::SiBits, false) => Format::SiBits,
+            (Format::IecBitsShort | Format::SiBitsShort, false) => Format::SiBitsShort,
+            (Format::Jedec, false) => Format::Si,
+            (Format::Coreutils, _) => Format::Coreutils,
+            (Format::WindowsExplorer, _) => Format::WindowsExplorer,
+        }
+    }
+
+    /// Returns the exponent of the largest unit prefix which keeps the displayed number at or
+    /// above 1, or 0 when the quantity should be shown unprefixed.
+    ///
+    /// Uses exact integer logarithms, so units switch at exactly 1024 or 1000 on every platform:
+    /// `quantity >= 1024^exp` exactly when `log2(quantity) >= 10 * exp`, and likewise for powers
+    /// of 1000 and `log10`.
+    #[cfg(not(feature = "float-unit-selection"))]
+    fn ideal_exponent(self, quantity: u128) -> usize {
+        let max_exp = self.unit_prefixes().len();
+
+        let exp = match quantity.checked_ilog2() {
+            None => 0,
+            Some(log2) if self.unit() == crate::KIB => log2 / 10,
+            Some(_) => quantity.ilog10() / 3,
+        };
+
+        (exp as usize).min(max_exp)
+    }
+
+    /// Returns the exponent of the largest unit prefix which keeps the displayed number at or
+    /// above 1, or 0 when the quantity should be shown unprefixed.
+    ///
+    /// Uses floating point logarithms with `std`, or repeated float division without it, as
+    /// before integer logarithms were used. Quantities which round up to a unit boundary as an
+    /// `f64` are shown in the larger unit.
+    #[cfg(feature = "float-unit-selection")]
+    fn ideal_exponent(self, quantity: u128) -> usize {
+        let max_exp = self.unit_prefixes().len();
+        let unit = self.unit() as f64;
+        let size = quantity as f64;
+
+        if size < unit {
+            return 0;
+        }
+
+        #[cfg(feature = "std")]
+        let exp = {
+            let ln_unit = if self.unit() == crate::KIB {
+                LN_KIB
+            } else {
+                LN_KB
+            };
+
+            (size.ln() / ln_unit) as usize
+        };
+
+        #[cfg(not(feature = "std"))]
+        let exp = {
+            let mut exp = 0;
+            let mut scaled = size;
+
+            while scaled >= unit {
+                scaled /= unit;
+                exp += 1;
+            }
+
+            exp
+        };
+
+        exp.min(max_exp)
+    }
+}
+
+/// `ln(1024) ~= 6.931`
+#[cfg(all(feature = "float-unit-selection", feature = "std"))]
+const LN_KIB: f64 = 6.931_471_805_599_453;
+
+/// `ln(1000) ~= 6.908`
+#[cfg(all(feature = "float-unit-selection", feature = "std"))]
+const LN_KB: f64 = 6.907_755_278_982_137;
+
+/// Provides unit labels, e.g., for translated or house-style output.
+///
+/// A label is written as the prefix followed by the suffix. Custom labels replace the built-in
+/// short, standard, and long unit styles entirely, while the number itself is still formatted by
+/// [`Display`].
+///
+/// # Examples
+///
+/// French uses "octet" for byte.
+///
+/// ```
+/// use bytesize::{ByteSize, UnitLabels};
+///
+/// #[derive(Debug)]
+/// struct French;
+///
+/// impl UnitLabels for French {
+///     fn prefix(&self, exp: usize, iec: bool) -> &str {
+///         let prefixes = if iec {
+///             ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"]
+///         } else {
+///             ["", "k", "M", "G", "T", "P", "E"]
+///         };
+///
+///         prefixes[exp]
+///     }
+///
+///     fn suffix(&self, bits: bool, _plural: bool) -> &str {
+///         if bits { "bit" } else { "o" }
+///     }
+/// }
+///
+/// let display = ByteSize::mib(1536).display().labels(&French);
+/// assert_eq!("1.5 Gio", display.to_string());
+///
+/// let display = ByteSize::kb(3).display().si().labels(&French);
+/// assert_eq!("3.0 ko", display.to_string());
+/// ```
+pub trait UnitLabels: fmt::Debug + Sync {
+    /// Returns the prefix for the `exp`-th power of the unit base, or an empty string for `exp`
+    /// of 0.
+    ///
+    /// `iec` is true when the display style calls for IEC (binary) prefixes, like `Ki`, rather
+    /// than SI prefixes, like `k`.
+    fn prefix(&self, exp: usize, iec: bool) -> &str;
+
+    /// Returns the suffix for bytes, or bits if `bits` is true.
+    ///
+    /// `plural` is false only when the number shown is exactly `1`.
+    fn suffix(&self, bits: bool, plural: bool) -> &str;
+}
+
+/// Behavior when the requested precision exceeds the digits a byte count can produce.
+///
+/// A byte count is always a whole number so, for example, a size shown in kilobytes has at most 3
+/// meaningful fractional digits. Digits beyond that are always zero.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, ExcessPrecision};
+///
+/// let size = ByteSize::b(1_234_567);
+///
+/// assert_eq!("1.234567000 MB", format!("{:.9}", size.display().si()));
+///
+/// let display = size.display().si().excess_precision(ExcessPrecision::Trim);
+/// assert_eq!("1.234567 MB", format!("{display:.9}"));
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ExcessPrecision {
+    /// Pad with zeros up to the requested precision.
+    #[default]
+    Pad,
+
+    /// Reduce precision to the number of meaningful digits.
+    Trim,
+
+    /// Return a formatting error.
+    ///
+    /// Note that [`to_string()`](alloc::string::ToString::to_string) panics when formatting
+    /// fails, so use [`write!`] to observe the error.
+    Error,
+}
+
+/// Behavior of bit formats when a size is smaller than the smallest prefixed unit.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, SubUnitBits};
+///
+/// let size = ByteSize::b(100);
+/// assert_eq!("800 bit", size.display().si_bits().to_string());
+///
+/// let display = size.display().si_bits().sub_unit_bits(SubUnitBits::Fraction);
+/// assert_eq!("0.8 kbit", display.to_string());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SubUnitBits {
+    /// Show the exact number of bits, e.g., `800 bit`.
+    #[default]
+    Bits,
+
+    /// Show a fraction of the smallest prefixed unit, e.g., `0.8 kbit`.
+    Fraction,
+
+    /// Fall back to showing bytes, e.g., `100 B`.
+    Bytes,
+
+    /// Return a formatting error.
+    ///
+    /// Note that [`to_string()`](alloc::string::ToString::to_string) panics when formatting
+    /// fails, so use [`write!`] to observe the error.
+    Error,
+}
+
+/// Rounding mode used when a size can not be displayed exactly at the requested precision.
+///
+/// The default, [`HalfEven`](Self::HalfEven), is also known as banker's rounding. Ties are
+/// rounded up as often as down, so totals and averages computed from displayed values do not
+/// drift upward the way they do with [`HalfUp`](Self::HalfUp). Use `HalfUp` to match the
+/// schoolbook rounding of other tools.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, Rounding};
+///
+/// // 2.0001 GiB
+/// let size = ByteSize::gib(2) + ByteSize::kib(110);
+///
+/// assert_eq!("2.0 GiB", size.display().to_string());
+/// assert_eq!("2.1 GiB", size.display().rounding(Rounding::Ceil).to_string());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round towards zero, i.e., truncate extra digits.
+    Floor,
+
+    /// Round away from zero. Displayed values are never less than the actual size.
+    Ceil,
+
+    /// Round to the nearest value, with ties rounded away from zero.
+    HalfUp,
+
+    /// Round to the nearest value, with ties rounded to the even neighbor.
+    #[default]
+    HalfEven,
+}
+
+/// Separator placed between the number and unit.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, Separator};
+///
+/// let display = ByteSize::kib(42).display().separator(Separator::NarrowNoBreakSpace);
+/// assert_eq!("42.0\u{202f}KiB", display.to_string());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Separator {
+    /// No separator, e.g., `42.0KiB`.
+    None,
+
+    /// A regular space, e.g., `42.0 KiB`.
+    Space,
+
+    /// A narrow no-break space (U+202F), as recommended by the SI brochure.
+    NarrowNoBreakSpace,
+}
+
+/// Style of the power of ten in [engineering notation](Display::engineering).
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, Exponent};
+///
+/// let display = ByteSize::mb(1500).display().engineering(Exponent::TimesTen);
+/// assert_eq!("1.5 × 10^9 B", display.to_string());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exponent {
+    /// E notation, e.g., `1.5e9 B`.
+    E,
+
+    /// Multiplication by a power of ten, e.g., `1.5 × 10^9 B`.
+    TimesTen,
+}
+
+impl Separator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Separator::None => "",
+            Separator::Space => " ",
+            Separator::NarrowNoBreakSpace => "\u{202f}",
+        }
+    }
+}
+
+/// Reusable display options, for formatting many sizes the same way.
+///
+/// The default options match [`ByteSize`]'s `Display` impl without a default format override. Use
+/// [`ByteSize::to_string_with()`] to format a size, or [`ByteSize::display()`] for options not
+/// covered here.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, Format, FormatOptions, Separator};
+///
+/// let options = FormatOptions::new()
+///     .format(Format::Si)
+///     .precision(2)
+///     .separator(Separator::NarrowNoBreakSpace);
+///
+/// assert_eq!("1.50\u{202f}MB", ByteSize::kb(1500).to_string_with(&options));
+/// assert_eq!("2.00\u{202f}kB", ByteSize::b(2000).to_string_with(&options));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    format: Format,
+    precision: Option<usize>,
+    separator: Option<Separator>,
+    rounding: Rounding,
+}
+
+impl FormatOptions {
+    /// Returns the default display options.
+    pub const fn new() -> Self {
+        Self {
+            format: Format::Iec,
+            precision: None,
+            separator: None,
+            rounding: Rounding::HalfEven,
         }
     }
+
+    /// Sets the format. See [`Format`].
+    #[must_use]
+    pub const fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the number of fractional digits. Defaults to 1.
+    #[must_use]
+    pub const fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the separator between the number and unit. See [`Display::separator()`].
+    #[must_use]
+    pub const fn separator(mut self, separator: Separator) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Sets the rounding mode. See [`Rounding`].
+    #[must_use]
+    pub const fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Writes `size` to `out` using these options.
+    pub(crate) fn write_to(&self, out: &mut impl fmt::Write, size: ByteSize) -> fmt::Result {
+        let display = Display {
+            format: self.format,
+            separator: self.separator,
+            rounding: self.rounding,
+            ..size.display()
+        };
+
+        display.write_with_precision(out, self.precision)
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Formatting display wrapper for [`ByteSize`].
@@ -67,10 +654,35 @@ impl Format {
 ///     ByteSize::kb(42).display().si_short().to_string(),
 /// );
 /// ```
+///
+/// Width, fill, and alignment flags are honored, which is useful for table layouts:
+///
+/// ```
+/// # use bytesize::ByteSize;
+/// assert_eq!(
+///     "|    1.5 MB|",
+///     format!("|{:>10}|", ByteSize::kb(1500).display().si()),
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub struct Display {
     pub(crate) byte_size: ByteSize,
     pub(crate) format: Format,
+    pub(crate) unit: Option<Unit>,
+    pub(crate) excess_precision: ExcessPrecision,
+    pub(crate) sub_unit_bits: SubUnitBits,
+    pub(crate) trim_zeros: bool,
+    pub(crate) separator: Option<Separator>,
+    pub(crate) long_units: bool,
+    pub(crate) rounding: Rounding,
+    pub(crate) significant_figures: Option<usize>,
+    pub(crate) auto_precision: bool,
+    pub(crate) group_separator: Option<char>,
+    pub(crate) decimal_separator: char,
+    pub(crate) remainder: bool,
+    pub(crate) labels: Option<&'static dyn UnitLabels>,
+    pub(crate) mixed_units: Option<usize>,
+    pub(crate) engineering: Option<Exponent>,
 }
 
 impl Display {
@@ -84,103 +696,1092 @@ impl Display {
         self
     }
 
-    /// Format using a short style and IEC (binary) units.
-    ///
-    /// E.g., `11.8M`.
-    ///
-    /// Designed to produce output compatible with `sort -h`.
-    #[must_use]
-    #[doc(alias = "binary")]
-    pub fn iec_short(mut self) -> Self {
-        self.format = Format::IecShort;
-        self
+    /// Format using a short style and IEC (binary) units.
+    ///
+    /// E.g., `11.8M`.
+    ///
+    /// Designed to produce output compatible with `sort -h`.
+    #[must_use]
+    #[doc(alias = "binary")]
+    pub fn iec_short(mut self) -> Self {
+        self.format = Format::IecShort;
+        self
+    }
+
+    /// Format using SI (decimal) units.
+    ///
+    /// E.g., `12.3 MB`.
+    #[must_use]
+    #[doc(alias = "decimal")]
+    pub fn si(mut self) -> Self {
+        self.format = Format::Si;
+        self
+    }
+
+    /// Format using a short style and SI (decimal) units.
+    ///
+    /// E.g., `12.3M`.
+    #[must_use]
+    #[doc(alias = "decimal")]
+    pub fn si_short(mut self) -> Self {
+        self.format = Format::SiShort;
+        self
+    }
+
+    /// Format using binary math with SI-style unit labels, as defined by JEDEC.
+    ///
+    /// E.g., `11.8 MB` for 11.8 mebibytes. This matches Windows Explorer and many firmware tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("1.5 GB", ByteSize::mib(1536).display().jedec().to_string());
+    /// ```
+    #[must_use]
+    pub fn jedec(mut self) -> Self {
+        self.format = Format::Jedec;
+        self
+    }
+
+    /// Format exactly like GNU coreutils' `-h` option, as used by `ls -lh`, `du -h`, and `df -h`.
+    ///
+    /// E.g., `4.0K`, `1.1M`, or `12G`. Sizes are rounded up, shown with one decimal below 10 and
+    /// none otherwise, and sizes below 1 KiB have no unit. Other display options, like precision,
+    /// are ignored so that output always matches coreutils.
+    ///
+    /// Note that `ls -lh` shows file sizes, while `du -h` shows disk usage, which is usually
+    /// rounded up to whole blocks. Format the corresponding size to match each tool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("1.1K", ByteSize::b(1025).display().coreutils().to_string());
+    /// assert_eq!("12M", ByteSize::kib(11_777).display().coreutils().to_string());
+    /// assert_eq!("512", ByteSize::b(512).display().coreutils().to_string());
+    /// ```
+    #[must_use]
+    #[doc(alias("ls", "du", "df"))]
+    pub fn coreutils(mut self) -> Self {
+        self.format = Format::Coreutils;
+        self
+    }
+
+    /// Format exactly like Windows Explorer.
+    ///
+    /// E.g., `1.43 MB` for 1.43 MiB. Uses binary math with SI-style labels, like
+    /// [`jedec()`](Self::jedec), but shows three significant digits, truncating the rest, and
+    /// switches to the next unit at 1000 rather than 1024. Sizes below 1 KiB are shown in bytes.
+    /// Other display options, like precision, are ignored so that output always matches Explorer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let display = |size: ByteSize| size.display().windows_explorer().to_string();
+    ///
+    /// assert_eq!("1.43 MB", display(ByteSize::kib(1469)));
+    /// assert_eq!("14.3 MB", display(ByteSize::kib(14_690)));
+    /// assert_eq!("0.97 MB", display(ByteSize::kib(1000)));
+    /// assert_eq!("512 bytes", display(ByteSize::b(512)));
+    /// ```
+    #[must_use]
+    #[doc(alias("windows", "explorer"))]
+    pub fn windows_explorer(mut self) -> Self {
+        self.format = Format::WindowsExplorer;
+        self
+    }
+
+    /// Format as a number of bits using IEC (binary) units.
+    ///
+    /// E.g., `94.4 Mibit`.
+    #[must_use]
+    #[doc(alias = "binary")]
+    pub fn iec_bits(mut self) -> Self {
+        self.format = Format::IecBits;
+        self
+    }
+
+    /// Format as a number of bits using SI (decimal) units.
+    ///
+    /// E.g., `98.4 Mbit`.
+    #[must_use]
+    #[doc(alias = "decimal")]
+    pub fn si_bits(mut self) -> Self {
+        self.format = Format::SiBits;
+        self
+    }
+
+    /// Format as a number of bits using a short style and IEC (binary) units.
+    ///
+    /// E.g., `94.4M`. Sizes under 1 kibibit keep the unit, e.g., `800bit`.
+    #[must_use]
+    #[doc(alias = "binary")]
+    pub fn iec_bits_short(mut self) -> Self {
+        self.format = Format::IecBitsShort;
+        self
+    }
+
+    /// Format as a number of bits using a short style and SI (decimal) units.
+    ///
+    /// E.g., `98.4M`. Sizes under 1 kilobit keep the unit, e.g., `800bit`.
+    #[must_use]
+    #[doc(alias = "decimal")]
+    pub fn si_bits_short(mut self) -> Self {
+        self.format = Format::SiBitsShort;
+        self
+    }
+
+    /// Applies a user's unit preference, e.g., loaded from a settings file.
+    ///
+    /// See [`UnitPreference`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, UnitPreference};
+    ///
+    /// let display = ByteSize::kib(1536).display().with_preference(UnitPreference::si());
+    /// assert_eq!("1.6 MB", display.to_string());
+    /// ```
+    #[must_use]
+    pub fn with_preference(self, preference: UnitPreference) -> Self {
+        preference.apply(self)
+    }
+
+    /// Sets the behavior of bit formats for sizes under 1 kilobit (or kibibit).
+    ///
+    /// By default, the exact number of bits is shown. See [`SubUnitBits`].
+    #[must_use]
+    pub fn sub_unit_bits(mut self, sub_unit_bits: SubUnitBits) -> Self {
+        self.sub_unit_bits = sub_unit_bits;
+        self
+    }
+
+    /// Always display in the given unit, regardless of magnitude.
+    ///
+    /// The unit's system (SI or IEC) takes precedence over the one chosen by the format, while
+    /// the short/long style is kept. E.g., `0.5 MiB` or `2048.0 MiB`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, Unit};
+    ///
+    /// let display = ByteSize::kib(512).display().in_unit(Unit::MebiByte);
+    /// assert_eq!("0.5 MiB", display.to_string());
+    ///
+    /// let display = ByteSize::gib(2).display().iec_short().in_unit(Unit::MebiByte);
+    /// assert_eq!("2048.0M", display.to_string());
+    /// ```
+    #[must_use]
+    pub fn in_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Spell out unit names, e.g., `1.5 gibibytes` or `2 megabytes`.
+    ///
+    /// Units are singular only when the displayed number is exactly `1`. A space is used as the
+    /// separator unless another is [set](Self::separator()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let display = ByteSize::mib(1536).display().long_units();
+    /// assert_eq!("1.5 gibibytes", display.to_string());
+    ///
+    /// let display = ByteSize::mb(2).display().si().long_units().trim_zeros();
+    /// assert_eq!("2 megabytes", display.to_string());
+    ///
+    /// let display = ByteSize::kib(1).display().long_units().trim_zeros();
+    /// assert_eq!("1 kibibyte", display.to_string());
+    /// ```
+    #[must_use]
+    pub fn long_units(mut self) -> Self {
+        self.long_units = true;
+        self
+    }
+
+    /// Sets the separator between the number and unit.
+    ///
+    /// By default, long styles use a space and short styles use no separator.
+    #[must_use]
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Removes trailing zeros from the fractional part, and the decimal point if nothing is left.
+    ///
+    /// E.g., `1 KiB` instead of `1.0 KiB`, or `2.5 MiB` instead of `2.50 MiB`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("1 KiB", ByteSize::kib(1).display().trim_zeros().to_string());
+    /// assert_eq!("2.5 MiB", format!("{:.2}", ByteSize::kib(2560).display().trim_zeros()));
+    /// ```
+    #[must_use]
+    pub fn trim_zeros(mut self) -> Self {
+        self.trim_zeros = true;
+        self
+    }
+
+    /// Show a fixed number of significant figures instead of a fixed number of decimal places.
+    ///
+    /// E.g., with 3 significant figures: `1.05 GiB`, `853 MiB`, or `12.4 KiB`. Sizes shown in
+    /// bytes are always exact. Takes precedence over the formatter's precision. A value of `0`
+    /// is treated as `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("853 MiB", ByteSize::mib(853).display().significant_figures(3).to_string());
+    /// assert_eq!("12.4 KiB", ByteSize::b(12_700).display().significant_figures(3).to_string());
+    /// ```
+    #[must_use]
+    pub fn significant_figures(mut self, figures: usize) -> Self {
+        self.significant_figures = Some(figures.max(1));
+        self
+    }
+
+    /// Show one decimal place only when the number is below 10, like `du -h` and `docker`.
+    ///
+    /// E.g., `9.8 GiB` but `124 GiB`. Takes precedence over the formatter's precision, but not
+    /// over [`significant_figures()`](Self::significant_figures()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("9.8 GiB", ByteSize::mib(10_035).display().auto_precision().to_string());
+    /// assert_eq!("124 GiB", ByteSize::gib(124).display().auto_precision().to_string());
+    /// ```
+    #[must_use]
+    #[doc(alias = "du")]
+    pub fn auto_precision(mut self) -> Self {
+        self.auto_precision = true;
+        self
+    }
+
+    /// Separate groups of thousands in the integer part of the number with `separator`.
+    ///
+    /// E.g., `1,023.5 MiB` or `12 345 B`. Grouped output cannot be parsed back into a
+    /// [`ByteSize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, Unit};
+    ///
+    /// let display = ByteSize::b(12_345).display().in_unit(Unit::Byte);
+    /// assert_eq!("12 345 B", display.group_separator(' ').to_string());
+    ///
+    /// let display = ByteSize::kib(1_048_064).display().in_unit(Unit::MebiByte);
+    /// assert_eq!("1,023.5 MiB", display.group_separator(',').to_string());
+    /// ```
+    #[must_use]
+    pub fn group_separator(mut self, separator: char) -> Self {
+        self.group_separator = Some(separator);
+        self
+    }
+
+    /// Use `separator` between the integer and fractional parts of the number.
+    ///
+    /// Defaults to `.`. E.g., `1,5 GiB` with `,`, as is common in European locales. Combines with
+    /// [`group_separator()`](Self::group_separator).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, Unit};
+    ///
+    /// let display = ByteSize::mib(1536).display().decimal_separator(',');
+    /// assert_eq!("1,5 GiB", display.to_string());
+    ///
+    /// let display = ByteSize::kib(1_048_064)
+    ///     .display()
+    ///     .in_unit(Unit::MebiByte)
+    ///     .group_separator('.')
+    ///     .decimal_separator(',');
+    /// assert_eq!("1.023,5 MiB", display.to_string());
+    /// ```
+    #[must_use]
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Use custom unit labels instead of the built-in ones.
+    ///
+    /// See [`UnitLabels`] for an example.
+    #[must_use]
+    pub fn labels(mut self, labels: &'static dyn UnitLabels) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Show the exact size as a sum of units, largest first.
+    ///
+    /// E.g., `1 GiB 512 MiB 3 KiB`. Units with a count of zero are skipped. Limit the number of
+    /// units shown with [`max_components()`](Self::max_components).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let size = ByteSize::gib(1) + ByteSize::mib(512) + ByteSize::kib(3);
+    /// assert_eq!("1 GiB 512 MiB 3 KiB", size.display().mixed_units().to_string());
+    /// assert_eq!("1 GiB 512 MiB", size.display().max_components(2).to_string());
+    /// ```
+    #[must_use]
+    pub fn mixed_units(mut self) -> Self {
+        self.mixed_units = Some(self.mixed_units.unwrap_or(usize::MAX));
+        self
+    }
+
+    /// Show the size as a sum of at most `max` units, largest first, truncating the rest.
+    ///
+    /// Implies [`mixed_units()`](Self::mixed_units). A `max` of `0` is treated as `1`.
+    #[must_use]
+    pub fn max_components(mut self, max: usize) -> Self {
+        self.mixed_units = Some(max.max(1));
+        self
+    }
+
+    /// Use engineering notation instead of unit prefixes, e.g., `1.5e9 B`.
+    ///
+    /// The exponent is a multiple of three and the number is between 1 and 1000. Sizes below
+    /// 1000 are shown without an exponent. Any unit set with [`in_unit()`](Self::in_unit) is
+    /// ignored, but [`iec_bits()`](Self::iec_bits) and [`si_bits()`](Self::si_bits) still count
+    /// bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, Exponent};
+    ///
+    /// let display = ByteSize::mb(1500).display().engineering(Exponent::E);
+    /// assert_eq!("1.5e9 B", display.to_string());
+    ///
+    /// let display = ByteSize::kb(42).display().engineering(Exponent::E).long_units();
+    /// assert_eq!("42.0e3 bytes", display.to_string());
+    /// ```
+    #[must_use]
+    pub fn engineering(mut self, exponent: Exponent) -> Self {
+        self.engineering = Some(exponent);
+        self
+    }
+
+    /// Show the nearest whole number of the chosen unit, plus or minus the remainder.
+    ///
+    /// E.g., `2.0 MiB − 4.0 KiB` for 2,093,056 bytes. Useful for sizes that are meant to be
+    /// aligned, to see where the slack went. The remainder is formatted with the same options but
+    /// always picks its own unit. Sizes that are already whole are shown as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let size = ByteSize::mib(2) - ByteSize::kib(4);
+    /// assert_eq!("2.0 MiB − 4.0 KiB", size.display().remainder().to_string());
+    ///
+    /// let size = ByteSize::gb(1) + ByteSize::b(512);
+    /// assert_eq!("1.0 GB + 512 B", size.display().si().remainder().to_string());
+    /// ```
+    #[must_use]
+    pub fn remainder(mut self) -> Self {
+        self.remainder = true;
+        self
+    }
+
+    /// Sets the rounding mode.
+    ///
+    /// By default, sizes are rounded to the nearest value with ties rounded to even. See
+    /// [`Rounding`].
+    #[must_use]
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets the behavior when the requested precision exceeds the meaningful digits of the size.
+    ///
+    /// By default, output is padded with zeros. See [`ExcessPrecision`].
+    #[must_use]
+    pub fn excess_precision(mut self, excess_precision: ExcessPrecision) -> Self {
+        self.excess_precision = excess_precision;
+        self
+    }
+
+    /// Writes the formatted size to `out` without allocating.
+    ///
+    /// Equivalent to `write!(out, "{display}")`, but skips the formatting machinery.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `out` fails, or if the size cannot be formatted with the chosen options,
+    /// e.g., due to [`ExcessPrecision::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let mut line = String::from("cache: ");
+    /// ByteSize::mb(42).display().si().write_to(&mut line)?;
+    /// assert_eq!("cache: 42.0 MB", line);
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn write_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.write_with_precision(out, None)
+    }
+}
+
+impl Display {
+    /// Writes the formatted size to `out`, using the default precision if `precision` is `None`.
+    ///
+    /// This is the single formatting core shared by all public display paths.
+    pub(crate) fn write_with_precision(
+        &self,
+        out: &mut impl fmt::Write,
+        precision: Option<usize>,
+    ) -> fmt::Result {
+        let bytes = self.byte_size.as_u64();
+
+        match self.format {
+            Format::Coreutils => return write_coreutils(out, bytes),
+            Format::WindowsExplorer => return write_windows_explorer(out, bytes),
+            _ => {}
+        }
+
+        let format = match self.unit {
+            Some(unit) => self.format.with_system_of(unit),
+            None => self.format,
+        };
+
+        let quantity = if format.is_bits() {
+            u128::from(bytes) * 8
+        } else {
+            u128::from(bytes)
+        };
+
+        let exp = match self.unit {
+            Some(unit) => unit.exponent(),
+            None => format.ideal_exponent(quantity),
+        };
+
+        let unit = format.unit();
+        let unit_separator = match (self.separator, self.long_units) {
+            (Some(separator), _) => separator.as_str(),
+            (None, true) => " ",
+            (None, false) => format.unit_separator(),
+        };
+        let precision = precision.unwrap_or(1);
+
+        if let Some(max_components) = self.mixed_units {
+            return self.write_mixed(out, format, quantity, unit_separator, max_components);
+        }
+
+        if let Some(exponent) = self.engineering {
+            return self.write_engineering(
+                out,
+                format,
+                quantity,
+                unit_separator,
+                precision,
+                exponent,
+            );
+        }
+
+        let exp = match (exp, format.is_bits(), self.sub_unit_bits) {
+            (0, true, SubUnitBits::Fraction) => 1,
+            (0, true, SubUnitBits::Error) => return Err(fmt::Error),
+            (exp, _, _) => exp,
+        };
+
+        if self.remainder && exp > 0 {
+            let divisor = unit.pow(exp as u32);
+            let divisor = if format.is_bits() {
+                divisor / 8
+            } else {
+                divisor
+            };
+
+            if let Some(nearest) = nearest_multiple(bytes, divisor) {
+                let whole = Display {
+                    byte_size: ByteSize(nearest),
+                    remainder: false,
+                    ..self.clone()
+                };
+                let rest = Display {
+                    byte_size: ByteSize(bytes.abs_diff(nearest)),
+                    unit: None,
+                    remainder: false,
+                    ..self.clone()
+                };
+
+                whole.write_with_precision(out, Some(precision))?;
+                out.write_str(if nearest > bytes { " \u{2212} " } else { " + " })?;
+                return rest.write_with_precision(out, Some(precision));
+            }
+        }
+
+        if exp == 0 {
+            let (quantity, bits) = match (format.is_bits(), self.sub_unit_bits) {
+                (true, SubUnitBits::Bytes) => (u128::from(bytes), false),
+                (bits, _) => (quantity, bits),
+            };
+
+            write_integer(out, quantity, self.group_separator)?;
+            out.write_str(unit_separator)?;
+            return self.write_unit(out, format, bits, 0, quantity != 1);
+        }
+
+        let mut exp = exp;
+
+        let number = loop {
+            let divisor = unit.pow(exp as u32);
+
+            let precision = match (self.significant_figures, self.auto_precision) {
+                (Some(figures), _) => significant_precision(quantity, divisor, figures),
+                (None, true) => usize::from(quantity < 10 * u128::from(divisor)),
+                (None, false) => precision,
+            };
+
+            let max_precision = format.byte_resolution_digits(exp);
+            let precision = match self.excess_precision {
+                ExcessPrecision::Pad => precision,
+                ExcessPrecision::Trim => precision.min(max_precision),
+                ExcessPrecision::Error if precision > max_precision => return Err(fmt::Error),
+                ExcessPrecision::Error => precision,
+            };
+
+            let fixed = |precision| {
+                Fixed::new(quantity, divisor, precision, self.rounding, self.trim_zeros)
+            };
+
+            let mut number = fixed(precision);
+
+            // rounding up can carry into a new digit (e.g., 9.996 to 10.00)
+            if let Some(figures) = self.significant_figures {
+                if number.significant_digits() > figures && precision > 0 {
+                    number = fixed(precision - 1);
+                }
+            } else if self.auto_precision && number.integer >= 10 && precision > 0 {
+                number = fixed(0);
+            }
+
+            // rounding up can also carry into the next unit (e.g., 1023.96 KiB to 1.0 MiB), which
+            // must be shown in that unit for the output to parse back to the same display
+            if self.unit.is_some()
+                || number.integer < u128::from(unit)
+                || exp == format.unit_prefixes().len()
+            {
+                break number;
+            }
+
+            exp += 1;
+        };
+
+        number.write_to(out, self.group_separator, self.decimal_separator)?;
+        out.write_str(unit_separator)?;
+        self.write_unit(out, format, format.is_bits(), exp, !number.is_one())
+    }
+
+    /// Writes `quantity` in engineering notation.
+    fn write_engineering(
+        &self,
+        out: &mut impl fmt::Write,
+        format: Format,
+        quantity: u128,
+        unit_separator: &str,
+        precision: usize,
+        exponent: Exponent,
+    ) -> fmt::Result {
+        let bits = format.is_bits();
+
+        let mut exp = match quantity.checked_ilog10() {
+            Some(log) => log - log % 3,
+            None => 0,
+        };
+
+        if exp == 0 {
+            write_integer(out, quantity, self.group_separator)?;
+            out.write_str(unit_separator)?;
+            return self.write_unit(out, format, bits, 0, quantity != 1);
+        }
+
+        let number = loop {
+            let divisor = 10_u64.pow(exp);
+
+            let precision = match self.significant_figures {
+                Some(figures) => significant_precision(quantity, divisor, figures),
+                None => precision,
+            };
+
+            // each fractional digit is a power of ten, so only `exp` of them can be non-zero
+            let max_precision = exp as usize;
+            let precision = match self.excess_precision {
+                ExcessPrecision::Pad => precision,
+                ExcessPrecision::Trim => precision.min(max_precision),
+                ExcessPrecision::Error if precision > max_precision => return Err(fmt::Error),
+                ExcessPrecision::Error => precision,
+            };
+
+            let number = Fixed::new(quantity, divisor, precision, self.rounding, self.trim_zeros);
+
+            // rounding up can carry into the next exponent (e.g., 999.96e3 to 1.0e6)
+            if number.integer < 1000 {
+                break number;
+            }
+
+            exp += 3;
+        };
+
+        number.write_to(out, self.group_separator, self.decimal_separator)?;
+
+        match exponent {
+            Exponent::E => write!(out, "e{exp}")?,
+            Exponent::TimesTen => write!(out, " \u{d7} 10^{exp}")?,
+        }
+
+        out.write_str(unit_separator)?;
+        self.write_unit(out, format, bits, 0, true)
+    }
+
+    /// Writes `quantity` as a sum of up to `max_components` units, largest first.
+    fn write_mixed(
+        &self,
+        out: &mut impl fmt::Write,
+        format: Format,
+        quantity: u128,
+        unit_separator: &str,
+        max_components: usize,
+    ) -> fmt::Result {
+        let unit = u128::from(format.unit());
+        let mut remaining = quantity;
+        let mut components = 0;
+
+        for exp in (0..=format.unit_prefixes().len()).rev() {
+            if components == max_components {
+                break;
+            }
+
+            let divisor = unit.pow(exp as u32);
+            let count = remaining / divisor;
+
+            if count == 0 {
+                continue;
+            }
+
+            if components > 0 {
+                out.write_char(' ')?;
+            }
+
+            write_integer(out, count, self.group_separator)?;
+            out.write_str(unit_separator)?;
+            self.write_unit(out, format, format.is_bits(), exp, count != 1)?;
+
+            remaining -= count * divisor;
+            components += 1;
+        }
+
+        if components == 0 {
+            out.write_char('0')?;
+            out.write_str(unit_separator)?;
+            self.write_unit(out, format, format.is_bits(), 0, true)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the unit label for the prefix exponent `exp`.
+    ///
+    /// `plural` is only relevant to long unit names.
+    fn write_unit(
+        &self,
+        out: &mut impl fmt::Write,
+        format: Format,
+        bits: bool,
+        exp: usize,
+        plural: bool,
+    ) -> fmt::Result {
+        if let Some(labels) = self.labels {
+            out.write_str(labels.prefix(exp, format.has_iec_labels()))?;
+            out.write_str(labels.suffix(bits, plural))
+        } else if self.long_units {
+            if exp > 0 {
+                out.write_str(format.long_prefixes()[exp - 1])?;
+            }
+
+            out.write_str(if bits { "bit" } else { "byte" })?;
+
+            if plural {
+                out.write_char('s')?;
+            }
+
+            Ok(())
+        } else if exp == 0 {
+            out.write_str(if bits { "bit" } else { "B" })
+        } else {
+            let unit_prefix = format.unit_prefixes()[exp - 1] as char;
+            let unit_suffix = format.unit_suffix();
+            write!(out, "{unit_prefix}{unit_suffix}")
+        }
+    }
+}
+
+impl fmt::Display for Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.width().is_none() {
+            // allocation-free fast path for when no width is specified
+            self.write_with_precision(f, f.precision())
+        } else {
+            let mut buf = String::new();
+            self.write_with_precision(&mut buf, f.precision())?;
+            pad(f, &buf)
+        }
+    }
+}
+
+/// Writes `s` to `f`, honoring the formatter's width, fill, and alignment.
+///
+/// Unlike [`fmt::Formatter::pad()`], the formatter's precision is not used to truncate `s` since,
+/// for sizes, it has already been applied to the number.
+pub(crate) fn pad(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    let len = s.chars().count();
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+    let (pre, post) = match f.align() {
+        None | Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+    };
+
+    let fill = f.fill();
+
+    for _ in 0..pre {
+        f.write_char(fill)?;
+    }
+
+    f.write_str(s)?;
+
+    for _ in 0..post {
+        f.write_char(fill)?;
+    }
+
+    Ok(())
+}
+
+/// Maximum number of non-zero fractional digits a [`Fixed`] can hold.
+///
+/// Enough to show a single byte in exbibytes exactly.
+const MAX_EXACT_DIGITS: usize = 60;
+
+/// Fixed-point decimal representation of a quotient, ready for writing.
+#[derive(Debug, Clone)]
+struct Fixed {
+    integer: u128,
+    digits: [u8; MAX_EXACT_DIGITS],
+    len: usize,
+    padding: usize,
+}
+
+impl Fixed {
+    /// Computes `value / divisor` with `precision` fractional digits, rounded using `rounding`.
+    ///
+    /// If `trim_zeros` is set, trailing zeros in the fractional part are omitted.
+    ///
+    /// Uses exact integer math, so the output never contains floating point artifacts. The
+    /// decimal expansion of `1 / divisor` must terminate within [`MAX_EXACT_DIGITS`], which holds
+    /// for all SI and IEC unit factors.
+    fn new(
+        value: u128,
+        divisor: u64,
+        precision: usize,
+        rounding: Rounding,
+        trim_zeros: bool,
+    ) -> Self {
+        let divisor = u128::from(divisor);
+        let mut integer = value / divisor;
+        let mut remainder = value % divisor;
+
+        // past MAX_EXACT_DIGITS the remainder is always zero so the digits can be padded instead
+        let mut digits = [0_u8; MAX_EXACT_DIGITS];
+        let len = precision.min(MAX_EXACT_DIGITS);
+
+        for digit in &mut digits[..len] {
+            remainder *= 10;
+            *digit = (remainder / divisor) as u8;
+            remainder %= divisor;
+        }
+
+        let round_up = match (rounding, (remainder * 2).cmp(&divisor)) {
+            (Rounding::Floor, _) => false,
+            (Rounding::Ceil, _) => remainder > 0,
+            (Rounding::HalfUp | Rounding::HalfEven, Ordering::Less) => false,
+            (Rounding::HalfUp | Rounding::HalfEven, Ordering::Greater) => true,
+            (Rounding::HalfUp, Ordering::Equal) => true,
+            (Rounding::HalfEven, Ordering::Equal) => match digits[..len].last() {
+                Some(&last) => last % 2 == 1,
+                None => integer % 2 == 1,
+            },
+        };
+
+        if round_up {
+            match digits[..len].iter().rposition(|&digit| digit < 9) {
+                Some(idx) => {
+                    digits[idx] += 1;
+                    digits[idx + 1..len].fill(0);
+                }
+                None => {
+                    digits[..len].fill(0);
+                    integer += 1;
+                }
+            }
+        }
+
+        if trim_zeros {
+            let len = digits[..len]
+                .iter()
+                .rposition(|&digit| digit != 0)
+                .map_or(0, |idx| idx + 1);
+
+            Fixed {
+                integer,
+                digits,
+                len,
+                padding: 0,
+            }
+        } else {
+            Fixed {
+                integer,
+                digits,
+                len,
+                padding: precision - len,
+            }
+        }
+    }
+
+    /// Returns the number of significant digits that would be written.
+    fn significant_digits(&self) -> usize {
+        let fraction = self.len + self.padding;
+
+        match self.integer.checked_ilog10() {
+            Some(log) => log as usize + 1 + fraction,
+            None => match self.digits[..self.len].iter().position(|&digit| digit != 0) {
+                Some(leading_zeros) => fraction - leading_zeros,
+                None => 0,
+            },
+        }
+    }
+
+    /// Returns true if the written number would be exactly `1`, without a fractional part.
+    fn is_one(&self) -> bool {
+        self.integer == 1 && self.len == 0 && self.padding == 0
+    }
+
+    fn write_to(
+        &self,
+        out: &mut impl fmt::Write,
+        group_separator: Option<char>,
+        decimal_separator: char,
+    ) -> fmt::Result {
+        write_integer(out, self.integer, group_separator)?;
+
+        if self.len > 0 || self.padding > 0 {
+            out.write_char(decimal_separator)?;
+
+            for &digit in &self.digits[..self.len] {
+                out.write_char(char::from(b'0' + digit))?;
+            }
+
+            for _ in 0..self.padding {
+                out.write_char('0')?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes `bytes` the way GNU coreutils' `human_readable()` does with ceiling rounding.
+fn write_coreutils(out: &mut impl fmt::Write, bytes: u64) -> fmt::Result {
+    let unit = Format::Coreutils.unit();
+    let prefixes = Format::Coreutils.unit_prefixes();
+
+    let mut exp = 0;
+    let mut amount = bytes;
+
+    while amount >= unit && exp < prefixes.len() {
+        amount /= unit;
+        exp += 1;
+    }
+
+    if exp == 0 {
+        return write!(out, "{bytes}");
     }
 
-    /// Format using SI (decimal) units.
-    ///
-    /// E.g., `12.3 MB`.
-    #[must_use]
-    #[doc(alias = "decimal")]
-    pub fn si(mut self) -> Self {
-        self.format = Format::Si;
-        self
+    let divisor = unit.pow(exp as u32);
+    let remainder = bytes - amount * divisor;
+
+    let mut tenths = if amount < 10 {
+        let tenths = (u128::from(remainder) * 10).div_ceil(u128::from(divisor)) as u64;
+
+        if tenths == 10 {
+            amount += 1;
+            Some(0)
+        } else {
+            Some(tenths)
+        }
+    } else {
+        if remainder > 0 {
+            amount += 1;
+        }
+
+        None
+    };
+
+    // rounding up can reach the next unit, e.g., 1023.1K becomes 1.0M
+    if amount == unit && exp < prefixes.len() {
+        amount = 1;
+        exp += 1;
+        tenths = Some(0);
     }
 
-    /// Format using a short style and SI (decimal) units.
-    ///
-    /// E.g., `12.3M`.
-    #[must_use]
-    #[doc(alias = "decimal")]
-    pub fn si_short(mut self) -> Self {
-        self.format = Format::SiShort;
-        self
+    let prefix = char::from(prefixes[exp - 1]);
+
+    match tenths {
+        Some(tenths) if amount < 10 => write!(out, "{amount}.{tenths}{prefix}"),
+        _ => write!(out, "{amount}{prefix}"),
     }
 }
 
-impl fmt::Display for Display {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let bytes = self.byte_size.as_u64();
+/// Writes `bytes` the way Windows' `StrFormatByteSize()` does.
+fn write_windows_explorer(out: &mut impl fmt::Write, bytes: u64) -> fmt::Result {
+    let format = Format::WindowsExplorer;
+    let unit = format.unit();
 
-        let unit = self.format.unit();
-        #[allow(unused_variables)] // used in std contexts
-        let unit_base = self.format.unit_base();
+    if bytes < unit {
+        return write!(out, "{bytes} bytes");
+    }
 
-        let unit_prefixes = self.format.unit_prefixes();
-        let unit_separator = self.format.unit_separator();
-        let unit_suffix = self.format.unit_suffix();
-        let precision = f.precision().unwrap_or(1);
+    // switch units at 1000, not 1024, so there are never more than three integer digits
+    let mut exp = 1;
+    while u128::from(bytes) >= 1000 * u128::from(unit).pow(exp as u32) {
+        exp += 1;
+    }
 
-        if bytes < unit {
-            write!(f, "{bytes}{unit_separator}B")?;
-        } else {
-            let size = bytes as f64;
+    let divisor = unit.pow(exp as u32);
+    let precision = match bytes / divisor {
+        0..=9 => 2,
+        10..=99 => 1,
+        _ => 0,
+    };
 
-            #[cfg(feature = "std")]
-            let exp = ideal_unit_std(size, unit_base);
+    let number = Fixed::new(
+        u128::from(bytes),
+        divisor,
+        precision,
+        Rounding::Floor,
+        false,
+    );
+    number.write_to(out, None, '.')?;
 
-            #[cfg(not(feature = "std"))]
-            let exp = ideal_unit_no_std(size, unit);
+    let unit_prefix = char::from(format.unit_prefixes()[exp - 1]);
+    write!(out, " {unit_prefix}B")
+}
 
-            let unit_prefix = unit_prefixes[exp - 1] as char;
+/// Rounds `bytes` to the nearest multiple of `divisor`, rounding halfway cases up.
+///
+/// Returns `None` if `bytes` is already a multiple or the nearest multiple does not fit in a `u64`.
+fn nearest_multiple(bytes: u64, divisor: u64) -> Option<u64> {
+    let remainder = bytes % divisor;
 
-            write!(
-                f,
-                "{:.precision$}{unit_separator}{unit_prefix}{unit_suffix}",
-                (size / unit.pow(exp as u32) as f64),
-            )?;
-        }
+    if remainder == 0 {
+        return None;
+    }
 
-        Ok(())
+    if remainder * 2 >= divisor {
+        bytes.checked_add(divisor - remainder)
+    } else {
+        Some(bytes - remainder)
     }
 }
 
-#[allow(dead_code)] // used in no-std contexts
-fn ideal_unit_no_std(size: f64, unit: u64) -> usize {
-    assert!(size >= unit as f64, "only called when bytes >= unit");
+/// Writes `value` in decimal, separating groups of thousands with `group_separator`, if set.
+fn write_integer(
+    out: &mut impl fmt::Write,
+    value: u128,
+    group_separator: Option<char>,
+) -> fmt::Result {
+    let Some(separator) = group_separator else {
+        return write!(out, "{value}");
+    };
 
-    let mut ideal_prefix = 0;
-    let mut ideal_size = size;
+    // u128::MAX has 39 digits
+    let mut digits = [0_u8; 39];
+    let mut len = 0;
+    let mut remaining = value;
 
     loop {
-        ideal_prefix += 1;
-        ideal_size /= unit as f64;
+        digits[len] = (remaining % 10) as u8;
+        len += 1;
+        remaining /= 10;
 
-        if ideal_size < unit as f64 {
+        if remaining == 0 {
             break;
         }
     }
 
-    ideal_prefix
+    for idx in (0..len).rev() {
+        out.write_char(char::from(b'0' + digits[idx]))?;
+
+        if idx > 0 && idx % 3 == 0 {
+            out.write_char(separator)?;
+        }
+    }
+
+    Ok(())
 }
 
-#[cfg(feature = "std")]
-#[allow(dead_code)] // used in std contexts
-fn ideal_unit_std(size: f64, unit_base: f64) -> usize {
-    assert!(size.ln() >= unit_base, "only called when bytes >= unit");
+/// Returns the precision needed to show `value / divisor` with `figures` significant figures.
+fn significant_precision(value: u128, divisor: u64, figures: usize) -> usize {
+    let divisor = u128::from(divisor);
+
+    if value == 0 {
+        return figures - 1;
+    }
+
+    match (value / divisor).checked_ilog10() {
+        Some(log) => figures.saturating_sub(log as usize + 1),
+        None => {
+            // count zeros between the decimal point and first significant digit
+            let mut leading_zeros = 0;
+            let mut scaled = value * 10;
 
-    match (size.ln() / unit_base) as usize {
-        0 => unreachable!(),
-        e => e,
+            while scaled < divisor {
+                leading_zeros += 1;
+                scaled *= 10;
+            }
+
+            leading_zeros + figures
+        }
     }
 }
 
@@ -190,42 +1791,87 @@ mod tests {
 
     use super::*;
 
-    #[cfg(feature = "std")]
+    #[cfg(all(feature = "std", not(feature = "float-unit-selection")))]
     quickcheck::quickcheck! {
         #[test]
-        fn ideal_unit_selection_std_no_std_iec(bytes: ByteSize) -> bool {
-            if bytes.0 < 1025 {
-                return true;
-            }
+        fn ideal_exponent_is_exact(bytes: ByteSize) -> bool {
+            [Format::Iec, Format::Si, Format::IecBits, Format::SiBits]
+                .into_iter()
+                .all(|format| {
+                    let quantity = if format.is_bits() {
+                        u128::from(bytes.0) * 8
+                    } else {
+                        u128::from(bytes.0)
+                    };
 
-            let size = bytes.0 as f64;
+                    let unit = u128::from(format.unit());
+                    let exp = format.ideal_exponent(quantity) as u32;
 
-            ideal_unit_std(size, crate::LN_KIB) == ideal_unit_no_std(size, crate::KIB)
+                    let at_least_one = exp == 0 || quantity >= unit.pow(exp);
+                    let below_next = exp as usize == format.unit_prefixes().len()
+                        || quantity < unit.pow(exp + 1);
+
+                    at_least_one && below_next
+                })
         }
+    }
 
-        #[test]
-        fn ideal_unit_selection_std_no_std_si(bytes: ByteSize) -> bool {
-            if bytes.0 < 1025 {
-                return true;
-            }
+    #[cfg(not(feature = "float-unit-selection"))]
+    #[test]
+    fn ideal_exponent() {
+        assert_eq!(0, Format::Iec.ideal_exponent(0));
+        assert_eq!(0, Format::Iec.ideal_exponent(1023));
+        assert_eq!(1, Format::Iec.ideal_exponent(1024));
+        assert_eq!(1, Format::Si.ideal_exponent(1000));
+        assert_eq!(0, Format::Si.ideal_exponent(999));
+
+        // one byte below a unit boundary, which f64 rounds up to the boundary itself
+        let eib = u128::from(crate::EIB);
+        assert_eq!(5, Format::Iec.ideal_exponent(eib - 1));
+        assert_eq!(6, Format::Iec.ideal_exponent(eib));
 
-            let size = bytes.0 as f64;
+        for exp in 1..=6 {
+            let kib_boundary = 1024_u128.pow(exp as u32);
+            assert_eq!(exp - 1, Format::Iec.ideal_exponent(kib_boundary - 1));
+            assert_eq!(exp, Format::IecBits.ideal_exponent(kib_boundary));
 
-            ideal_unit_std(size, crate::LN_KB) == ideal_unit_no_std(size, crate::KB)
+            let kb_boundary = 1000_u128.pow(exp as u32);
+            assert_eq!(exp - 1, Format::Si.ideal_exponent(kb_boundary - 1));
+            assert_eq!(exp, Format::SiBits.ideal_exponent(kb_boundary));
         }
+
+        // larger quantities stay in the largest unit
+        assert_eq!(6, Format::Iec.ideal_exponent(u128::from(u64::MAX) * 8));
+        assert_eq!(6, Format::Si.ideal_exponent(u128::MAX));
+    }
+
+    #[cfg(feature = "float-unit-selection")]
+    #[test]
+    fn ideal_exponent_float() {
+        assert_eq!(0, Format::Iec.ideal_exponent(0));
+        assert_eq!(0, Format::Iec.ideal_exponent(1023));
+        assert_eq!(1, Format::Iec.ideal_exponent(1024));
+        assert_eq!(1, Format::Si.ideal_exponent(1000));
+        assert_eq!(3, Format::Si.ideal_exponent(u128::from(crate::GB) * 5));
+
+        // one byte below a unit boundary rounds up to the boundary itself as an f64
+        let eib = u128::from(crate::EIB);
+        assert_eq!(6, Format::Iec.ideal_exponent(eib - 1));
+
+        assert_eq!(6, Format::Si.ideal_exponent(u128::MAX));
     }
 
     #[test]
     fn to_string_iec() {
         let display = Display {
-            byte_size: ByteSize::gib(1),
             format: Format::Iec,
+            ..ByteSize::gib(1).display()
         };
         assert_eq!("1.0 GiB", display.to_string());
 
         let display = Display {
-            byte_size: ByteSize::gb(1),
             format: Format::Iec,
+            ..ByteSize::gb(1).display()
         };
         assert_eq!("953.7 MiB", display.to_string());
     }
@@ -233,14 +1879,14 @@ mod tests {
     #[test]
     fn to_string_si() {
         let display = Display {
-            byte_size: ByteSize::gib(1),
             format: Format::Si,
+            ..ByteSize::gib(1).display()
         };
         assert_eq!("1.1 GB", display.to_string());
 
         let display = Display {
-            byte_size: ByteSize::gb(1),
             format: Format::Si,
+            ..ByteSize::gb(1).display()
         };
         assert_eq!("1.0 GB", display.to_string());
     }
@@ -248,21 +1894,28 @@ mod tests {
     #[test]
     fn to_string_short() {
         let display = Display {
-            byte_size: ByteSize::gib(1),
             format: Format::IecShort,
+            ..ByteSize::gib(1).display()
         };
         assert_eq!("1.0G", display.to_string());
 
         let display = Display {
-            byte_size: ByteSize::gb(1),
             format: Format::IecShort,
+            ..ByteSize::gb(1).display()
         };
         assert_eq!("953.7M", display.to_string());
     }
 
     #[track_caller]
     fn assert_to_string(expected: &str, byte_size: ByteSize, format: Format) {
-        assert_eq!(expected, Display { byte_size, format }.to_string());
+        assert_eq!(
+            expected,
+            Display {
+                format,
+                ..byte_size.display()
+            }
+            .to_string()
+        );
     }
 
     #[test]
@@ -301,5 +1954,934 @@ mod tests {
         assert_eq!("1.9 GiB".to_string(), format!("{size}"));
         assert_eq!("2 GiB".to_string(), format!("{size:.0}"));
         assert_eq!("1.86328 GiB".to_string(), format!("{size:.5}"));
+        assert_eq!("1.8632812500 GiB".to_string(), format!("{size:.10}"));
+    }
+
+    #[test]
+    fn exact_digits() {
+        let size = ByteSize(u64::MAX);
+        assert_eq!("16.0 EiB", format!("{size}"));
+        assert_eq!("15.9999999999999999991 EiB", format!("{size:.19}"));
+
+        let size = ByteSize(crate::EB + 1);
+        assert_eq!(
+            "1.000000000000000001 EB",
+            format!("{:.18}", size.display().si()),
+        );
+
+        // ties are rounded to even
+        assert_eq!("1.2 KiB", format!("{}", ByteSize::b(1_280)));
+        assert_eq!("1.4 KiB", format!("{}", ByteSize::b(1_434)));
+        assert_eq!("1.0 kB", format!("{}", ByteSize::b(1_050).display().si()));
+        assert_eq!("1.2 kB", format!("{}", ByteSize::b(1_150).display().si()));
+        assert_eq!("2 KiB", format!("{:.0}", ByteSize::b(2_560)));
+        assert_eq!("4 KiB", format!("{:.0}", ByteSize::b(3_584)));
+    }
+
+    #[test]
+    fn forced_unit() {
+        let size = ByteSize::kib(512);
+        assert_eq!(
+            "0.5 MiB",
+            size.display().in_unit(Unit::MebiByte).to_string()
+        );
+        assert_eq!(
+            "0.5 MiB",
+            size.display().si().in_unit(Unit::MebiByte).to_string()
+        );
+        assert_eq!(
+            "0.5M",
+            size.display()
+                .si_short()
+                .in_unit(Unit::MebiByte)
+                .to_string()
+        );
+        assert_eq!(
+            "524.3 kB",
+            size.display().in_unit(Unit::KiloByte).to_string()
+        );
+        assert_eq!(
+            "524.288k",
+            format!("{:.3}", size.display().iec_short().in_unit(Unit::KiloByte))
+        );
+        assert_eq!("524288 B", size.display().in_unit(Unit::Byte).to_string());
+
+        let size = ByteSize::gib(2);
+        assert_eq!(
+            "2048.0 MiB",
+            size.display().in_unit(Unit::MebiByte).to_string()
+        );
+        assert_eq!("0.0 PB", size.display().in_unit(Unit::PetaByte).to_string());
+        assert_eq!(
+            "0.000002147 PB",
+            format!("{:.9}", size.display().in_unit(Unit::PetaByte))
+        );
+
+        assert_eq!(
+            "0.0 EiB",
+            ByteSize(0).display().in_unit(Unit::ExbiByte).to_string()
+        );
+        assert_eq!(
+            "16.0 EiB",
+            ByteSize(u64::MAX)
+                .display()
+                .in_unit(Unit::ExbiByte)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!("0 bit", ByteSize(0).display().si_bits().to_string());
+        assert_eq!("800 bit", ByteSize(100).display().si_bits().to_string());
+        assert_eq!("1.0 kbit", ByteSize(125).display().si_bits().to_string());
+        assert_eq!("1000 bit", ByteSize(125).display().iec_bits().to_string());
+        assert_eq!("1.0 Kibit", ByteSize(128).display().iec_bits().to_string());
+        assert_eq!(
+            "8.0 Mibit",
+            ByteSize::mib(1).display().iec_bits().to_string()
+        );
+        assert_eq!("8.4 Mbit", ByteSize::mib(1).display().si_bits().to_string());
+        assert_eq!(
+            "128.0 Eibit",
+            ByteSize(u64::MAX).display().iec_bits().to_string()
+        );
+        assert_eq!(
+            "147.6 Ebit",
+            ByteSize(u64::MAX).display().si_bits().to_string()
+        );
+
+        let display = ByteSize::mib(1)
+            .display()
+            .iec_bits()
+            .in_unit(Unit::KiloByte);
+        assert_eq!("8388.6 kbit", display.to_string());
+    }
+
+    #[test]
+    fn bits_short() {
+        assert_eq!("0bit", ByteSize(0).display().si_bits_short().to_string());
+        assert_eq!(
+            "800bit",
+            ByteSize(100).display().si_bits_short().to_string()
+        );
+        assert_eq!("1.0k", ByteSize(125).display().si_bits_short().to_string());
+        assert_eq!(
+            "1000bit",
+            ByteSize(125).display().iec_bits_short().to_string()
+        );
+        assert_eq!("1.0K", ByteSize(128).display().iec_bits_short().to_string());
+        assert_eq!(
+            "8.4M",
+            ByteSize::mib(1).display().si_bits_short().to_string()
+        );
+        assert_eq!(
+            "8.0M",
+            ByteSize::mib(1).display().iec_bits_short().to_string()
+        );
+        assert_eq!(
+            "128.0E",
+            ByteSize(u64::MAX).display().iec_bits_short().to_string()
+        );
+        assert_eq!(
+            "8.4 megabits",
+            ByteSize::mib(1)
+                .display()
+                .si_bits_short()
+                .long_units()
+                .to_string()
+        );
+
+        let display = ByteSize::mib(1)
+            .display()
+            .iec_bits_short()
+            .in_unit(Unit::KiloByte);
+        assert_eq!("8388.6k", display.to_string());
+
+        let display = ByteSize(100)
+            .display()
+            .si_bits_short()
+            .sub_unit_bits(SubUnitBits::Fraction);
+        assert_eq!("0.8k", display.to_string());
+    }
+
+    #[test]
+    fn sub_unit_bits() {
+        let size = ByteSize(100);
+
+        let display = size
+            .display()
+            .si_bits()
+            .sub_unit_bits(SubUnitBits::Fraction);
+        assert_eq!("0.8 kbit", display.to_string());
+        assert_eq!("0.800 kbit", format!("{display:.3}"));
+
+        let display = size
+            .display()
+            .iec_bits()
+            .sub_unit_bits(SubUnitBits::Fraction);
+        assert_eq!("0.78125 Kibit", format!("{display:.5}"));
+
+        let display = size.display().si_bits().sub_unit_bits(SubUnitBits::Bytes);
+        assert_eq!("100 B", display.to_string());
+
+        let display = size.display().si_bits().sub_unit_bits(SubUnitBits::Error);
+        let mut buf = alloc::string::String::new();
+        fmt::write(&mut buf, format_args!("{display}")).unwrap_err();
+
+        // only applies under 1 kilobit
+        let display = ByteSize::kb(1)
+            .display()
+            .si_bits()
+            .sub_unit_bits(SubUnitBits::Error);
+        assert_eq!("8.0 kbit", display.to_string());
+
+        // only applies to bit formats
+        let display = size.display().sub_unit_bits(SubUnitBits::Error);
+        assert_eq!("100 B", display.to_string());
+    }
+
+    #[test]
+    fn trim_zeros() {
+        let display = ByteSize::kib(1).display().trim_zeros();
+        assert_eq!("1 KiB", display.to_string());
+        assert_eq!("1 KiB", format!("{display:.5}"));
+
+        let display = ByteSize::kib(2560).display().trim_zeros();
+        assert_eq!("2.5 MiB", format!("{display:.2}"));
+        assert_eq!("2.5 MiB", format!("{display:.20}"));
+        assert_eq!("2 MiB", format!("{display:.0}"));
+
+        let display = ByteSize::b(1_999_999).display().si().trim_zeros();
+        assert_eq!("2 MB", format!("{display:.3}"));
+        assert_eq!("1.999999 MB", format!("{display:.7}"));
+
+        assert_eq!("215 B", ByteSize::b(215).display().trim_zeros().to_string());
+    }
+
+    #[test]
+    fn separators() {
+        let size = ByteSize::kib(42);
+        assert_eq!(
+            "42.0KiB",
+            size.display().separator(Separator::None).to_string()
+        );
+        assert_eq!(
+            "42.0 K",
+            size.display()
+                .iec_short()
+                .separator(Separator::Space)
+                .to_string()
+        );
+        assert_eq!(
+            "42.0\u{202f}KiB",
+            size.display()
+                .separator(Separator::NarrowNoBreakSpace)
+                .to_string(),
+        );
+
+        let size = ByteSize::b(42);
+        assert_eq!("42B", size.display().separator(Separator::None).to_string());
+        assert_eq!(
+            "336\u{202f}bit",
+            size.display()
+                .si_bits()
+                .separator(Separator::NarrowNoBreakSpace)
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn long_units() {
+        let display = |size: ByteSize| size.display().long_units();
+
+        assert_eq!("0 bytes", display(ByteSize(0)).to_string());
+        assert_eq!("1 byte", display(ByteSize(1)).to_string());
+        assert_eq!("215 bytes", display(ByteSize(215)).to_string());
+        assert_eq!("1.0 kibibytes", display(ByteSize::kib(1)).to_string());
+        assert_eq!(
+            "1 kibibyte",
+            display(ByteSize::kib(1)).trim_zeros().to_string()
+        );
+        assert_eq!("1 kibibyte", format!("{:.0}", display(ByteSize::kib(1))));
+        assert_eq!("1.5 gibibytes", display(ByteSize::mib(1536)).to_string());
+        assert_eq!(
+            "2 megabytes",
+            display(ByteSize::mb(2)).si().trim_zeros().to_string()
+        );
+        assert_eq!("16.0 exbibytes", display(ByteSize(u64::MAX)).to_string());
+        assert_eq!(
+            "2 gigabytes",
+            display(ByteSize::gb(2)).si_short().trim_zeros().to_string()
+        );
+
+        assert_eq!("8 bits", display(ByteSize(1)).si_bits().to_string());
+        assert_eq!(
+            "8.0 kilobits",
+            display(ByteSize::kb(1)).si_bits().to_string()
+        );
+        assert_eq!(
+            "1 byte",
+            display(ByteSize(1))
+                .si_bits()
+                .sub_unit_bits(SubUnitBits::Bytes)
+                .to_string(),
+        );
+
+        assert_eq!(
+            "2.0\u{202f}mebibytes",
+            display(ByteSize::mib(2))
+                .separator(Separator::NarrowNoBreakSpace)
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn rounding_modes() {
+        #[track_caller]
+        fn assert_rounding(expected: [&str; 4], size: ByteSize) {
+            let modes = [
+                Rounding::Floor,
+                Rounding::Ceil,
+                Rounding::HalfUp,
+                Rounding::HalfEven,
+            ];
+
+            for (expected, rounding) in expected.into_iter().zip(modes) {
+                let display = size.display().si().rounding(rounding);
+                assert_eq!(expected, display.to_string(), "{rounding:?}");
+            }
+        }
+
+        // floor, ceil, half-up, half-even
+        assert_rounding(["1.0 kB", "1.0 kB", "1.0 kB", "1.0 kB"], ByteSize::b(1_000));
+        assert_rounding(["1.0 kB", "1.1 kB", "1.0 kB", "1.0 kB"], ByteSize::b(1_001));
+        assert_rounding(["1.0 kB", "1.1 kB", "1.1 kB", "1.0 kB"], ByteSize::b(1_050));
+        assert_rounding(["1.1 kB", "1.2 kB", "1.2 kB", "1.2 kB"], ByteSize::b(1_150));
+        assert_rounding(["1.0 kB", "1.1 kB", "1.1 kB", "1.1 kB"], ByteSize::b(1_051));
+        assert_rounding(
+            ["999.9 kB", "1.0 MB", "1.0 MB", "1.0 MB"],
+            ByteSize::b(999_999),
+        );
+
+        let display = ByteSize::b(2_500).display().si().rounding(Rounding::HalfUp);
+        assert_eq!("3 kB", format!("{display:.0}"));
+        let display = ByteSize::b(2_500)
+            .display()
+            .si()
+            .rounding(Rounding::HalfEven);
+        assert_eq!("2 kB", format!("{display:.0}"));
+    }
+
+    #[test]
+    fn rounding_ties_at_each_unit() {
+        #[track_caller]
+        fn assert_tie(half_even: &str, half_up: &str, display: Display) {
+            assert_eq!(half_even, display.clone().to_string(), "default");
+            assert_eq!(
+                half_even,
+                display.clone().rounding(Rounding::HalfEven).to_string(),
+                "HalfEven",
+            );
+            assert_eq!(
+                half_up,
+                display.rounding(Rounding::HalfUp).to_string(),
+                "HalfUp",
+            );
+        }
+
+        // x.25 and x.75 are exact ties at one decimal place, in every unit
+        for (exp, unit) in ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]
+            .into_iter()
+            .enumerate()
+        {
+            let factor = 1_u64 << (10 * exp);
+
+            assert_tie(
+                &format!("1.2 {unit}"),
+                &format!("1.3 {unit}"),
+                ByteSize(1_280 * factor).display(),
+            );
+            assert_tie(
+                &format!("1.8 {unit}"),
+                &format!("1.8 {unit}"),
+                ByteSize(1_792 * factor).display(),
+            );
+        }
+
+        for (exp, unit) in ["kB", "MB", "GB", "TB", "PB", "EB"].into_iter().enumerate() {
+            let factor = 1000_u64.pow(exp as u32);
+
+            assert_tie(
+                &format!("2.2 {unit}"),
+                &format!("2.3 {unit}"),
+                ByteSize(2_250 * factor).display().si(),
+            );
+            assert_tie(
+                &format!("2.4 {unit}"),
+                &format!("2.4 {unit}"),
+                ByteSize(2_350 * factor).display().si(),
+            );
+        }
+
+        // one byte either side of a tie is not affected by the rounding mode
+        assert_tie("1.2 KiB", "1.2 KiB", ByteSize(1_279).display());
+        assert_tie("1.3 KiB", "1.3 KiB", ByteSize(1_281).display());
+    }
+
+    #[test]
+    fn rounding_half_even_does_not_drift() {
+        // 1.5 KiB, 2.5 KiB, ..., 10.5 KiB, which sum to 60 KiB
+        let sizes = (1..=10).map(|kib| ByteSize::kib(kib) + ByteSize::b(512));
+
+        let displayed_sum = |rounding| {
+            sizes
+                .clone()
+                .map(|size| {
+                    let display = size.display().rounding(rounding);
+                    let string = format!("{display:.0}");
+                    string.trim_end_matches(" KiB").parse::<u64>().unwrap()
+                })
+                .sum::<u64>()
+        };
+
+        assert_eq!(60, displayed_sum(Rounding::HalfEven));
+        assert_eq!(65, displayed_sum(Rounding::HalfUp));
+    }
+
+    #[test]
+    fn auto_precision() {
+        #[track_caller]
+        fn assert_auto(expected: &str, size: ByteSize) {
+            assert_eq!(expected, size.display().auto_precision().to_string());
+        }
+
+        assert_auto("0 B", ByteSize(0));
+        assert_auto("512 B", ByteSize(512));
+        assert_auto("1.0 KiB", ByteSize::kib(1));
+        assert_auto("9.8 GiB", ByteSize::mib(10_035));
+        assert_auto("10 GiB", ByteSize::gib(10));
+        assert_auto("124 GiB", ByteSize::gib(124));
+        assert_auto("1023 MiB", ByteSize::mib(1023));
+
+        // rounding up to 10 drops the decimal
+        assert_auto("10 GiB", ByteSize::mib(10_199));
+
+        // rounding up to 1024 moves to the next unit
+        assert_auto("1.0 GiB", ByteSize::mib(1023) + ByteSize::kib(614));
+
+        let display = ByteSize::gib(124).display().auto_precision();
+        assert_eq!("124 GiB", format!("{display:.3}"));
+
+        let display = ByteSize::mib(10_035).display().si_short().auto_precision();
+        assert_eq!("11G", display.to_string());
+
+        let display = ByteSize::mib(10_035)
+            .display()
+            .auto_precision()
+            .significant_figures(3);
+        assert_eq!("9.80 GiB", display.to_string());
+    }
+
+    #[test]
+    fn significant_figures() {
+        #[track_caller]
+        fn assert_sig_figs(expected: &str, figures: usize, display: Display) {
+            assert_eq!(expected, display.significant_figures(figures).to_string());
+        }
+
+        assert_sig_figs("1.05 GiB", 3, ByteSize::mib(1075).display());
+        assert_sig_figs("853 MiB", 3, ByteSize::mib(853).display());
+        assert_sig_figs("12.4 KiB", 3, ByteSize::b(12_700).display());
+        assert_sig_figs("1.00 KiB", 3, ByteSize::kib(1).display());
+        assert_sig_figs("1 KiB", 3, ByteSize::kib(1).display().trim_zeros());
+        assert_sig_figs("1023 KiB", 3, ByteSize::kib(1023).display());
+        assert_sig_figs("1.9 GiB", 2, ByteSize::mib(1908).display());
+        assert_sig_figs("2 GiB", 1, ByteSize::mib(1908).display());
+        assert_sig_figs("2 GiB", 0, ByteSize::mib(1908).display());
+        assert_sig_figs("1.86328 GiB", 6, ByteSize::mib(1908).display());
+
+        // bytes are exact
+        assert_sig_figs("999 B", 1, ByteSize::b(999).display().si());
+
+        // carrying into a new digit
+        assert_sig_figs("10.0 kB", 3, ByteSize::b(9_999).display().si());
+        assert_sig_figs("100 kB", 3, ByteSize::b(99_999).display().si());
+
+        // values below 1
+        assert_sig_figs(
+            "0.000954 GiB",
+            3,
+            ByteSize::kib(1000).display().in_unit(Unit::GibiByte),
+        );
+        assert_sig_figs(
+            "0.10 MB",
+            2,
+            ByteSize::b(99_999).display().in_unit(Unit::MegaByte),
+        );
+        assert_sig_figs(
+            "0.00 MB",
+            3,
+            ByteSize::b(0).display().in_unit(Unit::MegaByte),
+        );
+
+        // overrides precision
+        let display = ByteSize::mib(853).display().significant_figures(3);
+        assert_eq!("853 MiB", format!("{display:.5}"));
+    }
+
+    #[test]
+    fn group_separator() {
+        #[track_caller]
+        fn assert_grouped(expected: &str, display: Display) {
+            assert_eq!(expected, display.to_string());
+        }
+
+        assert_grouped("0 B", ByteSize(0).display().group_separator(','));
+        assert_grouped("999 B", ByteSize(999).display().si().group_separator(','));
+        assert_grouped("1023 B", ByteSize(1023).display());
+        assert_grouped("1,023 B", ByteSize(1023).display().group_separator(','));
+        assert_grouped(
+            "18\u{202f}446\u{202f}744\u{202f}073\u{202f}709\u{202f}551\u{202f}615 B",
+            ByteSize(u64::MAX)
+                .display()
+                .in_unit(Unit::Byte)
+                .group_separator('\u{202f}'),
+        );
+        assert_grouped(
+            "1,023.5 MiB",
+            ByteSize::kib(1_048_064)
+                .display()
+                .in_unit(Unit::MebiByte)
+                .group_separator(','),
+        );
+        assert_grouped(
+            "123,456,789.00 kbit",
+            ByteSize::b(15_432_098_625)
+                .display()
+                .si_bits()
+                .in_unit(Unit::KiloByte)
+                .significant_figures(11)
+                .group_separator(','),
+        );
+        assert_grouped("1.0 KiB", ByteSize::kib(1).display().group_separator(','));
+    }
+
+    #[test]
+    fn decimal_separator() {
+        assert_eq!(
+            "1,5 GiB",
+            ByteSize::mib(1536)
+                .display()
+                .decimal_separator(',')
+                .to_string(),
+        );
+        assert_eq!(
+            "1 023,50 MiB",
+            ByteSize::kib(1_048_064)
+                .display()
+                .in_unit(Unit::MebiByte)
+                .group_separator(' ')
+                .decimal_separator(',')
+                .significant_figures(6)
+                .to_string(),
+        );
+        assert_eq!(
+            "1023 B",
+            ByteSize(1023).display().decimal_separator(',').to_string(),
+        );
+        assert_eq!(
+            "2 MiB",
+            ByteSize::mib(2)
+                .display()
+                .decimal_separator(',')
+                .trim_zeros()
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn coreutils() {
+        #[track_caller]
+        fn assert_coreutils(expected: &str, size: ByteSize) {
+            assert_eq!(expected, size.display().coreutils().to_string());
+        }
+
+        // checked against GNU coreutils 9 output for files of these sizes
+        assert_coreutils("0", ByteSize(0));
+        assert_coreutils("1", ByteSize(1));
+        assert_coreutils("1023", ByteSize(1023));
+        assert_coreutils("1.0K", ByteSize(1024));
+        assert_coreutils("1.1K", ByteSize(1025));
+        assert_coreutils("1.5K", ByteSize(1536));
+        assert_coreutils("1.6K", ByteSize(1537));
+        assert_coreutils("9.9K", ByteSize(10_137));
+        assert_coreutils("10K", ByteSize(10_138));
+        assert_coreutils("10K", ByteSize(10_240));
+        assert_coreutils("11K", ByteSize(10_241));
+        assert_coreutils("1023K", ByteSize::kib(1023));
+        assert_coreutils("1.0M", ByteSize::kib(1023) + 1_u64);
+        assert_coreutils("1.0M", ByteSize::mib(1));
+        assert_coreutils("1.0M", ByteSize::mib(1) - 1_u64);
+        assert_coreutils("4.0K", ByteSize::kib(4));
+        assert_coreutils("12M", ByteSize::kib(11_777));
+        assert_coreutils("15E", ByteSize::eib(15));
+        assert_coreutils("16E", ByteSize(u64::MAX));
+
+        // other options do not apply
+        let display = ByteSize(1025)
+            .display()
+            .coreutils()
+            .long_units()
+            .trim_zeros();
+        assert_eq!("1.1K", format!("{display:.3}"));
+    }
+
+    #[test]
+    fn windows_explorer() {
+        #[track_caller]
+        fn assert_explorer(expected: &str, size: ByteSize) {
+            assert_eq!(expected, size.display().windows_explorer().to_string());
+        }
+
+        assert_explorer("0 bytes", ByteSize(0));
+        assert_explorer("1 bytes", ByteSize(1));
+        assert_explorer("1023 bytes", ByteSize(1023));
+        assert_explorer("1.00 KB", ByteSize(1024));
+        assert_explorer("1.99 KB", ByteSize(2047));
+        assert_explorer("9.99 KB", ByteSize::kib(10) - 1_u64);
+        assert_explorer("10.0 KB", ByteSize::kib(10));
+        assert_explorer("99.9 KB", ByteSize::kib(100) - 1_u64);
+        assert_explorer("100 KB", ByteSize::kib(100));
+        assert_explorer("999 KB", ByteSize::kib(1000) - 1_u64);
+        assert_explorer("0.97 MB", ByteSize::kib(1000));
+        assert_explorer("1.00 MB", ByteSize::mib(1));
+        assert_explorer("1.43 MB", ByteSize::kib(1469));
+        assert_explorer("0.97 GB", ByteSize::mib(1000));
+        assert_explorer("931 GB", ByteSize::gb(1000));
+        assert_explorer("15.9 EB", ByteSize(u64::MAX));
+
+        // other options do not apply
+        let display = ByteSize::kib(1469)
+            .display()
+            .windows_explorer()
+            .trim_zeros();
+        assert_eq!("1.43 MB", format!("{display:.5}"));
+    }
+
+    #[test]
+    fn jedec() {
+        #[track_caller]
+        fn assert_jedec(expected: &str, size: ByteSize) {
+            assert_eq!(expected, size.display().jedec().to_string());
+        }
+
+        assert_jedec("1023 B", ByteSize(1023));
+        assert_jedec("1.0 KB", ByteSize::kib(1));
+        assert_jedec("1000.0 KB", ByteSize::kb(1024));
+        assert_jedec("1.5 GB", ByteSize::mib(1536));
+        assert_jedec("1.8 TB", ByteSize::tb(2));
+        assert_jedec("16.0 EB", ByteSize(u64::MAX));
+
+        let display = ByteSize::mib(1536)
+            .display()
+            .jedec()
+            .in_unit(Unit::MebiByte);
+        assert_eq!("1536.0 MB", display.to_string());
+
+        let display = ByteSize::mib(1536).display().jedec().long_units();
+        assert_eq!("1.5 gigabytes", display.to_string());
+    }
+
+    #[test]
+    fn labels() {
+        #[derive(Debug)]
+        struct Emoji;
+
+        impl UnitLabels for Emoji {
+            fn prefix(&self, exp: usize, iec: bool) -> &str {
+                match (exp, iec) {
+                    (0, _) => "",
+                    (_, true) => "🍰",
+                    (_, false) => "🍪",
+                }
+            }
+
+            fn suffix(&self, bits: bool, plural: bool) -> &str {
+                match (bits, plural) {
+                    (true, _) => "🐜",
+                    (false, true) => "🐘🐘",
+                    (false, false) => "🐘",
+                }
+            }
+        }
+
+        #[track_caller]
+        fn assert_labels(expected: &str, display: Display) {
+            assert_eq!(expected, display.labels(&Emoji).to_string());
+        }
+
+        assert_labels("1 🐘", ByteSize(1).display());
+        assert_labels("2 🐘🐘", ByteSize(2).display());
+        assert_labels("1.0 🍰🐘🐘", ByteSize::kib(1).display());
+        assert_labels("1.0 🍪🐘🐘", ByteSize::kb(1).display().si());
+        assert_labels("1.0🍪🐘🐘", ByteSize::kb(1).display().si_short());
+        assert_labels("1 🍪🐘", ByteSize::kb(1).display().si().trim_zeros());
+        assert_labels("8.0 🍪🐜", ByteSize::kb(1).display().si_bits());
+        assert_labels("1.0 🍪🐘🐘", ByteSize::kib(1).display().long_units().si());
+        assert_labels("1.0 🍪🐘🐘", ByteSize::kib(1).os_visible());
+    }
+
+    #[test]
+    fn format_options() {
+        assert_eq!(
+            ByteSize::kib(1536).to_string(),
+            ByteSize::kib(1536).to_string_with(&FormatOptions::default()),
+        );
+
+        let options = FormatOptions::new().format(Format::IecShort).precision(0);
+        assert_eq!("2M", ByteSize::kib(1536).to_string_with(&options));
+        assert_eq!("2M", ByteSize::kib(2560).to_string_with(&options));
+        assert_eq!(
+            "3M",
+            ByteSize::kib(2560).to_string_with(&options.rounding(Rounding::HalfUp)),
+        );
+        assert_eq!(
+            "1.500 MiB",
+            ByteSize::kib(1536).to_string_with(
+                &FormatOptions::new()
+                    .precision(3)
+                    .separator(Separator::Space),
+            ),
+        );
+        assert_eq!(
+            "1.6GB",
+            ByteSize::kib(1536 * 1024).to_string_with(
+                &FormatOptions::new()
+                    .format(Format::Si)
+                    .separator(Separator::None),
+            ),
+        );
+    }
+
+    #[test]
+    fn display_of() {
+        assert_eq!("0 B", super::display_of(0_u8).to_string());
+        assert_eq!("64.0 KiB", super::display_of(u16::MAX).to_string());
+        assert_eq!("16.0 EiB", super::display_of(u64::MAX).to_string());
+
+        assert_eq!("8 B", display_size_of_val(&0_u64).to_string());
+        assert_eq!("0 B", display_size_of_val(&()).to_string());
+        assert_eq!(
+            "2.0 KiB",
+            display_size_of_val(&[0_u16; 1024][..]).to_string()
+        );
+    }
+
+    #[test]
+    fn alignment() {
+        let display = ByteSize::kb(1500).display().si();
+
+        assert_eq!("|1.5 MB    |", format!("|{display:10}|"));
+        assert_eq!("|    1.5 MB|", format!("|{display:>10}|"));
+        assert_eq!("|  1.5 MB  |", format!("|{display:^10}|"));
+        assert_eq!("|1.50 MB---|", format!("|{display:-<10.2}|"));
+        assert_eq!("|1.5 MB|", format!("|{display:3}|"));
+
+        let display = ByteSize::mib(3).display().iec_short().long_units();
+        assert_eq!("|···3.0 mebibytes|", format!("|{display:·>16}|"));
+    }
+
+    #[test]
+    fn write_to() {
+        /// Fixed-capacity buffer, to show that formatting needs no allocation.
+        struct Buf {
+            bytes: [u8; 16],
+            len: usize,
+        }
+
+        impl fmt::Write for Buf {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let end = self.len + s.len();
+                self.bytes
+                    .get_mut(self.len..end)
+                    .ok_or(fmt::Error)?
+                    .copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+
+        let mut buf = Buf {
+            bytes: [0; 16],
+            len: 0,
+        };
+
+        ByteSize::mib(1536).display().write_to(&mut buf).unwrap();
+        ByteSize(42).write_to(&mut buf).unwrap();
+        assert_eq!(b"1.5 GiB42 B", &buf.bytes[..buf.len]);
+
+        // overflowing the buffer is reported
+        assert!(ByteSize::mib(1536)
+            .display()
+            .long_units()
+            .write_to(&mut buf)
+            .is_err());
+
+        let mut out = String::new();
+        let display = ByteSize::kb(1).display().significant_figures(4);
+        display.write_to(&mut out).unwrap();
+        assert_eq!(display.to_string(), out);
+    }
+
+    #[test]
+    fn mixed_units() {
+        #[track_caller]
+        fn assert_mixed(expected: &str, display: Display) {
+            assert_eq!(expected, display.to_string());
+        }
+
+        let size = ByteSize::gib(1) + ByteSize::mib(512) + ByteSize::kib(3);
+
+        assert_mixed("1 GiB 512 MiB 3 KiB", size.display().mixed_units());
+        assert_mixed("1 GiB 512 MiB", size.display().max_components(2));
+        assert_mixed("1 GiB", size.display().max_components(0));
+        assert_mixed(
+            "1 GiB 512 MiB",
+            size.display().max_components(2).mixed_units(),
+        );
+        assert_mixed("1 GiB 512 MiB 3 KiB", size.display().max_components(5));
+
+        assert_mixed("0 B", ByteSize(0).display().mixed_units());
+        assert_mixed("1 B", ByteSize(1).display().mixed_units());
+        assert_mixed("1 KiB 1 B", ByteSize(1025).display().mixed_units());
+        assert_mixed("1 kB 25 B", ByteSize(1025).display().si().mixed_units());
+        assert_mixed("1K 1B", ByteSize(1025).display().iec_short().mixed_units());
+        assert_mixed(
+            "8 Kibit 8 bit",
+            ByteSize(1025).display().iec_bits().mixed_units(),
+        );
+        assert_mixed(
+            "1 kibibyte 2 bytes",
+            ByteSize(1026).display().long_units().mixed_units(),
+        );
+        assert_mixed(
+            "15 EiB 1023 PiB 1023 TiB 1023 GiB 1023 MiB 1023 KiB 1023 B",
+            ByteSize(u64::MAX).display().mixed_units(),
+        );
+        assert_mixed(
+            "1,023 KiB 1 B",
+            ByteSize(1_047_553)
+                .display()
+                .group_separator(',')
+                .mixed_units(),
+        );
+    }
+
+    #[test]
+    fn engineering() {
+        #[track_caller]
+        fn assert_engineering(expected: &str, display: Display) {
+            assert_eq!(expected, display.to_string());
+        }
+
+        let e = |size: ByteSize| size.display().engineering(Exponent::E);
+
+        assert_engineering("0 B", e(ByteSize(0)));
+        assert_engineering("1 B", e(ByteSize(1)));
+        assert_engineering("999 B", e(ByteSize(999)));
+        assert_engineering("1.0e3 B", e(ByteSize(1000)));
+        assert_engineering("1.0e3 B", e(ByteSize::kib(1)));
+        assert_engineering("12.3e3 B", e(ByteSize(12_345)));
+        assert_engineering("123.5e3 B", e(ByteSize(123_456)));
+        assert_engineering("1.5e9 B", e(ByteSize::mb(1500)));
+        assert_engineering("18.4e18 B", e(ByteSize(u64::MAX)));
+
+        // rounding carries into the next exponent
+        assert_engineering("1.0e6 B", e(ByteSize(999_960)));
+        assert_engineering("999.9e3 B", e(ByteSize(999_949)));
+
+        assert_engineering(
+            "1.234567e6 B",
+            e(ByteSize(1_234_567)).significant_figures(7),
+        );
+        assert_engineering("1.23e6 B", e(ByteSize(1_234_567)).significant_figures(3));
+        assert_engineering("1.5e9 B", e(ByteSize::mb(1500)).trim_zeros());
+        assert_engineering("1,5e9 B", e(ByteSize::mb(1500)).decimal_separator(','));
+        assert_engineering("1.5e9B", e(ByteSize::mb(1500)).separator(Separator::None));
+        assert_engineering("1.0e3 bytes", e(ByteSize::kb(1)).long_units());
+        assert_engineering("1 byte", e(ByteSize(1)).long_units());
+        assert_engineering("8.0e9 bit", e(ByteSize::gb(1)).si_bits());
+        assert_engineering("1.5e9 B", e(ByteSize::mb(1500)).in_unit(Unit::KibiByte));
+
+        assert_eq!("1.000e3 B", format!("{:.3}", e(ByteSize::kb(1))));
+        assert_eq!(
+            "1.000e3 B",
+            format!(
+                "{:.9}",
+                e(ByteSize::kb(1)).excess_precision(ExcessPrecision::Trim)
+            )
+        );
+
+        assert_engineering(
+            "1.5 \u{d7} 10^9 B",
+            ByteSize::mb(1500).display().engineering(Exponent::TimesTen),
+        );
+        assert_engineering(
+            "512 B",
+            ByteSize(512).display().engineering(Exponent::TimesTen),
+        );
+    }
+
+    #[test]
+    fn remainder() {
+        #[track_caller]
+        fn assert_remainder(expected: &str, display: Display) {
+            assert_eq!(expected, display.remainder().to_string());
+        }
+
+        assert_remainder("2.0 MiB − 4.0 KiB", ByteSize(2_093_056).display());
+        assert_remainder("2.0 MiB + 1 B", ByteSize(2_097_153).display());
+        assert_remainder("2.0 MiB", ByteSize::mib(2).display());
+        assert_remainder("1023 B", ByteSize(1023).display());
+        assert_remainder("1.0 MiB − 1 B", (ByteSize::mib(1) - 1_u64).display());
+        assert_remainder("2.0 KiB − 512 B", ByteSize(1536).display());
+        assert_remainder("1.0 KiB + 511 B", ByteSize(1535).display());
+        assert_remainder("2.0 GB − 1.0 MB", ByteSize::mb(1999).display().si());
+        assert_remainder(
+            "2.0 MiB − 4.0 KiB",
+            ByteSize(2_093_056).display().in_unit(Unit::MebiByte),
+        );
+        assert_remainder("1.0 kbit + 8 bit", ByteSize(126).display().si_bits());
+
+        // nearest multiple would overflow
+        assert_remainder("16.0 EiB", ByteSize(u64::MAX).display());
+
+        let display = ByteSize(2_093_056).display().remainder();
+        assert_eq!("2 MiB − 4 KiB", format!("{display:.0}"));
+    }
+
+    #[test]
+    fn excess_precision() {
+        let size = ByteSize::kb(1234);
+
+        let display = size.display().si();
+        assert_eq!("1.234000000 MB", format!("{display:.9}"));
+
+        let display = size.display().si().excess_precision(ExcessPrecision::Trim);
+        assert_eq!("1.234000 MB", format!("{display:.9}"));
+        assert_eq!("1.2 MB", format!("{display}"));
+
+        let display = size.display().si().excess_precision(ExcessPrecision::Error);
+        assert_eq!("1.234000 MB", format!("{display:.6}"));
+
+        let mut buf = alloc::string::String::new();
+        fmt::write(&mut buf, format_args!("{display:.7}")).unwrap_err();
+
+        let display = ByteSize::kib(3)
+            .display()
+            .excess_precision(ExcessPrecision::Trim);
+        assert_eq!("3.0000000000 KiB", format!("{display:.12}"));
+        assert_eq!("3 B", format!("{:.12}", ByteSize::b(3).display()));
     }
 }
@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use crate::ByteSize;
+
+impl ByteSize {
+    /// Splits this size into shares proportional to `weights`.
+    ///
+    /// Uses the largest remainder method: each share is first rounded down, then the bytes left
+    /// over are handed out one at a time to the shares with the largest remainders. Ties go to the
+    /// share that comes first. The shares always sum to exactly `self`.
+    ///
+    /// Returns `None` if the weights sum to zero, including when `weights` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let quota = ByteSize::b(100);
+    ///
+    /// assert_eq!(
+    ///     quota.distribute_weighted(&[1, 1, 1]).unwrap(),
+    ///     [ByteSize::b(34), ByteSize::b(33), ByteSize::b(33)],
+    /// );
+    ///
+    /// assert_eq!(
+    ///     ByteSize::gib(10).distribute_weighted(&[3, 2]).unwrap(),
+    ///     [ByteSize::gib(6), ByteSize::gib(4)],
+    /// );
+    /// ```
+    pub fn distribute_weighted(self, weights: &[u64]) -> Option<Vec<ByteSize>> {
+        let total_weight = weights.iter().map(|&w| u128::from(w)).sum::<u128>();
+
+        if total_weight == 0 {
+            return None;
+        }
+
+        let total = u128::from(self.0);
+
+        let mut shares = Vec::with_capacity(weights.len());
+        let mut remainders = Vec::with_capacity(weights.len());
+
+        for &weight in weights {
+            let exact = total * u128::from(weight);
+
+            // a share is never larger than the total so always fits in a u64
+            shares.push(ByteSize((exact / total_weight) as u64));
+            remainders.push(exact % total_weight);
+        }
+
+        let allocated = shares.iter().map(|share| share.0).sum::<u64>();
+        let leftover = (self.0 - allocated) as usize;
+
+        // stable sort keeps earlier shares first among equal remainders
+        let mut order = (0..weights.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&idx| Reverse(remainders[idx]));
+
+        for &idx in &order[..leftover] {
+            shares[idx].0 += 1;
+        }
+
+        Some(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn even_split() {
+        assert_eq!(
+            ByteSize::kib(3).distribute_weighted(&[1, 1, 1]).unwrap(),
+            [ByteSize::kib(1); 3],
+        );
+    }
+
+    #[test]
+    fn largest_remainders_get_leftovers() {
+        // exact shares are 14.28.., 28.57.., 57.14..
+        assert_eq!(
+            ByteSize::b(100).distribute_weighted(&[1, 2, 4]).unwrap(),
+            [ByteSize::b(14), ByteSize::b(29), ByteSize::b(57)],
+        );
+
+        // ties are broken by position
+        assert_eq!(
+            ByteSize::b(2).distribute_weighted(&[1, 1, 1]).unwrap(),
+            [ByteSize::b(1), ByteSize::b(1), ByteSize::b(0)],
+        );
+    }
+
+    #[test]
+    fn zero_weights() {
+        assert_eq!(ByteSize::b(100).distribute_weighted(&[]), None);
+        assert_eq!(ByteSize::b(100).distribute_weighted(&[0, 0]), None);
+
+        assert_eq!(
+            ByteSize::b(100).distribute_weighted(&[0, 1, 0]).unwrap(),
+            [ByteSize::b(0), ByteSize::b(100), ByteSize::b(0)],
+        );
+    }
+
+    #[test]
+    fn extreme_values() {
+        let shares = ByteSize(u64::MAX)
+            .distribute_weighted(&[u64::MAX, u64::MAX, 1])
+            .unwrap();
+        assert_eq!(shares.iter().sum::<ByteSize>(), ByteSize(u64::MAX));
+        assert!(shares[2] <= ByteSize(1));
+    }
+
+    quickcheck::quickcheck! {
+        fn shares_sum_to_total(size: ByteSize, weights: alloc::vec::Vec<u64>) -> bool {
+            match size.distribute_weighted(&weights) {
+                Some(shares) => shares.iter().map(|s| u128::from(s.0)).sum::<u128>() == u128::from(size.0),
+                None => weights.iter().all(|&w| w == 0),
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::string::String;
+use core::{fmt, time::Duration};
+
+use crate::{display, ByteRate, ByteSize};
+
+impl ByteSize {
+    /// Returns the time left to transfer this size at `rate`, or `None` if `rate` is zero.
+    ///
+    /// The duration is truncated to whole nanoseconds. Wrap it in an [`Eta`] to display it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytesize::{ByteRate, ByteSize};
+    ///
+    /// let remaining = ByteSize::gib(1);
+    /// assert_eq!(
+    ///     Some(Duration::from_secs(128)),
+    ///     remaining.eta_at(ByteRate::mib(8)),
+    /// );
+    /// assert_eq!(None, remaining.eta_at(ByteRate(0)));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    #[inline(always)]
+    pub const fn eta_at(self, rate: ByteRate) -> Option<Duration> {
+        self.checked_div_rate(rate)
+    }
+}
+
+/// Remaining time of a transfer, displayed compactly for progress output.
+///
+/// Times are rounded up to whole seconds and show the two largest units, e.g., `42s`, `5m 07s`,
+/// `2h 05m`, or `3d 04h`. An unknown time, e.g., at a rate of zero, is displayed as `--`. The
+/// formatter's width, fill, and alignment are honored.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bytesize::{ByteRate, ByteSize, Eta};
+///
+/// assert_eq!("2m 08s", Eta::new(ByteSize::gib(1), ByteRate::mib(8)).to_string());
+/// assert_eq!("--", Eta::new(ByteSize::gib(1), ByteRate(0)).to_string());
+///
+/// assert_eq!("  1h 01m", format!("{:>8}", Eta::from(Duration::from_secs(3660))));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub struct Eta(pub Option<Duration>);
+
+impl Eta {
+    /// Constructs the time left to transfer `remaining` at `rate`.
+    #[inline(always)]
+    pub const fn new(remaining: ByteSize, rate: ByteRate) -> Eta {
+        Eta(remaining.eta_at(rate))
+    }
+}
+
+impl From<Duration> for Eta {
+    fn from(duration: Duration) -> Self {
+        Eta(Some(duration))
+    }
+}
+
+impl From<Option<Duration>> for Eta {
+    fn from(duration: Option<Duration>) -> Self {
+        Eta(duration)
+    }
+}
+
+impl fmt::Display for Eta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write as _;
+
+        const MINUTE: u64 = 60;
+        const HOUR: u64 = 60 * MINUTE;
+        const DAY: u64 = 24 * HOUR;
+
+        let Some(duration) = self.0 else {
+            return display::pad(f, "--");
+        };
+
+        let secs = duration
+            .as_secs()
+            .saturating_add(u64::from(duration.subsec_nanos() > 0));
+
+        let mut buf = String::new();
+
+        if secs >= DAY {
+            write!(buf, "{}d {:02}h", secs / DAY, secs % DAY / HOUR)?;
+        } else if secs >= HOUR {
+            write!(buf, "{}h {:02}m", secs / HOUR, secs % HOUR / MINUTE)?;
+        } else if secs >= MINUTE {
+            write!(buf, "{}m {:02}s", secs / MINUTE, secs % MINUTE)?;
+        } else {
+            write!(buf, "{secs}s")?;
+        }
+
+        display::pad(f, &buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString as _};
+
+    use super::*;
+
+    #[test]
+    fn eta_at() {
+        assert_eq!(
+            Some(Duration::from_millis(2500)),
+            ByteSize::mib(10).eta_at(ByteRate::mib(4))
+        );
+        assert_eq!(Some(Duration::ZERO), ByteSize(0).eta_at(ByteRate(1)));
+        assert_eq!(None, ByteSize(0).eta_at(ByteRate(0)));
+        assert_eq!(None, ByteSize::gib(1).eta_at(ByteRate(0)));
+    }
+
+    #[test]
+    fn display() {
+        let eta = |secs, nanos| Eta::from(Duration::new(secs, nanos)).to_string();
+
+        assert_eq!("0s", eta(0, 0));
+        assert_eq!("1s", eta(0, 1));
+        assert_eq!("42s", eta(42, 0));
+        assert_eq!("1m 00s", eta(59, 500_000_000));
+        assert_eq!("5m 07s", eta(307, 0));
+        assert_eq!("1h 00m", eta(3600, 0));
+        assert_eq!("2h 05m", eta(7530, 0));
+        assert_eq!("23h 59m", eta(86_399, 0));
+        assert_eq!("3d 04h", eta(3 * 86_400 + 4 * 3600 + 59, 0));
+        assert_eq!("213503982334601d 07h", eta(u64::MAX, 999_999_999));
+        assert_eq!("--", Eta(None).to_string());
+        assert_eq!("--", Eta::default().to_string());
+    }
+
+    #[test]
+    fn padding() {
+        let eta = Eta::from(Duration::from_secs(42));
+
+        assert_eq!("42s   |", format!("{eta:6}|"));
+        assert_eq!("   42s", format!("{eta:>6}"));
+        assert_eq!("**42s**", format!("{eta:*^7}"));
+        assert_eq!("  --", format!("{:>4}", Eta(None)));
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            Eta(Some(Duration::from_secs(128))),
+            Eta::new(ByteSize::gib(1), ByteRate::mib(8))
+        );
+        assert_eq!(Eta(None), Eta::new(ByteSize::gib(1), ByteRate(0)));
+    }
+}
@@ -0,0 +1,268 @@
+//! C bindings for parsing and formatting.
+//!
+//! The functions here are exported unmangled, so C and C++ code can call them once this crate is
+//! linked in, e.g., through a `staticlib` or `cdylib` crate that depends on it with the `ffi`
+//! feature. Their declarations are in `include/bytesize.h`, generated with `cbindgen`.
+//!
+//! Strings passed in are NUL-terminated UTF-8. Formatting functions write into a caller-provided
+//! buffer and, like `snprintf`, return the length of the full string, not counting the NUL
+//! terminator, so output was truncated if the result is at least the buffer's length.
+//!
+//! ```c
+//! #include "bytesize.h"
+//!
+//! uint64_t bytes;
+//! if (bytesize_parse("1.5 GiB", &bytes)) {
+//!     char buf[BYTESIZE_FORMAT_LEN];
+//!     bytesize_format_si(bytes, buf, sizeof buf); // "1.6 GB"
+//! }
+//! ```
+
+use alloc::string::{String, ToString as _};
+use core::{
+    ffi::{c_char, CStr},
+    slice,
+};
+
+use crate::ByteSize;
+
+/// Buffer length that fits any string from [`bytesize_format_iec()`] or [`bytesize_format_si()`],
+/// including the NUL terminator.
+pub const BYTESIZE_FORMAT_LEN: usize = 16;
+
+/// Parses the size in `input`, e.g., `"1.5 GiB"`, and stores its number of bytes in `out`.
+///
+/// Returns `false` if `input` isn't a valid size, or either pointer is null. `out` is left
+/// unchanged in that case. See [`bytesize_parse_error()`] for a description of the problem.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string, and `out` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn bytesize_parse(input: *const c_char, out: *mut u64) -> bool {
+    if out.is_null() {
+        return false;
+    }
+
+    match parse(input) {
+        Ok(size) => {
+            // SAFETY: `out` is non-null, and valid for writes per this function's contract.
+            unsafe { out.write(size.0) };
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Writes a description of why `input` isn't a valid size to `buf`, e.g., for logging after
+/// [`bytesize_parse()`] failed.
+///
+/// Returns the length of the description, or 0 if `input` is a valid size, in which case an empty
+/// string is written.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string, and `buf` must be valid for writes of
+/// `len` bytes, or null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn bytesize_parse_error(
+    input: *const c_char,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    let err = parse(input).err().unwrap_or_default();
+
+    // SAFETY: forwarded from this function's contract.
+    unsafe { write_str(&err, buf, len) }
+}
+
+/// Formats `bytes` in IEC (binary) units, e.g., `1.5 KiB`.
+///
+/// Returns the length of the string, not counting the NUL terminator. At most `len` bytes are
+/// written, always including a NUL terminator if `len` isn't 0.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn bytesize_format_iec(bytes: u64, buf: *mut c_char, len: usize) -> usize {
+    let string = ByteSize(bytes).display().iec().to_string();
+
+    // SAFETY: forwarded from this function's contract.
+    unsafe { write_str(&string, buf, len) }
+}
+
+/// Formats `bytes` in SI (decimal) units, e.g., `1.5 kB`.
+///
+/// Returns the length of the string, not counting the NUL terminator. At most `len` bytes are
+/// written, always including a NUL terminator if `len` isn't 0.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn bytesize_format_si(bytes: u64, buf: *mut c_char, len: usize) -> usize {
+    let string = ByteSize(bytes).display().si().to_string();
+
+    // SAFETY: forwarded from this function's contract.
+    unsafe { write_str(&string, buf, len) }
+}
+
+/// Formats `bytes` exactly, in the largest unit that divides it, e.g., `1536 KiB`.
+///
+/// Returns the length of the string, not counting the NUL terminator. At most `len` bytes are
+/// written, always including a NUL terminator if `len` isn't 0.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn bytesize_format_exact(bytes: u64, buf: *mut c_char, len: usize) -> usize {
+    let string = ByteSize(bytes).to_string_exact();
+
+    // SAFETY: forwarded from this function's contract.
+    unsafe { write_str(&string, buf, len) }
+}
+
+/// Parses the NUL-terminated string at `input`.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string.
+unsafe fn parse(input: *const c_char) -> Result<ByteSize, String> {
+    if input.is_null() {
+        return Err("input is null".to_string());
+    }
+
+    // SAFETY: `input` is non-null, and NUL-terminated per this function's contract.
+    let input = unsafe { CStr::from_ptr(input) };
+
+    input
+        .to_str()
+        .map_err(|_| "input is not valid UTF-8".to_string())?
+        .parse()
+}
+
+/// Copies as much of `string` as fits into `buf`, followed by a NUL terminator, and returns the
+/// length of `string`.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of `len` bytes, or null if `len` is 0.
+unsafe fn write_str(string: &str, buf: *mut c_char, len: usize) -> usize {
+    if !buf.is_null() && len > 0 {
+        // SAFETY: `buf` is non-null, and valid for writes of `len` bytes per this function's
+        // contract.
+        let buf = unsafe { slice::from_raw_parts_mut(buf.cast::<u8>(), len) };
+
+        let mut copied = string.len().min(len - 1);
+        while !string.is_char_boundary(copied) {
+            copied -= 1;
+        }
+
+        buf[..copied].copy_from_slice(&string.as_bytes()[..copied]);
+        buf[copied] = 0;
+    }
+
+    string.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+
+    use super::*;
+
+    fn format(f: unsafe extern "C" fn(u64, *mut c_char, usize) -> usize, bytes: u64) -> String {
+        let mut buf = [0 as c_char; BYTESIZE_FORMAT_LEN];
+
+        // SAFETY: `buf` is valid for writes of its length.
+        let n = unsafe { f(bytes, buf.as_mut_ptr(), buf.len()) };
+        assert!(n < buf.len());
+
+        // SAFETY: a NUL terminator was written.
+        let string = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        string.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn parsing() {
+        let mut bytes = 0;
+
+        // SAFETY: valid strings and output pointer.
+        unsafe {
+            assert!(bytesize_parse(c"1.5 KiB".as_ptr(), &mut bytes));
+            assert_eq!(1536, bytes);
+
+            assert!(!bytesize_parse(c"1.5 parsecs".as_ptr(), &mut bytes));
+            assert!(!bytesize_parse(ptr::null(), &mut bytes));
+            assert!(!bytesize_parse(c"1 KiB".as_ptr(), ptr::null_mut()));
+            assert!(!bytesize_parse(c"\xff".as_ptr(), &mut bytes));
+            assert_eq!(1536, bytes);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let mut buf = [0 as c_char; 64];
+
+        // SAFETY: valid strings and buffers.
+        unsafe {
+            let n = bytesize_parse_error(c"12 KB".as_ptr(), buf.as_mut_ptr(), buf.len());
+            assert_eq!(0, n);
+            assert_eq!(0, buf[0]);
+
+            let n = bytesize_parse_error(c"12 parsecs".as_ptr(), buf.as_mut_ptr(), buf.len());
+            let err = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+            assert!(err.contains("parsecs"), "{err}");
+            assert!(n >= buf.len() || n == err.len());
+
+            let n = bytesize_parse_error(ptr::null(), ptr::null_mut(), 0);
+            assert_eq!("input is null".len(), n);
+        }
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!("1.5 KiB", format(bytesize_format_iec, 1536));
+        assert_eq!("1.5 kB", format(bytesize_format_si, 1500));
+        assert_eq!("1536 KiB", format(bytesize_format_exact, 1536 * 1024));
+
+        assert_eq!("16.0 EiB", format(bytesize_format_iec, u64::MAX));
+        for bytes in [u64::MAX, 999_949_999_999_999_999, 1023 << 50] {
+            format(bytesize_format_iec, bytes);
+            format(bytesize_format_si, bytes);
+        }
+    }
+
+    #[test]
+    fn truncation() {
+        let mut buf = [1 as c_char; 4];
+
+        // SAFETY: `buf` is valid for writes of its length.
+        let n = unsafe { bytesize_format_iec(1536, buf.as_mut_ptr(), buf.len()) };
+        assert_eq!(7, n);
+        assert_eq!([b'1', b'.', b'5', 0], buf.map(|c| c as u8));
+
+        // SAFETY: null is allowed with a length of 0.
+        let n = unsafe { bytesize_format_iec(1536, ptr::null_mut(), 0) };
+        assert_eq!(7, n);
+    }
+
+    #[test]
+    fn header() {
+        let header = include_str!("../include/bytesize.h");
+
+        for declaration in [
+            "#define BYTESIZE_FORMAT_LEN 16",
+            "bool bytesize_parse(const char *input, uint64_t *out);",
+            "size_t bytesize_parse_error(const char *input, char *buf, size_t len);",
+            "size_t bytesize_format_iec(uint64_t bytes, char *buf, size_t len);",
+            "size_t bytesize_format_si(uint64_t bytes, char *buf, size_t len);",
+            "size_t bytesize_format_exact(uint64_t bytes, char *buf, size_t len);",
+        ] {
+            assert!(header.contains(declaration), "{declaration}");
+        }
+    }
+}
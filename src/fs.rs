@@ -0,0 +1,270 @@
+use alloc::vec;
+use std::{collections::HashSet, fs, io, path::Path};
+
+use crate::ByteSize;
+
+impl ByteSize {
+    /// Returns the size of the file at `path`, following symbolic links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file's metadata can't be read, e.g., if it doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bytesize::ByteSize;
+    ///
+    /// let size = ByteSize::of_file("Cargo.toml")?;
+    /// println!("Cargo.toml is {size}");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn of_file(path: impl AsRef<Path>) -> io::Result<ByteSize> {
+        fs::metadata(path).map(|metadata| ByteSize::from(&metadata))
+    }
+
+    /// Returns the total size of the files in the directory tree at `path`.
+    ///
+    /// Sizes are apparent sizes and symbolic links are not followed. See [`DirSizeOptions`] to
+    /// change this.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any directory in the tree can't be read, or any file's metadata can't
+    /// be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bytesize::ByteSize;
+    ///
+    /// let size = ByteSize::of_dir("target")?;
+    /// println!("target is {size}");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn of_dir(path: impl AsRef<Path>) -> io::Result<ByteSize> {
+        DirSizeOptions::new().size_of(path)
+    }
+}
+
+/// Options for summing the sizes of files in a directory tree.
+///
+/// The default options match [`ByteSize::of_dir()`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use bytesize::DirSizeOptions;
+///
+/// // like `du -sL`
+/// let size = DirSizeOptions::new()
+///     .follow_symlinks()
+///     .block_size()
+///     .size_of("target")?;
+/// println!("target uses {size} on disk");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct DirSizeOptions {
+    follow_symlinks: bool,
+    block_size: bool,
+}
+
+impl DirSizeOptions {
+    /// Returns the default options.
+    pub const fn new() -> Self {
+        Self {
+            follow_symlinks: false,
+            block_size: false,
+        }
+    }
+
+    /// Follow symbolic links, counting the files and directories they point to.
+    ///
+    /// Each directory is walked once, so links to a parent directory don't loop forever.
+    /// Otherwise, symbolic links are skipped.
+    #[must_use]
+    pub const fn follow_symlinks(mut self) -> Self {
+        self.follow_symlinks = true;
+        self
+    }
+
+    /// Count the space allocated to files on disk instead of their apparent sizes, which differs
+    /// for sparse, compressed, or small files.
+    ///
+    /// This is only supported on Unix; other platforms always count apparent sizes.
+    #[must_use]
+    pub const fn block_size(mut self) -> Self {
+        self.block_size = true;
+        self
+    }
+
+    /// Returns the total size of the files in the directory tree at `path`, or the size of the
+    /// file at `path` if it's not a directory.
+    ///
+    /// Directories themselves aren't counted, and hard links are counted once for each link.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any directory in the tree can't be read, or any file's metadata can't
+    /// be read.
+    pub fn size_of(&self, path: impl AsRef<Path>) -> io::Result<ByteSize> {
+        let mut total = 0_u64;
+        let mut visited = HashSet::new();
+        let mut pending = vec![path.as_ref().to_path_buf()];
+
+        while let Some(path) = pending.pop() {
+            let metadata = fs::symlink_metadata(&path)?;
+
+            let metadata = if !metadata.file_type().is_symlink() {
+                metadata
+            } else if self.follow_symlinks {
+                fs::metadata(&path)?
+            } else {
+                continue;
+            };
+
+            if !metadata.is_dir() {
+                total = total.saturating_add(self.file_size(&metadata).0);
+                continue;
+            }
+
+            if self.follow_symlinks && !visited.insert(fs::canonicalize(&path)?) {
+                continue;
+            }
+
+            for entry in fs::read_dir(&path)? {
+                pending.push(entry?.path());
+            }
+        }
+
+        Ok(ByteSize(total))
+    }
+
+    fn file_size(&self, metadata: &fs::Metadata) -> ByteSize {
+        #[cfg(unix)]
+        if self.block_size {
+            use std::os::unix::fs::MetadataExt as _;
+
+            // `st_blocks` is always in 512-byte units
+            return ByteSize(metadata.blocks().saturating_mul(512));
+        }
+
+        ByteSize::from(metadata)
+    }
+}
+
+/// Returns the length of a file, as reported by [`fs::Metadata::len()`].
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<&fs::Metadata> for ByteSize {
+    fn from(metadata: &fs::Metadata) -> Self {
+        ByteSize(metadata.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use std::{env, io::Write as _, path::PathBuf, process};
+
+    use super::*;
+
+    #[test]
+    fn of_file() {
+        let path = env::temp_dir().join(format!("bytesize-of-file-{}", process::id()));
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(&[0; 1536]).unwrap();
+        drop(file);
+
+        let size = ByteSize::of_file(&path);
+        let metadata = fs::metadata(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(ByteSize(1536), size.unwrap());
+        assert_eq!(ByteSize(1536), ByteSize::from(&metadata));
+    }
+
+    /// Temporary directory tree, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("bytesize-{name}-{}", process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(path.join("nested/deeper")).unwrap();
+
+            for (file, len) in [("a", 1000), ("nested/b", 24), ("nested/deeper/c", 512)] {
+                fs::write(path.join(file), vec![0; len]).unwrap();
+            }
+            fs::create_dir(path.join("empty")).unwrap();
+
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn of_dir() {
+        let dir = TempDir::new("of-dir");
+
+        assert_eq!(ByteSize(1536), ByteSize::of_dir(&dir.0).unwrap());
+        assert_eq!(
+            ByteSize(536),
+            ByteSize::of_dir(dir.0.join("nested")).unwrap()
+        );
+        assert_eq!(ByteSize(0), ByteSize::of_dir(dir.0.join("empty")).unwrap());
+        assert_eq!(ByteSize(1000), ByteSize::of_dir(dir.0.join("a")).unwrap());
+
+        let error = ByteSize::of_dir(dir.0.join("missing")).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new("symlinks");
+        symlink(dir.0.join("a"), dir.0.join("link-to-file")).unwrap();
+        symlink(dir.0.join("nested"), dir.0.join("link-to-dir")).unwrap();
+        symlink(&dir.0, dir.0.join("nested/deeper/link-to-root")).unwrap();
+        symlink(dir.0.join("missing"), dir.0.join("empty/broken")).unwrap();
+
+        assert_eq!(ByteSize(1536), ByteSize::of_dir(&dir.0).unwrap());
+
+        // every directory is walked once, through whichever path comes first
+        let follow = DirSizeOptions::new().follow_symlinks();
+        assert_eq!(
+            io::ErrorKind::NotFound,
+            follow.size_of(&dir.0).unwrap_err().kind()
+        );
+
+        fs::remove_file(dir.0.join("empty/broken")).unwrap();
+        assert_eq!(ByteSize(2536), follow.size_of(&dir.0).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn block_size() {
+        let dir = TempDir::new("block-size");
+
+        let size = DirSizeOptions::new().block_size().size_of(&dir.0).unwrap();
+        // each non-empty file takes at least one block
+        assert!(size >= ByteSize(3 * 512), "{size:?}");
+        assert_eq!(0, size.0 % 512);
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = ByteSize::of_file("does/not/exist").unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+    }
+}
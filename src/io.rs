@@ -0,0 +1,635 @@
+use core::fmt;
+use std::io::{self, BufRead, IoSlice, IoSliceMut, Read, Write};
+
+use crate::ByteSize;
+#[cfg(feature = "unstable")]
+use crate::ThroughputMeter;
+
+/// Counts the bytes passing through an I/O adapter, optionally measuring their rate.
+#[derive(Debug, Clone, Default)]
+struct Counter {
+    count: u64,
+    #[cfg(feature = "unstable")]
+    meter: Option<ThroughputMeter>,
+}
+
+impl Counter {
+    fn record(&mut self, bytes: usize) {
+        let bytes = bytes as u64;
+        self.count = self.count.saturating_add(bytes);
+
+        #[cfg(feature = "unstable")]
+        if let Some(meter) = &mut self.meter {
+            meter.record(ByteSize(bytes));
+        }
+    }
+}
+
+/// Accessors shared by the synchronous and [Tokio](crate::tokio) counting adapters, given the
+/// path of the adapter's meter field.
+macro_rules! adapter_accessors {
+    ($inner:ident, $($meter:ident).+) => {
+        /// Measures the rate of the transfer with `meter`, e.g., a new
+        /// [`ThroughputMeter::new()`](crate::ThroughputMeter::new).
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+        #[must_use]
+        pub fn with_meter(mut self, meter: $crate::ThroughputMeter) -> Self {
+            self.$($meter).+ = Some(meter);
+            self
+        }
+
+        /// Returns the meter measuring the rate of the transfer, if any.
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+        pub const fn meter(&self) -> Option<&$crate::ThroughputMeter> {
+            self.$($meter).+.as_ref()
+        }
+
+        /// Returns the [current](crate::ThroughputMeter::current) rate of the transfer, or `None`
+        /// without a meter.
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+        pub fn rate(&self) -> Option<$crate::ByteRate> {
+            self.meter().map($crate::ThroughputMeter::current)
+        }
+
+        /// Returns a reference to the wrapped value.
+        pub const fn get_ref(&self) -> &$inner {
+            &self.inner
+        }
+
+        /// Returns a mutable reference to the wrapped value.
+        ///
+        /// Bytes transferred directly through it are not counted.
+        pub fn get_mut(&mut self) -> &mut $inner {
+            &mut self.inner
+        }
+
+        /// Returns the wrapped value.
+        pub fn into_inner(self) -> $inner {
+            self.inner
+        }
+    };
+}
+
+#[cfg(feature = "tokio")]
+pub(crate) use adapter_accessors;
+
+macro_rules! counting_adapter {
+    ($name:ident, $inner:ident, $bytes:literal) => {
+        impl<$inner> $name<$inner> {
+            #[doc = concat!("Wraps `inner`, counting the bytes ", $bytes, " it.")]
+            pub fn new(inner: $inner) -> Self {
+                Self {
+                    inner,
+                    counter: Counter::default(),
+                }
+            }
+
+            adapter_accessors!($inner, counter.meter);
+
+            #[doc = concat!("Returns the number of bytes ", $bytes, " the adapter so far.")]
+            pub const fn count(&self) -> ByteSize {
+                ByteSize(self.counter.count)
+            }
+        }
+    };
+}
+
+/// Reader adapter counting the bytes read through it.
+///
+/// Buffered reads through [`BufRead`] are counted when they're consumed.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read as _;
+///
+/// use bytesize::{ByteSize, CountingReader};
+///
+/// let mut reader = CountingReader::new(&[0_u8; 1536][..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+///
+/// assert_eq!(ByteSize::b(1536), reader.count());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct CountingReader<R> {
+    inner: R,
+    counter: Counter,
+}
+
+counting_adapter!(CountingReader, R, "read through");
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.counter.record(read);
+        Ok(read)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let read = self.inner.read_vectored(bufs)?;
+        self.counter.record(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.counter.record(amt);
+    }
+}
+
+/// Writer adapter counting the bytes written through it.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// use bytesize::{ByteSize, CountingWriter};
+///
+/// let mut writer = CountingWriter::new(Vec::new());
+/// writer.write_all(&[0; 4096]).unwrap();
+///
+/// assert_eq!(ByteSize::kib(4), writer.count());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct CountingWriter<W> {
+    inner: W,
+    counter: Counter,
+}
+
+counting_adapter!(CountingWriter, W, "written through");
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.counter.record(written);
+        Ok(written)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let written = self.inner.write_vectored(bufs)?;
+        self.counter.record(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Tracks the bytes passing through an I/O adapter against a limit.
+#[derive(Debug, Clone)]
+struct Limit {
+    limit: u64,
+    count: u64,
+}
+
+impl Limit {
+    const fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.count)
+    }
+
+    /// Returns the number of bytes out of `len` that fit in the limit.
+    fn clamp(&self, len: usize) -> usize {
+        self.remaining().min(len as u64) as usize
+    }
+
+    fn record(&mut self, bytes: usize) {
+        self.count = self.count.saturating_add(bytes as u64);
+    }
+
+    fn exceeded(&self) -> io::Error {
+        LimitExceeded::new(ByteSize(self.limit)).into()
+    }
+}
+
+macro_rules! limited_adapter {
+    ($name:ident, $inner:ident, $bytes:literal $(, $field:ident: $value:expr)*) => {
+        impl<$inner> $name<$inner> {
+            #[doc = concat!("Wraps `inner`, allowing at most `limit` bytes to be ", $bytes, " it.")]
+            pub const fn new(inner: $inner, limit: ByteSize) -> Self {
+                Self {
+                    inner,
+                    limit: Limit {
+                        limit: limit.0,
+                        count: 0,
+                    },
+                    $($field: $value,)*
+                }
+            }
+
+            /// Returns the configured limit.
+            pub const fn limit(&self) -> ByteSize {
+                ByteSize(self.limit.limit)
+            }
+
+            #[doc = concat!("Returns the number of bytes ", $bytes, " the adapter so far.")]
+            pub const fn count(&self) -> ByteSize {
+                ByteSize(self.limit.count)
+            }
+
+            /// Returns the number of bytes left before the limit is reached.
+            pub const fn remaining(&self) -> ByteSize {
+                ByteSize(self.limit.remaining())
+            }
+
+            /// Returns a reference to the wrapped value.
+            pub const fn get_ref(&self) -> &$inner {
+                &self.inner
+            }
+
+            /// Returns a mutable reference to the wrapped value.
+            ///
+            /// Bytes transferred directly through it don't count towards the limit.
+            pub fn get_mut(&mut self) -> &mut $inner {
+                &mut self.inner
+            }
+
+            /// Returns the wrapped value.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+        }
+    };
+}
+
+/// Reader adapter failing once more than a limited number of bytes are read through it.
+///
+/// Reads are cut short at the limit. Once it's reached, reading returns end-of-file if the wrapped
+/// reader is also exhausted, or a [`LimitExceeded`] error otherwise. To silently stop at the
+/// limit instead, use [`Read::take()`].
+///
+/// Telling these apart takes one more read from the wrapped reader once the limit is reached,
+/// even if it ends exactly at the limit. Through [`Read`], that read consumes one byte past the
+/// limit, which is kept as the [`overflow_byte()`](Self::overflow_byte) rather than being lost.
+/// Through [`BufRead`], nothing past the limit is consumed.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read as _;
+///
+/// use bytesize::{ByteSize, LimitExceeded, LimitedReader};
+///
+/// let mut reader = LimitedReader::new(&[0_u8; 1536][..], ByteSize::kib(1));
+/// let mut buf = Vec::new();
+/// let err = reader.read_to_end(&mut buf).unwrap_err();
+///
+/// let exceeded = err.get_ref().unwrap().downcast_ref::<LimitExceeded>().unwrap();
+/// assert_eq!(ByteSize::kib(1), exceeded.limit());
+/// assert_eq!(1024, buf.len());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct LimitedReader<R> {
+    inner: R,
+    limit: Limit,
+
+    /// Byte read from `inner` past the limit, when detecting that it was exceeded.
+    overflow: Option<u8>,
+}
+
+limited_adapter!(LimitedReader, R, "read through", overflow: None);
+
+impl<R> LimitedReader<R> {
+    /// Returns the byte read from the wrapped reader past the limit, if the limit was exceeded
+    /// through [`Read`].
+    ///
+    /// The wrapped reader has already consumed this byte, so it must be put back in front of the
+    /// rest of its data to keep reading from it, e.g., after [`into_inner()`](Self::into_inner).
+    pub const fn overflow_byte(&self) -> Option<u8> {
+        self.overflow
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.overflow.is_some() {
+            return Err(self.limit.exceeded());
+        }
+
+        if self.limit.remaining() == 0 {
+            // any more data would exceed the limit
+            let mut probe = [0; 1];
+            return match self.inner.read(&mut probe)? {
+                0 => Ok(0),
+                _ => {
+                    self.overflow = Some(probe[0]);
+                    Err(self.limit.exceeded())
+                }
+            };
+        }
+
+        let len = self.limit.clamp(buf.len());
+        let read = self.inner.read(&mut buf[..len])?;
+        self.limit.record(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for LimitedReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.overflow.is_some() {
+            return Err(self.limit.exceeded());
+        }
+
+        let buf = self.inner.fill_buf()?;
+
+        if self.limit.remaining() == 0 && !buf.is_empty() {
+            return Err(self.limit.exceeded());
+        }
+
+        Ok(&buf[..self.limit.clamp(buf.len())])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.limit.record(amt);
+    }
+}
+
+/// Writer adapter failing once more than a limited number of bytes are written through it.
+///
+/// Writes are cut short at the limit, and writing more once it's reached returns a
+/// [`LimitExceeded`] error, so no more than the limit is ever passed to the wrapped writer.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// use bytesize::{ByteSize, LimitedWriter};
+///
+/// # bytesize::set_default_format(bytesize::Format::Iec);
+/// let mut writer = LimitedWriter::new(Vec::new(), ByteSize::kib(4));
+/// writer.write_all(&[0; 4096]).unwrap();
+/// assert_eq!(ByteSize::b(0), writer.remaining());
+///
+/// let err = writer.write_all(b"more").unwrap_err();
+/// assert_eq!("byte limit of 4.0 KiB exceeded", err.to_string());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct LimitedWriter<W> {
+    inner: W,
+    limit: Limit,
+}
+
+limited_adapter!(LimitedWriter, W, "written through");
+
+impl<W: Write> Write for LimitedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.limit.clamp(buf.len());
+        if len == 0 && !buf.is_empty() {
+            return Err(self.limit.exceeded());
+        }
+
+        let written = self.inner.write(&buf[..len])?;
+        self.limit.record(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Error returned by [`LimitedReader`] and [`LimitedWriter`] when their limit is exceeded.
+///
+/// It's wrapped in an [`io::Error`] of kind [`FileTooLarge`](io::ErrorKind::FileTooLarge), and
+/// can be recovered with [`io::Error::get_ref()`] and `downcast_ref()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct LimitExceeded {
+    limit: ByteSize,
+}
+
+impl LimitExceeded {
+    const fn new(limit: ByteSize) -> Self {
+        Self { limit }
+    }
+
+    /// Returns the limit that was exceeded.
+    pub const fn limit(&self) -> ByteSize {
+        self.limit
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte limit of {} exceeded", self.limit)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+impl From<LimitExceeded> for io::Error {
+    fn from(err: LimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::FileTooLarge, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn reader() {
+        let data = vec![7_u8; 10_000];
+        let mut reader = CountingReader::new(&data[..]);
+
+        let mut buf = [0; 4096];
+        assert_eq!(4096, reader.read(&mut buf).unwrap());
+        assert_eq!(ByteSize(4096), reader.count());
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(ByteSize(10_000), reader.count());
+        #[cfg(feature = "unstable")]
+        assert_eq!(None, reader.rate());
+        assert!(reader.into_inner().is_empty());
+    }
+
+    #[test]
+    fn buf_reader() {
+        let mut reader = CountingReader::new(&b"one\ntwo\nthree\n"[..]);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(ByteSize(4), reader.count());
+
+        assert_eq!(b"two\nthree\n", reader.fill_buf().unwrap());
+        assert_eq!(ByteSize(4), reader.count());
+
+        reader.consume(4);
+        assert_eq!(ByteSize(8), reader.count());
+        assert_eq!(b"three\n", *reader.get_ref());
+    }
+
+    #[test]
+    fn writer() {
+        let mut writer = CountingWriter::new(Vec::new());
+
+        writer.write_all(b"hello, ").unwrap();
+        write!(writer, "{}", ByteSize::kib(1)).unwrap();
+        let written = writer
+            .write_vectored(&[IoSlice::new(b"a"), IoSlice::new(b"bc")])
+            .unwrap();
+        assert_eq!(3, written);
+        writer.flush().unwrap();
+
+        assert_eq!(ByteSize(17), writer.count());
+        assert_eq!(b"hello, 1.0 KiBabc", &writer.get_ref()[..]);
+
+        // writes bypassing the adapter aren't counted
+        writer.get_mut().push(b'!');
+        assert_eq!(ByteSize(17), writer.count());
+        assert_eq!(18, writer.into_inner().len());
+    }
+
+    #[test]
+    fn errors() {
+        struct Failing;
+
+        impl Write for Failing {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = CountingWriter::new(Failing);
+        assert!(writer.write(b"data").is_err());
+        assert_eq!(ByteSize(0), writer.count());
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn meter() {
+        let mut writer = CountingWriter::new(io::sink()).with_meter(ThroughputMeter::new());
+        writer.write_all(&[0; 1024]).unwrap();
+        writer.write_all(&[0; 1024]).unwrap();
+
+        let meter = writer.meter().unwrap();
+        assert_eq!(ByteSize::kib(2), meter.total());
+        assert!(writer.rate().is_some());
+    }
+
+    fn limit_exceeded(err: &io::Error) -> ByteSize {
+        assert_eq!(io::ErrorKind::FileTooLarge, err.kind());
+        err.get_ref()
+            .and_then(|err| err.downcast_ref::<LimitExceeded>())
+            .unwrap()
+            .limit()
+    }
+
+    #[test]
+    fn limited_reader() {
+        // data exactly at the limit reads to the end
+        let mut reader = LimitedReader::new(&[1_u8; 100][..], ByteSize(100));
+        let mut buf = Vec::new();
+        assert_eq!(100, reader.read_to_end(&mut buf).unwrap());
+        assert_eq!(ByteSize(0), reader.remaining());
+        assert_eq!(0, reader.read(&mut [0; 8]).unwrap());
+
+        let mut reader = LimitedReader::new(&[1_u8; 101][..], ByteSize(100));
+        let mut buf = [0; 64];
+        assert_eq!(64, reader.read(&mut buf).unwrap());
+        assert_eq!(36, reader.read(&mut buf).unwrap());
+        assert_eq!(ByteSize(100), reader.count());
+
+        let err = reader.read(&mut buf).unwrap_err();
+        assert_eq!(ByteSize(100), limit_exceeded(&err));
+        assert_eq!("byte limit of 100 B exceeded", err.to_string());
+    }
+
+    #[test]
+    fn limited_reader_overflow_byte() {
+        let data: Vec<u8> = (0..10).collect();
+
+        // ending exactly at the limit doesn't consume anything more
+        let mut reader = LimitedReader::new(io::Cursor::new(&data[..]), ByteSize(10));
+        assert_eq!(10, reader.read_to_end(&mut Vec::new()).unwrap());
+        assert_eq!(None, reader.overflow_byte());
+        assert_eq!(10, reader.get_ref().position());
+
+        let mut reader = LimitedReader::new(io::Cursor::new(&data[..]), ByteSize(4));
+        let mut buf = Vec::new();
+        assert!(reader.read_to_end(&mut buf).is_err());
+        assert_eq!([0, 1, 2, 3], *buf);
+        assert_eq!(Some(4), reader.overflow_byte());
+
+        // the wrapped reader isn't read any further
+        assert!(reader.read(&mut [0; 4]).is_err());
+        assert_eq!(5, reader.get_ref().position());
+
+        let mut inner = reader.into_inner();
+        let mut rest = Vec::new();
+        inner.read_to_end(&mut rest).unwrap();
+        assert_eq!([5, 6, 7, 8, 9], *rest);
+
+        // buffered reads stop before the limit is exceeded
+        let mut reader = LimitedReader::new(io::Cursor::new(&data[..]), ByteSize(4));
+        let mut buf = Vec::new();
+        assert!(reader.read_until(b'!', &mut buf).is_err());
+        assert_eq!(None, reader.overflow_byte());
+        assert_eq!(4, reader.get_ref().position());
+    }
+
+    #[test]
+    fn limited_buf_reader() {
+        let mut reader = LimitedReader::new(&b"one\ntwo\nthree\n"[..], ByteSize(6));
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!("one\n", line);
+        assert_eq!(b"tw", reader.fill_buf().unwrap());
+
+        reader.consume(2);
+        assert_eq!(ByteSize(6), reader.count());
+
+        let err = reader.read_line(&mut line).unwrap_err();
+        assert_eq!(ByteSize(6), limit_exceeded(&err));
+    }
+
+    #[test]
+    fn limited_writer() {
+        let mut writer = LimitedWriter::new(Vec::new(), ByteSize(10));
+        assert_eq!(ByteSize(10), writer.limit());
+
+        writer.write_all(b"hello, ").unwrap();
+        assert_eq!(3, writer.write(b"world").unwrap());
+        assert_eq!(ByteSize(0), writer.remaining());
+        assert_eq!(0, writer.write(b"").unwrap());
+
+        let err = writer.write_all(b"!").unwrap_err();
+        assert_eq!(ByteSize(10), limit_exceeded(&err));
+        assert_eq!(b"hello, wor", &writer.into_inner()[..]);
+
+        // `write_all` writes up to the limit before failing
+        let mut writer = LimitedWriter::new(Vec::new(), ByteSize::kib(1));
+        assert!(writer.write_all(&[0; 2048]).is_err());
+        assert_eq!(1024, writer.get_ref().len());
+    }
+}
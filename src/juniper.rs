@@ -0,0 +1,130 @@
+use alloc::string::{String, ToString as _};
+
+use juniper::{
+    graphql_scalar,
+    parser::{ParseError, ScalarToken, Token},
+    ParseScalarResult, Value,
+};
+
+use crate::{parse::whole_bytes, ByteSize};
+
+graphql_scalar!(ByteSize as "ByteSize" where Scalar = <S> {
+    description: "A size, formatted like \"1.5 KiB\". Accepts a whole, non-negative number of \
+                  bytes, or a string like \"1.5 KiB\"."
+
+    resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    from_input_value(v: &InputValue) -> Option<ByteSize> {
+        let value = v.as_scalar()?;
+
+        if let Some(size) = value.as_string() {
+            size.parse().ok()
+        } else if let Some(bytes) = value.as_int() {
+            u64::try_from(bytes).ok().map(ByteSize)
+        } else {
+            whole_bytes(value.as_float()?).ok()
+        }
+    }
+
+    from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            // kept as a string, since GraphQL's `Int` is only 32 bits
+            ScalarToken::String(value) | ScalarToken::Int(value) => {
+                Ok(S::from(String::from(value)))
+            }
+            ScalarToken::Float(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use juniper::{
+        graphql_object, DefaultScalarValue, EmptyMutation, FromInputValue, InputValue, RootNode,
+        ToInputValue, Variables,
+    };
+
+    use super::*;
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field quota() -> ByteSize {
+            ByteSize::kib(1536)
+        }
+
+        field double(size: ByteSize) -> ByteSize {
+            size * 2_u32
+        }
+    });
+
+    fn execute(query: &str, variables: &Variables) -> Value {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+        let (value, errors) = juniper::execute(query, None, &schema, variables, &()).unwrap();
+        assert_eq!(0, errors.len());
+        value
+    }
+
+    #[track_caller]
+    fn from_input(value: InputValue) -> Option<ByteSize> {
+        <ByteSize as FromInputValue<DefaultScalarValue>>::from_input_value(&value)
+    }
+
+    #[test]
+    fn query() {
+        let value = execute(
+            r#"{ quota a: double(size: "1 KiB") b: double(size: 1024) c: double(size: 8589934592) }"#,
+            &Variables::new(),
+        );
+        let object = value.as_object_value().unwrap();
+
+        for (field, expected) in [
+            ("quota", "1.5 MiB"),
+            ("a", "2.0 KiB"),
+            ("b", "2.0 KiB"),
+            ("c", "16.0 GiB"),
+        ] {
+            let actual = object.get_field_value(field).unwrap().as_scalar_value();
+            assert_eq!(Some(&expected.to_string()), actual, "{field}");
+        }
+    }
+
+    #[test]
+    fn variables() {
+        let query = "query($size: ByteSize!) { double(size: $size) }";
+
+        for input in [
+            InputValue::scalar("1 KiB"),
+            InputValue::scalar(1024),
+            InputValue::scalar(1024.0),
+        ] {
+            let variables = [("size".to_string(), input)].into_iter().collect();
+            let value = execute(query, &variables);
+            let object = value.as_object_value().unwrap();
+            let actual = object.get_field_value("double").unwrap().as_scalar_value();
+            assert_eq!(Some(&"2.0 KiB".to_string()), actual);
+        }
+    }
+
+    #[test]
+    fn input_values() {
+        assert_eq!(
+            Some(ByteSize(1536)),
+            from_input(InputValue::scalar("1.5 KiB"))
+        );
+        assert_eq!(Some(ByteSize(1536)), from_input(InputValue::scalar("1536")));
+        assert_eq!(Some(ByteSize(1536)), from_input(InputValue::scalar(1536)));
+        assert_eq!(Some(ByteSize(1536)), from_input(InputValue::scalar(1536.0)));
+
+        assert_eq!(None, from_input(InputValue::scalar(-1)));
+        assert_eq!(None, from_input(InputValue::scalar(1.5)));
+        assert_eq!(None, from_input(InputValue::scalar("1.5 parsecs")));
+        assert_eq!(None, from_input(InputValue::scalar(true)));
+        assert_eq!(None, from_input(InputValue::null()));
+
+        let value: InputValue = ByteSize(1536).to_input_value();
+        assert_eq!(Some(&"1.5 KiB".to_string()), value.as_scalar_value());
+    }
+}
@@ -40,24 +40,276 @@
 //! let minus = ByteSize::tb(1) - ByteSize::gb(4);
 //! assert_eq!(ByteSize::gb(996), minus);
 //! ```
+//!
+//! Constants can be defined from human-readable literals.
+//!
+//! ```
+//! use bytesize::{bytesize, ByteSize};
+//!
+//! const MAX_UPLOAD: ByteSize = bytesize!("1.5 GiB");
+//! assert_eq!(ByteSize::mib(1536), MAX_UPLOAD);
+//! ```
+//!
+//! # Crate Features
+//!
+//! The crate is `no_std` compatible but always requires `alloc`, since parsing errors and some
+//! helpers return `String`s. Allocation-free alternatives, like [`ByteSize::write_to()`] and
+//! [`Display::write_to()`], are available for formatting into existing buffers.
+//!
+//! - `std` (default): implements `std::error::Error` for error types and adds [`CachedDisplay`],
+//!   the [`CountingReader`], [`CountingWriter`], [`LimitedReader`], and [`LimitedWriter`] I/O
+//!   adapters, [`ByteSize::of_file()`], and [`ByteSize::of_dir()`].
+//! - `arbitrary`: implements `arbitrary::Arbitrary` for `ByteSize`.
+//! - `async-graphql`: implements `async-graphql`'s `ScalarType` for `ByteSize`, and adds the
+//!   [`async_graphql`] module with a scalar that outputs formatted strings. Requires `std`.
+//! - `bincode`: implements bincode 2's `Encode`, `Decode`, and `BorrowDecode` for `ByteSize`, as
+//!   a `u64`, without going through serde.
+//! - `bitcode`: implements `bitcode::{Encode, Decode}` for `ByteSize`.
+//! - `bson`: adds the [`bson`] module for reading sizes from BSON values and implements
+//!   `From<ByteSize>` for `Bson`. Requires `std`.
+//! - `bytemuck`: implements `bytemuck::{Pod, Zeroable}` for `ByteSize`.
+//! - `clap`: adds the [`clap`] module and implements `clap`'s `ValueParserFactory` for `ByteSize`.
+//!   Requires `std`.
+//! - `cli`: builds the `bytesize` command-line converter. Requires `std`.
+//! - `cli-compat`: adds the [`cli`] module for parsing `df`, `du`, `free`, and `ls`
+//!   output.
+//! - `defmt`: implements `defmt::Format` for `ByteSize`, logging the same IEC string as its
+//!   `Display` impl.
+//! - `diesel`: implements Diesel's `ToSql<BigInt>` and `FromSql<BigInt>` for `ByteSize`, for all
+//!   backends, and derives `AsExpression` and `FromSqlRow`. Requires `std`.
+//! - `display-si-default`: see [Default Format](#default-format).
+//! - `ffi`: adds the [`ffi`] module of C functions for parsing and formatting, declared in
+//!   `include/bytesize.h`.
+//! - `float-unit-selection`: selects display units with the floating point logarithms used before
+//!   integer logarithms, instead of exact integer math. Sizes just below a unit boundary, like
+//!   one byte less than 1 EiB, may then switch to the larger unit early. Kept for compatibility
+//!   until the next major release.
+//! - `juniper`: implements Juniper's `GraphQLType`, `FromInputValue`, and `ToInputValue` for
+//!   `ByteSize`, as a `ByteSize` scalar. It accepts numbers of bytes and strings like `"1.5 KiB"`,
+//!   and outputs strings formatted like `ByteSize`'s `Display` impl. Requires `std`.
+//! - `log`: implements `log::kv::ToValue` for `ByteSize`, as the number of bytes, so sizes can be
+//!   logged as structured key-values.
+//! - `macros`: adds the `byte_size!` procedural macro.
+//! - `napi`: implements napi-rs' `FromNapiValue` and `ToNapiValue` for `ByteSize`, converting from
+//!   JavaScript numbers, `BigInt`s, and strings like `"1.5 GiB"`. Requires `std`.
+//! - `opentelemetry`: adds the [`otel`] module and methods for OpenTelemetry attributes.
+//!   Requires `std`.
+//! - `postgres`: implements `postgres_types::{ToSql, FromSql}` for `ByteSize`, as used by
+//!   `tokio-postgres` and `postgres`, mapped to `int8` (`BIGINT`). Requires `std`.
+//! - `proptest`: adds the [`proptest`] module of strategies and implements `proptest`'s
+//!   `Arbitrary` for `ByteSize`. Requires `std`.
+//! - `pyo3`: implements PyO3's `FromPyObject` and `IntoPyObject` for `ByteSize`, converting from
+//!   Python `int`s and strings like `"1.5GiB"`, and to `int`s. Requires `std`.
+//! - `quickcheck`: implements `quickcheck::Arbitrary` for `ByteSize`. Requires `std`.
+//! - `rand`: adds the [`rand`] module, implements `SampleUniform` for `ByteSize`, and adds
+//!   methods for sampling sizes uniformly or log-uniformly. Requires `std`.
+//! - `schemars`: implements `schemars::JsonSchema` for `ByteSize`, describing its human-readable
+//!   serde representation.
+//! - `sea-orm`: implements SeaORM's `ValueType`, `TryGetable`, and `Nullable` for `ByteSize`, and
+//!   `From<ByteSize>` for `Value`, so entities can have `ByteSize` columns, stored as `BIGINT`.
+//!   Requires `std`.
+//! - `serde`: implements `Serialize` and `Deserialize` for `ByteSize` and
+//!   [`byte_newtype!`] types, and adds the [`serde`] module of field helpers.
+//! - `serde_with`: adds the [`serde_with`] module of `serde_with` adapters. Enables `serde`.
+//! - `slog`: implements `slog::Value` for `ByteSize`, logged as a nested value with both the
+//!   number of bytes and a human-readable string.
+//! - `speedy`: implements `speedy::{Readable, Writable}` for `ByteSize`, as a `u64`. Requires
+//!   `std`.
+//! - `sqlx`: implements `sqlx`'s `Type`, `Encode`, and `Decode` for `ByteSize`, stored as a
+//!   signed 64-bit integer (`BIGINT`) in any database supporting `i64`. Requires `std`.
+//! - `tokio`: adds the [`tokio`] module of counting adapters for Tokio's `AsyncRead` and
+//!   `AsyncWrite`, sharing an atomic [`tokio::ByteCounter`] across tasks. Requires `std`.
+//! - `ufmt`: implements `ufmt`'s `uDisplay` and `uDebug` for `ByteSize`, writing the same IEC
+//!   string as its `Display` and `Debug` impls without allocating, e.g., for AVR and MSP430
+//!   projects using `ufmt`.
+//! - `uniffi`: exports `ByteSize` as a UniFFI custom type, and adds the [`uniffi`] module of
+//!   exported functions for parsing and formatting. Requires `std`.
+//! - `unstable`: see [Stability](#stability).
+//! - `utoipa`: implements `utoipa::{PartialSchema, ToSchema}` for `ByteSize`, describing its
+//!   human-readable serde representation. Requires `std`.
+//! - `valuable`: implements `valuable::{Valuable, Structable}` for `ByteSize`, as a struct with
+//!   both the number of bytes and a human-readable string, e.g., for structured `tracing` output.
+//! - `wasm`: adds the [`wasm`] module of JavaScript bindings for parsing and formatting, using
+//!   `wasm-bindgen`. Requires `std`.
+//! - `zerocopy`: implements `zerocopy`'s `FromBytes`, `IntoBytes`, `Immutable`, and `KnownLayout`
+//!   for `ByteSize`.
+//!
+//! Every feature builds on its own and in combination with `no_std`, which is checked in CI.
+//!
+//! # Default Format
+//!
+//! `ByteSize`'s `Display` and `Debug` impls use IEC units, e.g., `1.5 KiB`. Applications can switch
+//! to SI units with the `display-si-default` crate feature, or to any format at runtime with
+//! [`set_default_format()`]. Since this affects every crate in the build, libraries should not
+//! enable the feature, and should use [`ByteSize::display()`] when they need a specific format.
+//!
+//! # Stability
+//!
+//! Items behind the `unstable` crate feature are experimental and may change or be removed in any
+//! release. Everything else follows semver.
+//!
+//! Currently, these are `ByteRate` and the transfer rate APIs built on it: `Eta`, arithmetic
+//! between sizes, rates, and durations, and, with `std`, `ThroughputMeter`, `RateLimiter`, and
+//! the rate measurement of the counting I/O adapters.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
 extern crate alloc;
 
-use alloc::string::ToString as _;
+use alloc::{format, string::String};
 use core::{fmt, iter, ops};
 
 #[cfg(feature = "arbitrary")]
 mod arbitrary;
+#[cfg(feature = "async-graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-graphql")))]
+pub mod async_graphql;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "bson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+pub mod bson;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "std")]
+mod cached;
+mod canonical;
+mod capacity;
+#[cfg(feature = "clap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+pub mod clap;
+#[cfg(feature = "cli-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cli-compat")))]
+pub mod cli;
+#[cfg(feature = "defmt")]
+mod defmt;
+// not named `diesel`, since the derives expand to paths starting with `diesel::`
+#[cfg(feature = "diesel")]
+mod diesel_types;
 mod display;
+mod distribute;
+#[cfg(feature = "unstable")]
+mod eta;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod fs;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "juniper")]
+mod juniper;
+#[cfg(all(feature = "std", feature = "unstable"))]
+mod limiter;
+#[cfg(feature = "log")]
+mod log;
+mod macros;
+#[cfg(all(feature = "std", feature = "unstable"))]
+mod meter;
+#[cfg(feature = "napi")]
+mod napi;
+mod newtype;
+#[cfg(feature = "opentelemetry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+pub mod otel;
 mod parse;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod preference;
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub mod proptest;
+#[cfg(feature = "pyo3")]
+mod pyo3;
+// also used by property tests
+#[cfg(any(test, feature = "quickcheck"))]
+mod quickcheck;
+#[cfg(feature = "rand")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+pub mod rand;
+#[cfg(feature = "unstable")]
+mod rate;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
 #[cfg(feature = "serde")]
-mod serde;
-
-pub use self::display::Display;
-use self::display::Format;
-pub use self::parse::{Unit, UnitParseError};
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+#[cfg(feature = "serde_with")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+pub mod serde_with;
+#[cfg(feature = "slog")]
+mod slog;
+#[cfg(feature = "speedy")]
+mod speedy;
+#[cfg(any(
+    feature = "diesel",
+    feature = "postgres",
+    feature = "sea-orm",
+    feature = "sqlx"
+))]
+mod sql;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(any(feature = "defmt", feature = "ufmt"))]
+mod stack_str;
+mod structured;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub mod tokio;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+#[cfg(feature = "uniffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uniffi")))]
+pub mod uniffi;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+#[cfg(feature = "valuable")]
+mod valuable;
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub mod wasm;
+
+#[cfg(feature = "uniffi")]
+::uniffi::setup_scaffolding!("bytesize");
+
+#[cfg(feature = "std")]
+pub use self::cached::CachedDisplay;
+pub use self::canonical::to_canonical_json;
+pub use self::display::{
+    default_format, display_of, display_size_of_val, set_default_format, Display, ExcessPrecision,
+    Exponent, Format, FormatOptions, Rounding, Separator, SubUnitBits, UnitLabels,
+};
+#[cfg(feature = "unstable")]
+pub use self::eta::Eta;
+#[cfg(feature = "std")]
+pub use self::fs::DirSizeOptions;
+#[cfg(feature = "std")]
+pub use self::io::{CountingReader, CountingWriter, LimitExceeded, LimitedReader, LimitedWriter};
+#[cfg(all(feature = "std", feature = "unstable"))]
+pub use self::limiter::RateLimiter;
+#[cfg(all(feature = "std", feature = "unstable"))]
+pub use self::meter::ThroughputMeter;
+pub use self::newtype::OutOfRangeError;
+pub use self::parse::{ParseOptions, Unit, UnitParseError};
+pub use self::preference::UnitPreference;
+#[cfg(feature = "unstable")]
+pub use self::rate::ByteRate;
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use bytesize_macros::byte_size;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::{String, ToString};
+
+    #[cfg(feature = "serde")]
+    pub use serde_core;
+
+    pub use crate::parse::parse_const;
+}
 
 /// Number of bytes in 1 kilobyte.
 pub const KB: u64 = 1_000;
@@ -95,12 +347,6 @@ const UNITS_IEC: &str = "KMGTPE";
 /// See <https://en.wikipedia.org/wiki/Kilobyte>.
 const UNITS_SI: &str = "kMGTPE";
 
-/// `ln(1024) ~= 6.931`
-const LN_KIB: f64 = 6.931_471_805_599_453;
-
-/// `ln(1000) ~= 6.908`
-const LN_KB: f64 = 6.907_755_278_982_137;
-
 /// Converts a quantity of kilobytes to bytes.
 pub fn kb(size: impl Into<u64>) -> u64 {
     size.into() * KB
@@ -162,7 +408,27 @@ pub fn eib<V: Into<u64>>(size: V) -> u64 {
 }
 
 /// Byte size representation.
+///
+/// `ByteSize` has the same layout as `u64`. With the `bytemuck` or `zerocopy` crate features, it
+/// implements their marker traits, so it can be read directly from memory-mapped or on-disk
+/// structures.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default)]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::BigInt)
+)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout
+    )
+)]
+#[repr(transparent)]
 pub struct ByteSize(pub u64);
 
 impl ByteSize {
@@ -250,6 +516,62 @@ impl ByteSize {
         self.0
     }
 
+    /// Returns the byte count as a little-endian byte array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!([0, 4, 0, 0, 0, 0, 0, 0], ByteSize::kib(1).to_le_bytes());
+    /// ```
+    #[inline(always)]
+    pub const fn to_le_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+
+    /// Returns the byte count as a big-endian byte array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!([0, 0, 0, 0, 0, 0, 4, 0], ByteSize::kib(1).to_be_bytes());
+    /// ```
+    #[inline(always)]
+    pub const fn to_be_bytes(self) -> [u8; 8] {
+        self.0.to_be_bytes()
+    }
+
+    /// Constructs a byte size from a little-endian byte array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!(ByteSize::kib(1), ByteSize::from_le_bytes([0, 4, 0, 0, 0, 0, 0, 0]));
+    /// ```
+    #[inline(always)]
+    pub const fn from_le_bytes(bytes: [u8; 8]) -> ByteSize {
+        ByteSize(u64::from_le_bytes(bytes))
+    }
+
+    /// Constructs a byte size from a big-endian byte array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!(ByteSize::kib(1), ByteSize::from_be_bytes([0, 0, 0, 0, 0, 0, 4, 0]));
+    /// ```
+    #[inline(always)]
+    pub const fn from_be_bytes(bytes: [u8; 8]) -> ByteSize {
+        ByteSize(u64::from_be_bytes(bytes))
+    }
+
     /// Returns byte count as kilobytes.
     #[inline(always)]
     pub fn as_kb(&self) -> f64 {
@@ -322,25 +644,329 @@ impl ByteSize {
         self.0 as f64 / EIB as f64
     }
 
+    /// Returns the byte count as a fixed-width, zero-padded string.
+    ///
+    /// The string is always 20 digits long, enough for any `u64`, so sorting these strings
+    /// lexicographically gives the same order as sorting the sizes. This is useful when sizes are
+    /// embedded in object store keys or log lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("00000000000000001024", ByteSize::kib(1).sortable_string());
+    /// assert!(ByteSize::kib(2).sortable_string() < ByteSize::kib(10).sortable_string());
+    /// ```
+    pub fn sortable_string(&self) -> String {
+        format!("{:020}", self.0)
+    }
+
+    /// Returns an exact, round-trippable string using the largest unit that divides evenly.
+    ///
+    /// Binary and decimal units are both tried, and whichever gives the smaller number is used,
+    /// preferring binary units on ties. Unlike the [`Display`] output, no precision is lost, so
+    /// parsing the result always gives back the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!("1536 KiB", ByteSize::kib(1536).to_string_exact());
+    /// assert_eq!("1500 MB", ByteSize::mb(1500).to_string_exact());
+    /// assert_eq!("7 B", ByteSize::b(7).to_string_exact());
+    /// ```
+    pub fn to_string_exact(&self) -> String {
+        if self.0 == 0 {
+            return String::from("0 B");
+        }
+
+        let (iec, iec_exp) = largest_exact_unit(self.0, KIB);
+        let (si, si_exp) = largest_exact_unit(self.0, KB);
+
+        let (value, exp, units, suffix) = if si < iec {
+            (si, si_exp, UNITS_SI, "B")
+        } else {
+            (iec, iec_exp, UNITS_IEC, "iB")
+        };
+
+        match exp {
+            0 => format!("{value} B"),
+            _ => format!("{value} {}{suffix}", char::from(units.as_bytes()[exp - 1]),),
+        }
+    }
+
+    /// Multiplies by a floating point factor.
+    ///
+    /// The result is truncated towards zero and saturates at `u64::MAX`. Negative and NaN factors
+    /// produce zero.
+    ///
+    /// Only integer math is used, so results are exact and identical on targets without hardware
+    /// floating point support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!(ByteSize::mib(3), ByteSize::mib(4).mul_f32(0.75));
+    /// assert_eq!(ByteSize::mib(3), 0.75_f32 * ByteSize::mib(4));
+    /// ```
+    pub fn mul_f32(self, factor: f32) -> ByteSize {
+        let bits = factor.to_bits();
+        let negative = bits >> 31 == 1;
+        let exp = ((bits >> 23) & 0xff) as i32;
+        let fraction = u64::from(bits & 0x7f_ffff);
+
+        match (negative, exp, fraction) {
+            (_, 0xff, 1..) | (true, _, _) => ByteSize(0),
+            (false, 0xff, 0) => ByteSize(if self.0 == 0 { 0 } else { u64::MAX }),
+            (false, 0, _) => ByteSize(mul_pow2(self.0, fraction, -149)),
+            (false, _, _) => ByteSize(mul_pow2(self.0, fraction | 1 << 23, exp - 150)),
+        }
+    }
+
+    /// Multiplies by a floating point factor.
+    ///
+    /// The result is truncated towards zero and saturates at `u64::MAX`. Negative and NaN factors
+    /// produce zero.
+    ///
+    /// Only integer math is used, so results are exact and identical on targets without hardware
+    /// floating point support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!(ByteSize::kb(1500), ByteSize::mb(1).mul_f64(1.5));
+    /// assert_eq!(ByteSize::kb(1500), 1.5 * ByteSize::mb(1));
+    /// ```
+    pub fn mul_f64(self, factor: f64) -> ByteSize {
+        let bits = factor.to_bits();
+        let negative = bits >> 63 == 1;
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & 0xf_ffff_ffff_ffff;
+
+        match (negative, exp, fraction) {
+            (_, 0x7ff, 1..) | (true, _, _) => ByteSize(0),
+            (false, 0x7ff, 0) => ByteSize(if self.0 == 0 { 0 } else { u64::MAX }),
+            (false, 0, _) => ByteSize(mul_pow2(self.0, fraction, -1074)),
+            (false, _, _) => ByteSize(mul_pow2(self.0, fraction | 1 << 52, exp - 1075)),
+        }
+    }
+
+    /// Returns the greatest common divisor of two sizes.
+    ///
+    /// This is the largest block size that evenly divides both. The GCD of any size and zero is
+    /// that size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let client_chunk = ByteSize::kib(96);
+    /// let server_stripe = ByteSize::kib(128);
+    ///
+    /// assert_eq!(ByteSize::kib(32), client_chunk.gcd(server_stripe));
+    /// ```
+    #[must_use]
+    pub const fn gcd(self, other: ByteSize) -> ByteSize {
+        let (mut a, mut b) = (self.0, other.0);
+
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        ByteSize(a)
+    }
+
+    /// Returns the least common multiple of two sizes, or `None` if it overflows.
+    ///
+    /// This is the smallest size that is a whole number of both blocks. The LCM of any size and
+    /// zero is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let client_chunk = ByteSize::kib(96);
+    /// let server_stripe = ByteSize::kib(128);
+    ///
+    /// assert_eq!(Some(ByteSize::kib(384)), client_chunk.checked_lcm(server_stripe));
+    /// assert_eq!(None, ByteSize::eib(15).checked_lcm(ByteSize::eib(14)));
+    /// ```
+    #[must_use]
+    pub const fn checked_lcm(self, other: ByteSize) -> Option<ByteSize> {
+        if self.0 == 0 || other.0 == 0 {
+            return Some(ByteSize(0));
+        }
+
+        match (self.0 / self.gcd(other).0).checked_mul(other.0) {
+            Some(lcm) => Some(ByteSize(lcm)),
+            None => None,
+        }
+    }
+
+    /// Raises the byte count to the power `exp`, returning `None` if it overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!(Some(ByteSize::mib(1)), ByteSize::b(1024).checked_pow(2));
+    /// assert_eq!(Some(ByteSize::b(1)), ByteSize::eib(8).checked_pow(0));
+    /// assert_eq!(None, ByteSize::gib(4).checked_pow(2));
+    /// ```
+    #[must_use]
+    pub const fn checked_pow(self, exp: u32) -> Option<ByteSize> {
+        match self.0.checked_pow(exp) {
+            Some(bytes) => Some(ByteSize(bytes)),
+            None => None,
+        }
+    }
+
+    /// Multiplies the size by each of `factors` in turn, returning `None` if it overflows.
+    ///
+    /// This is the overflow-checked form of a chain of `*` operators, for capacity math that
+    /// multiplies several dimensions together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let nodes = 40_u32;
+    /// let disks_per_node = 24_u32;
+    ///
+    /// assert_eq!(
+    ///     Some(ByteSize::tb(18_240)),
+    ///     ByteSize::tb(19).try_product([nodes, disks_per_node]),
+    /// );
+    /// assert_eq!(None, ByteSize::eb(1).try_product([nodes, disks_per_node]));
+    /// ```
+    #[must_use]
+    pub fn try_product<I>(self, factors: I) -> Option<ByteSize>
+    where
+        I: IntoIterator,
+        I::Item: Into<u64>,
+    {
+        factors
+            .into_iter()
+            .try_fold(self.0, |bytes, factor| bytes.checked_mul(factor.into()))
+            .map(ByteSize)
+    }
+
+    /// Writes the size, formatted with the default display style, to `out` without allocating.
+    ///
+    /// Use [`Display::write_to()`] to customize the style.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `out` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// let mut line = String::from("cache: ");
+    /// ByteSize::mib(42).write_to(&mut line)?;
+    /// assert_eq!("cache: 42.0 MiB", line);
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn write_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.display().write_to(out)
+    }
+
+    /// Formats the size using reusable `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, Format, FormatOptions};
+    ///
+    /// let options = FormatOptions::new().format(Format::Si).precision(2);
+    /// assert_eq!("1.50 MB", ByteSize::kb(1500).to_string_with(&options));
+    /// ```
+    pub fn to_string_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        options
+            .write_to(&mut out, *self)
+            .expect("a formatting trait implementation returned an error unexpectedly");
+        out
+    }
+
     /// Returns a formatting display wrapper.
     pub fn display(&self) -> Display {
         Display {
             byte_size: *self,
             format: Format::Iec,
+            unit: None,
+            excess_precision: ExcessPrecision::Pad,
+            sub_unit_bits: SubUnitBits::Bits,
+            trim_zeros: false,
+            separator: None,
+            long_units: false,
+            rounding: Rounding::HalfEven,
+            significant_figures: None,
+            auto_precision: false,
+            group_separator: None,
+            decimal_separator: '.',
+            remainder: false,
+            labels: None,
+            mixed_units: None,
+            engineering: None,
         }
     }
 }
 
+/// Divides non-zero `bytes` by `unit` as many times as it divides evenly, up to exa-, returning the
+/// quotient and the number of divisions.
+fn largest_exact_unit(mut bytes: u64, unit: u64) -> (u64, usize) {
+    let mut exp = 0;
+
+    while exp < UNITS_IEC.len() && bytes % unit == 0 {
+        bytes /= unit;
+        exp += 1;
+    }
+
+    (bytes, exp)
+}
+
+/// Computes `bytes * mantissa * 2^exp`, truncating towards zero and saturating at `u64::MAX`.
+///
+/// `mantissa` must be less than 2^53 so that the product fits in 128 bits.
+fn mul_pow2(bytes: u64, mantissa: u64, exp: i32) -> u64 {
+    let product = u128::from(bytes) * u128::from(mantissa);
+
+    let scaled = if product == 0 {
+        0
+    } else if exp >= 0 {
+        if exp >= product.leading_zeros() as i32 {
+            return u64::MAX;
+        }
+
+        product << exp
+    } else if exp > -128 {
+        product >> -exp
+    } else {
+        0
+    };
+
+    u64::try_from(scaled).unwrap_or(u64::MAX)
+}
+
 impl fmt::Display for ByteSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let display = self.display();
+        let mut display = self.display();
+        display.format = display::default_format();
 
-        if f.width().is_none() {
-            // allocation-free fast path for when no formatting options are specified
-            fmt::Display::fmt(&display, f)
-        } else {
-            f.pad(&display.to_string())
-        }
+        fmt::Display::fmt(&display, f)
     }
 }
 
@@ -375,6 +1001,26 @@ commutative_op!(u32);
 commutative_op!(u16);
 commutative_op!(u8);
 
+macro_rules! float_mul_op {
+    ($t:ty, $method:ident) => {
+        /// Multiplies by a floating point factor, like
+        #[doc = concat!("[`ByteSize::", stringify!($method), "()`].")]
+        ///
+        /// Only `factor * size` is supported, since `size * factor` would overlap with multiplying
+        /// by integers.
+        impl ops::Mul<ByteSize> for $t {
+            type Output = ByteSize;
+            #[inline(always)]
+            fn mul(self, rhs: ByteSize) -> ByteSize {
+                rhs.$method(self)
+            }
+        }
+    };
+}
+
+float_mul_op!(f32, mul_f32);
+float_mul_op!(f64, mul_f64);
+
 impl ops::Add<ByteSize> for ByteSize {
     type Output = ByteSize;
 
@@ -490,17 +1136,14 @@ where
 
 #[cfg(test)]
 mod property_tests {
-    use alloc::string::{String, ToString as _};
+    use alloc::{
+        format,
+        string::{String, ToString as _},
+    };
 
     use super::*;
 
-    impl quickcheck::Arbitrary for ByteSize {
-        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
-            Self(u64::arbitrary(g))
-        }
-    }
-
-    quickcheck::quickcheck! {
+    ::quickcheck::quickcheck! {
         fn parsing_never_panics(size: String) -> bool {
             let _ = size.parse::<ByteSize>();
             true
@@ -514,23 +1157,110 @@ mod property_tests {
             size.to_string().len() < 11
         }
 
+        fn display_paths_agree(size: ByteSize, precision: u8) -> bool {
+            let precision = usize::from(precision % 24);
+            let display = size.display();
+
+            let expected = format!("{display:.precision$}");
+
+            format!("{size:.precision$}") == expected
+                && format!("{size:40.precision$}").trim_end() == expected
+                && format!("{size:>40.precision$}").trim_start() == expected
+                && (precision != 1 || size.to_string() == expected)
+                && (precision != 1 || display.to_string() == expected)
+        }
+
+        fn mul_f64_matches_float_math(size: ByteSize, numerator: u16) -> bool {
+            // keep operands small enough that float math is exact
+            let size = ByteSize(size.0 >> 28);
+            let factor = f64::from(numerator) / 1024.0;
+
+            size.mul_f64(factor).0 == (size.0 as f64 * factor) as u64
+        }
+
+        fn sortable_string_order(a: ByteSize, b: ByteSize) -> bool {
+            a.cmp(&b) == a.sortable_string().cmp(&b.sortable_string())
+        }
+
+        fn gcd_lcm_divisibility(a: ByteSize, b: ByteSize) -> bool {
+            let gcd = a.gcd(b);
+
+            let gcd_divides = gcd.0 == 0 || (a.0 % gcd.0 == 0 && b.0 % gcd.0 == 0);
+
+            let lcm_divisible = match a.checked_lcm(b) {
+                Some(ByteSize(0)) => a.0 == 0 || b.0 == 0,
+                Some(lcm) => lcm.0 % a.0 == 0 && lcm.0 % b.0 == 0,
+                None => u128::from(a.0) * u128::from(b.0) / u128::from(gcd.0) > u128::from(u64::MAX),
+            };
+
+            gcd_divides && lcm_divisible
+        }
+
+        fn exact_string_round_trip(size: ByteSize) -> bool {
+            size.to_string_exact().parse::<ByteSize>().unwrap() == size
+        }
+
         fn string_round_trip(size: ByteSize) -> bool {
-            // currently fails on many inputs above the pebibyte level
-            if size > ByteSize::pib(1) {
-                return true;
-            }
+            // display is lossy, so the parsed size only has to display the same way
+            let string = size.to_string();
 
-            size.to_string().parse::<ByteSize>().unwrap() == size
+            string.parse::<ByteSize>().unwrap().to_string() == string
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use alloc::format;
+    use alloc::{format, string::ToString as _};
 
     use super::*;
 
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(ByteSize(0), ByteSize(0).gcd(ByteSize(0)));
+        assert_eq!(ByteSize::kib(4), ByteSize(0).gcd(ByteSize::kib(4)));
+        assert_eq!(ByteSize::kib(4), ByteSize::kib(4).gcd(ByteSize(0)));
+        assert_eq!(ByteSize(1), ByteSize(7).gcd(ByteSize(12)));
+        assert_eq!(ByteSize::kb(1), ByteSize::mb(1).gcd(ByteSize::kb(1)));
+        assert_eq!(ByteSize(8), ByteSize::kb(1).gcd(ByteSize::kib(1)));
+
+        assert_eq!(Some(ByteSize(0)), ByteSize(0).checked_lcm(ByteSize::kib(4)));
+        assert_eq!(Some(ByteSize(84)), ByteSize(7).checked_lcm(ByteSize(12)));
+        assert_eq!(
+            Some(ByteSize::mib(1)),
+            ByteSize::mib(1).checked_lcm(ByteSize::kib(4))
+        );
+        assert_eq!(
+            Some(ByteSize(128_000)),
+            ByteSize::kb(1).checked_lcm(ByteSize::kib(1)),
+        );
+        assert_eq!(
+            Some(ByteSize(u64::MAX)),
+            ByteSize(u64::MAX).checked_lcm(ByteSize(u64::MAX)),
+        );
+        assert_eq!(None, ByteSize(u64::MAX).checked_lcm(ByteSize(2)));
+    }
+
+    #[test]
+    fn to_string_exact() {
+        assert_eq!("0 B", ByteSize(0).to_string_exact());
+        assert_eq!("1023 B", ByteSize(1023).to_string_exact());
+        assert_eq!("1 KiB", ByteSize::kib(1).to_string_exact());
+        assert_eq!("1000 KiB", ByteSize::kb(1024).to_string_exact());
+        assert_eq!("1 kB", ByteSize::kb(1).to_string_exact());
+        assert_eq!("1500 kB", ByteSize::kb(1500).to_string_exact());
+        assert_eq!("1500 MB", ByteSize::mb(1500).to_string_exact());
+        assert_eq!("16 EB", ByteSize::eb(16).to_string_exact());
+        assert_eq!("1536 KiB", ByteSize::kib(1536).to_string_exact());
+        assert_eq!("1025 MiB", ByteSize::mib(1025).to_string_exact());
+        assert_eq!("15 EiB", ByteSize::eib(15).to_string_exact());
+        assert_eq!("8 EiB", ByteSize(1 << 63).to_string_exact());
+        assert_eq!(
+            "18446744073709551615 B",
+            ByteSize(u64::MAX).to_string_exact()
+        );
+    }
+
     #[test]
     fn test_arithmetic_op() {
         let mut x = ByteSize::mb(1);
@@ -569,6 +1299,67 @@ mod tests {
         assert_eq!(x.as_u64(), 3_000_011);
     }
 
+    #[test]
+    fn test_checked_pow() {
+        assert_eq!(Some(ByteSize::b(1)), ByteSize::b(0).checked_pow(0));
+        assert_eq!(Some(ByteSize::b(0)), ByteSize::b(0).checked_pow(3));
+        assert_eq!(Some(ByteSize::gib(1)), ByteSize::b(1024).checked_pow(3));
+        assert_eq!(Some(ByteSize(u64::MAX)), ByteSize(u64::MAX).checked_pow(1));
+        assert_eq!(Some(ByteSize::eib(8)), ByteSize::b(2).checked_pow(63));
+        assert_eq!(None, ByteSize::b(2).checked_pow(64));
+    }
+
+    #[test]
+    fn test_try_product() {
+        assert_eq!(
+            Some(ByteSize::kib(1)),
+            ByteSize::kib(1).try_product(iter::empty::<u64>()),
+        );
+        assert_eq!(
+            Some(ByteSize::tib(96)),
+            ByteSize::tib(4).try_product([4_u8, 6]),
+        );
+        assert_eq!(Some(ByteSize(0)), ByteSize(0).try_product([u64::MAX, 2]));
+        assert_eq!(None, ByteSize::eib(2).try_product([2_u64, 4, 0]));
+    }
+
+    #[test]
+    fn test_mul_float() {
+        assert_eq!(ByteSize::mib(4).mul_f32(0.75), ByteSize::mib(3));
+        assert_eq!(ByteSize::kib(1).mul_f32(0.1), ByteSize::b(102));
+        assert_eq!(ByteSize::b(3).mul_f32(0.5), ByteSize::b(1));
+        assert_eq!(ByteSize::gb(2).mul_f32(2.0), ByteSize::gb(4));
+        assert_eq!(
+            ByteSize::b(1).mul_f32(f32::MIN_POSITIVE / 2.0),
+            ByteSize::b(0)
+        );
+        assert_eq!(ByteSize::eib(1).mul_f32(16.0), ByteSize::b(u64::MAX));
+        assert_eq!(ByteSize::b(1).mul_f32(f32::MAX), ByteSize::b(u64::MAX));
+        assert_eq!(ByteSize::b(1).mul_f32(f32::INFINITY), ByteSize::b(u64::MAX));
+        assert_eq!(ByteSize::b(0).mul_f32(f32::INFINITY), ByteSize::b(0));
+        assert_eq!(ByteSize::b(1).mul_f32(f32::NAN), ByteSize::b(0));
+        assert_eq!(ByteSize::b(1).mul_f32(-1.0), ByteSize::b(0));
+        assert_eq!(ByteSize::b(1).mul_f32(-0.0), ByteSize::b(0));
+
+        assert_eq!(ByteSize::mb(1).mul_f64(1.5), ByteSize::kb(1500));
+        assert_eq!(
+            ByteSize::b(u64::MAX).mul_f64(0.5),
+            ByteSize::b(u64::MAX / 2)
+        );
+        assert_eq!(ByteSize::b(u64::MAX).mul_f64(1.0), ByteSize::b(u64::MAX));
+        assert_eq!(
+            ByteSize::b(1).mul_f64(f64::MIN_POSITIVE / 2.0),
+            ByteSize::b(0)
+        );
+        assert_eq!(ByteSize::b(1).mul_f64(f64::MAX), ByteSize::b(u64::MAX));
+        assert_eq!(ByteSize::b(1).mul_f64(f64::NAN), ByteSize::b(0));
+        assert_eq!(ByteSize::b(1).mul_f64(f64::NEG_INFINITY), ByteSize::b(0));
+
+        assert_eq!(0.75_f32 * ByteSize::mib(4), ByteSize::mib(3));
+        assert_eq!(1.5 * ByteSize::mb(1), ByteSize::kb(1500));
+        assert_eq!(-1.0 * ByteSize::mb(1), ByteSize::b(0));
+    }
+
     #[test]
     fn test_sum() {
         let sizes = [ByteSize::kb(1), ByteSize::mb(1), ByteSize::mib(1)];
@@ -621,6 +1412,42 @@ mod tests {
         assert_display("15.0 EiB", ByteSize::eib(15));
     }
 
+    #[test]
+    fn test_display_rounding_carries_unit() {
+        assert_display("1023 B", ByteSize::b(1023));
+        assert_display("1.0 MiB", ByteSize::mib(1) - ByteSize::b(1));
+        assert_display("1.0 TiB", ByteSize::b(1_099_501_374_362));
+        assert_display("1.0 EiB", ByteSize::eib(1) - ByteSize::b(1));
+        assert_display("16.0 EiB", ByteSize(u64::MAX));
+        assert_eq!("1.0 MB", ByteSize::b(999_999).display().si().to_string());
+        assert_eq!(
+            "1.00 MiB",
+            format!("{:.2}", ByteSize::mib(1) - ByteSize::b(1))
+        );
+        assert_eq!(
+            "1023.999 KiB",
+            format!("{:.3}", ByteSize::mib(1) - ByteSize::b(1))
+        );
+
+        // an explicit unit is never changed
+        assert_eq!(
+            "1024.0 KiB",
+            (ByteSize::mib(1) - ByteSize::b(1))
+                .display()
+                .in_unit(Unit::KibiByte)
+                .to_string(),
+        );
+
+        for size in [
+            ByteSize::mib(1) - ByteSize::b(1),
+            ByteSize::eib(14) + ByteSize::pib(1000),
+            ByteSize(u64::MAX),
+        ] {
+            let string = size.to_string();
+            assert_eq!(string, string.parse::<ByteSize>().unwrap().to_string());
+        }
+    }
+
     #[test]
     fn test_display_alignment() {
         assert_eq!("|357 B     |", format!("|{:10}|", ByteSize(357)));
@@ -631,10 +1458,63 @@ mod tests {
         assert_eq!("|-----357 B|", format!("|{:->10}|", ByteSize(357)));
         assert_eq!("|357 B-----|", format!("|{:-<10}|", ByteSize(357)));
         assert_eq!("|--357 B---|", format!("|{:-^10}|", ByteSize(357)));
+
+        assert_eq!("|1.86 GiB  |", format!("|{:10.2}|", ByteSize::mib(1908)));
+        assert_eq!("|  1.86 GiB|", format!("|{:>10.2}|", ByteSize::mib(1908)));
+        assert_eq!("|2 GiB|", format!("|{:2.0}|", ByteSize::mib(1908)));
+
+        // multi-byte characters count as a single column
+        let size = format!(
+            "{}",
+            ByteSize::kib(42)
+                .display()
+                .separator(Separator::NarrowNoBreakSpace)
+        );
+        assert_eq!("|  42.0\u{202f}KiB|", format!("|{size:>10}|"));
+    }
+
+    #[test]
+    fn test_sortable_string() {
+        assert_eq!("00000000000000000000", ByteSize(0).sortable_string());
+        assert_eq!("00000000000000000042", ByteSize(42).sortable_string());
+        assert_eq!("18446744073709551615", ByteSize(u64::MAX).sortable_string());
+    }
+
+    #[test]
+    fn test_endian_bytes() {
+        let size = ByteSize(0x0102_0304_0506_0708);
+        assert_eq!([8, 7, 6, 5, 4, 3, 2, 1], size.to_le_bytes());
+        assert_eq!([1, 2, 3, 4, 5, 6, 7, 8], size.to_be_bytes());
+        assert_eq!(size, ByteSize::from_le_bytes(size.to_le_bytes()));
+        assert_eq!(size, ByteSize::from_be_bytes(size.to_be_bytes()));
     }
 
     #[test]
     fn test_default() {
         assert_eq!(ByteSize::b(0), ByteSize::default());
     }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn test_zerocopy() {
+        use zerocopy::{FromBytes as _, IntoBytes as _};
+
+        let size = ByteSize::kib(4);
+        assert_eq!(&4096_u64.to_ne_bytes(), size.as_bytes());
+        assert_eq!(size, ByteSize::read_from_bytes(size.as_bytes()).unwrap());
+        assert!(ByteSize::read_from_bytes(&[0; 7]).is_err());
+    }
+
+    #[cfg(feature = "bitcode")]
+    #[test]
+    fn test_bitcode() {
+        let sizes = alloc::vec![ByteSize::kib(4), ByteSize(u64::MAX), ByteSize(0)];
+
+        let bytes = bitcode::encode(&sizes);
+        assert_eq!(
+            sizes,
+            bitcode::decode::<alloc::vec::Vec<ByteSize>>(&bytes).unwrap()
+        );
+        assert!(bitcode::decode::<ByteSize>(&[]).is_err());
+    }
 }
@@ -0,0 +1,202 @@
+use std::time::{Duration, Instant};
+
+use crate::{ByteRate, ByteSize};
+
+/// Token-bucket limiter, spacing out transfers to a [`ByteRate`].
+///
+/// The bucket holds up to [`burst()`](Self::burst) bytes and refills at [`rate()`](Self::rate).
+/// [Acquiring](Self::acquire) a chunk takes its size out of the bucket, and returns how long to
+/// wait before transferring it so the rate isn't exceeded. Chunks larger than the bucket are
+/// allowed, and are waited for as if the bucket could go into debt.
+///
+/// The limiter only keeps time; it doesn't sleep, so it works with or without an async runtime.
+/// Chunks should be acquired in chronological order.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use bytesize::{ByteSize, RateLimiter};
+///
+/// let start = Instant::now();
+/// let mut limiter = RateLimiter::starting_at("10 MiB/s".parse().unwrap(), start);
+///
+/// // the bucket starts full, with one second's worth of bytes
+/// assert_eq!(Duration::ZERO, limiter.acquire_at(ByteSize::mib(10), start));
+/// assert_eq!(
+///     Duration::from_millis(500),
+///     limiter.acquire_at(ByteSize::mib(5), start),
+/// );
+/// ```
+///
+/// Sleeping for the returned time limits a transfer:
+///
+/// ```no_run
+/// use std::{io::Read as _, thread};
+///
+/// use bytesize::{ByteRate, ByteSize, RateLimiter};
+///
+/// # let mut file = std::io::empty();
+/// let mut limiter = RateLimiter::new(ByteRate::mib(10));
+/// let mut buf = [0; 64 * 1024];
+///
+/// loop {
+///     let read = file.read(&mut buf)?;
+///     if read == 0 {
+///         break;
+///     }
+///     thread::sleep(limiter.acquire(ByteSize::b(read as u64)));
+///     // ... send `buf[..read]`
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "unstable"))))]
+pub struct RateLimiter {
+    rate: ByteRate,
+    burst: ByteSize,
+
+    /// Time the bucket is full again, after paying for every chunk acquired so far.
+    full_at: Instant,
+}
+
+impl RateLimiter {
+    /// Constructs a limiter to `rate`, with a full bucket of one second's worth of bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    pub fn new(rate: ByteRate) -> Self {
+        Self::starting_at(rate, Instant::now())
+    }
+
+    /// Constructs a limiter to `rate`, with a bucket of one second's worth of bytes which is full
+    /// at `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    pub fn starting_at(rate: ByteRate, start: Instant) -> Self {
+        assert!(rate.0 != 0, "rate must not be zero");
+
+        Self {
+            rate,
+            burst: rate.per_second(),
+            full_at: start,
+        }
+    }
+
+    /// Sets the size of the bucket, i.e., the most that can be transferred at once without
+    /// waiting after the limiter has been idle.
+    #[must_use]
+    pub const fn with_burst(mut self, burst: ByteSize) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Returns the rate the bucket refills at.
+    pub const fn rate(&self) -> ByteRate {
+        self.rate
+    }
+
+    /// Returns the size of the bucket.
+    pub const fn burst(&self) -> ByteSize {
+        self.burst
+    }
+
+    /// Acquires `size` bytes now, returning how long to wait before transferring them.
+    pub fn acquire(&mut self, size: ByteSize) -> Duration {
+        self.acquire_at(size, Instant::now())
+    }
+
+    /// Acquires `size` bytes at `now`, returning how long to wait before transferring them.
+    ///
+    /// Returns [`Duration::MAX`], without acquiring anything, if the wait is too long to
+    /// represent.
+    pub fn acquire_at(&mut self, size: ByteSize, now: Instant) -> Duration {
+        let cost = size.checked_div_rate(self.rate).unwrap_or(Duration::MAX);
+        let burst = self
+            .burst
+            .checked_div_rate(self.rate)
+            .unwrap_or(Duration::MAX);
+
+        let Some(full_at) = self.full_at.max(now).checked_add(cost) else {
+            return Duration::MAX;
+        };
+        self.full_at = full_at;
+
+        // the bucket is in debt until it's `burst` away from full
+        full_at.saturating_duration_since(now).saturating_sub(burst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn acquire() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::starting_at(ByteRate::kib(1), start);
+        assert_eq!(ByteSize::kib(1), limiter.burst());
+
+        assert_eq!(Duration::ZERO, limiter.acquire_at(ByteSize::kib(1), start));
+        assert_eq!(millis(500), limiter.acquire_at(ByteSize::b(512), start));
+
+        // half the bucket has refilled a second later
+        let now = start + millis(1000);
+        assert_eq!(Duration::ZERO, limiter.acquire_at(ByteSize::b(512), now));
+        assert_eq!(millis(1000), limiter.acquire_at(ByteSize::kib(1), now));
+
+        // an idle bucket refills no further than full
+        let now = start + millis(10_000);
+        assert_eq!(millis(1000), limiter.acquire_at(ByteSize::kib(2), now));
+
+        // acquiring nothing still waits out the debt
+        assert_eq!(millis(1000), limiter.acquire_at(ByteSize(0), now));
+        assert_eq!(
+            Duration::ZERO,
+            limiter.acquire_at(ByteSize(0), now + millis(1000))
+        );
+    }
+
+    #[test]
+    fn burst() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::starting_at(ByteRate::mib(1), start).with_burst(ByteSize(0));
+
+        // without a bucket, every chunk waits for the last
+        assert_eq!(millis(250), limiter.acquire_at(ByteSize::kib(256), start));
+        assert_eq!(millis(500), limiter.acquire_at(ByteSize::kib(256), start));
+        assert_eq!(
+            millis(250),
+            limiter.acquire_at(ByteSize::kib(256), start + millis(500))
+        );
+
+        let mut limiter =
+            RateLimiter::starting_at(ByteRate::mib(1), start).with_burst(ByteSize::mib(4));
+        assert_eq!(ByteRate::mib(1), limiter.rate());
+        assert_eq!(Duration::ZERO, limiter.acquire_at(ByteSize::mib(4), start));
+        assert_eq!(millis(1000), limiter.acquire_at(ByteSize::mib(1), start));
+    }
+
+    #[test]
+    fn overflow() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::starting_at(ByteRate(1), start);
+
+        assert_eq!(Duration::MAX, limiter.acquire_at(ByteSize(u64::MAX), start));
+        assert_eq!(Duration::ZERO, limiter.acquire_at(ByteSize(1), start));
+    }
+
+    #[test]
+    #[should_panic(expected = "rate must not be zero")]
+    fn zero_rate() {
+        let _ = RateLimiter::new(ByteRate(0));
+    }
+}
@@ -0,0 +1,40 @@
+use log::kv::{ToValue, Value};
+
+use crate::ByteSize;
+
+/// Captured as the number of bytes, e.g., `size = 1536`, so structured loggers get an integer.
+///
+/// To log the human-readable form instead, capture it with `Display`, e.g.,
+/// `log::info!(size:% = size; "...")`.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::ByteSize;
+///
+/// let size = ByteSize::kib(1536);
+/// log::info!(size; "upload finished");
+/// log::info!(size:% = size; "upload finished");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+impl ToValue for ByteSize {
+    fn to_value(&self) -> Value<'_> {
+        Value::from(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use super::*;
+
+    #[test]
+    fn to_value() {
+        let size = ByteSize::kib(1536);
+        let value = size.to_value();
+
+        assert_eq!(Some(1_572_864), value.to_u64());
+        assert_eq!("1572864", value.to_string());
+    }
+}
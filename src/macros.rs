@@ -0,0 +1,49 @@
+/// Constructs a [`ByteSize`](crate::ByteSize) from a human-readable literal at compile time.
+///
+/// Accepts either a string literal (e.g., `bytesize!("1.5 GiB")`) or a number followed by a unit
+/// (e.g., `bytesize!(1.5 GiB)`). The syntax is the same as [`ByteSize`](crate::ByteSize)'s
+/// `FromStr` impl, except that only ASCII whitespace may separate the number and unit, and sizes
+/// that don't fit in a `u64` are rejected instead of saturating.
+///
+/// The value is always computed in a const context, so invalid sizes are compile errors.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{bytesize, ByteSize};
+///
+/// const DEFAULT_LIMIT: ByteSize = bytesize!(1.5 GiB);
+/// assert_eq!(ByteSize::mib(1536), DEFAULT_LIMIT);
+///
+/// assert_eq!(ByteSize::kb(42), bytesize!("42kB"));
+/// assert_eq!(ByteSize::b(512), bytesize!(512));
+/// ```
+///
+/// Typos in units are caught at compile time.
+///
+/// ```compile_fail
+/// let size = bytesize::bytesize!("12.5MiBB");
+/// ```
+///
+/// So are sizes that overflow a `u64`, and decimal numbers without a unit.
+///
+/// ```compile_fail
+/// let size = bytesize::bytesize!("99 EiB");
+/// ```
+///
+/// ```compile_fail
+/// let size = bytesize::bytesize!("5.");
+/// ```
+#[macro_export]
+macro_rules! bytesize {
+    ($size:literal) => {{
+        const SIZE: $crate::ByteSize = $crate::__private::parse_const(::core::concat!($size));
+        SIZE
+    }};
+
+    ($size:literal $unit:ident) => {{
+        const SIZE: $crate::ByteSize =
+            $crate::__private::parse_const(::core::concat!($size, ::core::stringify!($unit),));
+        SIZE
+    }};
+}
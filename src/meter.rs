@@ -0,0 +1,372 @@
+use alloc::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{ByteRate, ByteSize};
+
+/// Measures the throughput of a transfer from the sizes of the chunks passing through it.
+///
+/// Each chunk is [recorded](Self::record) with the time it was transferred, and the meter reports
+/// three rates:
+///
+/// - [`current()`](Self::current): the rate over the last [`window()`](Self::window), e.g., for a
+///   progress bar;
+/// - [`average()`](Self::average): the rate since the meter was started, e.g., for a summary;
+/// - [`ewma()`](Self::ewma): an exponentially weighted moving average, which follows changes in
+///   rate smoothly, e.g., for an [`Eta`](crate::Eta).
+///
+/// Rates are zero until some time has passed since the meter was started. Chunks should be
+/// recorded in chronological order.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use bytesize::{ByteRate, ByteSize, ThroughputMeter};
+///
+/// # bytesize::set_default_format(bytesize::Format::Iec);
+/// let start = Instant::now();
+/// let mut meter = ThroughputMeter::starting_at(start);
+///
+/// for secs in 1..=10 {
+///     meter.record_at(ByteSize::mib(4), start + Duration::from_secs(secs));
+/// }
+///
+/// let now = start + Duration::from_secs(10);
+/// assert_eq!(ByteSize::mib(40), meter.total());
+/// assert_eq!(ByteRate::mib(4), meter.current_at(now));
+/// assert_eq!(ByteRate::mib(4), meter.average_at(now));
+///
+/// // the moving average starts from zero, so it's still catching up
+/// assert_eq!("3.6 MiB/s", meter.ewma_at(now).to_string());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "unstable"))))]
+pub struct ThroughputMeter {
+    start: Instant,
+    window: Duration,
+    half_life: Duration,
+    total: u64,
+
+    /// Chunks recorded within the window, oldest first.
+    samples: VecDeque<(Instant, u64)>,
+
+    /// Moving average in bytes per second, as of `last`.
+    ewma: f64,
+
+    /// Time the moving average was last updated.
+    last: Instant,
+
+    /// Bytes recorded at or before `last`, not yet included in the moving average.
+    pending: u64,
+}
+
+impl ThroughputMeter {
+    /// Window of [`current()`](Self::current) rates by default.
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+
+    /// Half-life of [`ewma()`](Self::ewma) rates by default.
+    pub const DEFAULT_HALF_LIFE: Duration = Duration::from_secs(3);
+
+    /// Constructs a meter started now.
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    /// Constructs a meter started at `start`.
+    pub fn starting_at(start: Instant) -> Self {
+        Self {
+            start,
+            window: Self::DEFAULT_WINDOW,
+            half_life: Self::DEFAULT_HALF_LIFE,
+            total: 0,
+            samples: VecDeque::new(),
+            ewma: 0.0,
+            last: start,
+            pending: 0,
+        }
+    }
+
+    /// Sets the window of [`current()`](Self::current) rates.
+    ///
+    /// Longer windows give steadier rates which are slower to follow changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    #[must_use]
+    pub fn with_window(mut self, window: Duration) -> Self {
+        assert!(!window.is_zero(), "window must not be zero");
+        self.window = window;
+        self
+    }
+
+    /// Sets the half-life of [`ewma()`](Self::ewma) rates, i.e., the time after which a chunk
+    /// counts for half as much as a new one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `half_life` is zero.
+    #[must_use]
+    pub fn with_half_life(mut self, half_life: Duration) -> Self {
+        assert!(!half_life.is_zero(), "half-life must not be zero");
+        self.half_life = half_life;
+        self
+    }
+
+    /// Returns the time the meter was started.
+    pub const fn start(&self) -> Instant {
+        self.start
+    }
+
+    /// Returns the window of [`current()`](Self::current) rates.
+    pub const fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Returns the half-life of [`ewma()`](Self::ewma) rates.
+    pub const fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Returns the total size recorded, saturating at `u64::MAX` bytes.
+    pub const fn total(&self) -> ByteSize {
+        ByteSize(self.total)
+    }
+
+    /// Records a chunk transferred now.
+    pub fn record(&mut self, size: ByteSize) {
+        self.record_at(size, Instant::now());
+    }
+
+    /// Records a chunk transferred at `at`.
+    pub fn record_at(&mut self, size: ByteSize, at: Instant) {
+        self.total = self.total.saturating_add(size.0);
+
+        while let Some(&(time, _)) = self.samples.front() {
+            if at.saturating_duration_since(time) < self.window {
+                break;
+            }
+            self.samples.pop_front();
+        }
+
+        match self.samples.back_mut() {
+            Some((time, bytes)) if *time >= at => *bytes = bytes.saturating_add(size.0),
+            _ => self.samples.push_back((at, size.0)),
+        }
+
+        self.pending = self.pending.saturating_add(size.0);
+
+        if at > self.last {
+            self.ewma = self.ewma_at_secs(at);
+            self.last = at;
+            self.pending = 0;
+        }
+    }
+
+    /// Returns the rate over the last [`window()`](Self::window).
+    pub fn current(&self) -> ByteRate {
+        self.current_at(Instant::now())
+    }
+
+    /// Returns the rate over the [`window()`](Self::window) up to `now`.
+    pub fn current_at(&self, now: Instant) -> ByteRate {
+        let bytes = self
+            .samples
+            .iter()
+            .filter(|&&(time, _)| time <= now && now.duration_since(time) < self.window)
+            .fold(0_u64, |total, &(_, bytes)| total.saturating_add(bytes));
+
+        let span = now.saturating_duration_since(self.start).min(self.window);
+        rate(ByteSize(bytes), span)
+    }
+
+    /// Returns the rate since the meter was started.
+    pub fn average(&self) -> ByteRate {
+        self.average_at(Instant::now())
+    }
+
+    /// Returns the rate from the start of the meter up to `now`.
+    pub fn average_at(&self, now: Instant) -> ByteRate {
+        rate(self.total(), now.saturating_duration_since(self.start))
+    }
+
+    /// Returns the exponentially weighted moving average of the rate.
+    pub fn ewma(&self) -> ByteRate {
+        self.ewma_at(Instant::now())
+    }
+
+    /// Returns the exponentially weighted moving average of the rate at `now`.
+    ///
+    /// The average decays towards zero while no chunks are recorded.
+    pub fn ewma_at(&self, now: Instant) -> ByteRate {
+        // float to int casts saturate
+        ByteRate(self.ewma_at_secs(now) as u64)
+    }
+
+    /// Returns the moving average at `now` in bytes per second, treating pending bytes as
+    /// transferred between `last` and `now`.
+    ///
+    /// Chunks are only averaged in once some time has passed since the previous update, since
+    /// their rate is unknown until then.
+    fn ewma_at_secs(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last);
+
+        if elapsed.is_zero() {
+            return self.ewma;
+        }
+
+        let rate = self.pending as f64 / elapsed.as_secs_f64();
+        let weight = 1.0 - (-elapsed.as_secs_f64() / self.half_life.as_secs_f64()).exp2();
+
+        self.ewma + weight * (rate - self.ewma)
+    }
+}
+
+impl Default for ThroughputMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the rate of transferring `size` in `duration`, or zero if no time has passed.
+fn rate(size: ByteSize, duration: Duration) -> ByteRate {
+    if duration.is_zero() {
+        ByteRate(0)
+    } else {
+        ByteRate::from_bytes_per(size, duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: f64) -> Duration {
+        Duration::from_secs_f64(secs)
+    }
+
+    #[test]
+    fn empty() {
+        let start = Instant::now();
+        let meter = ThroughputMeter::starting_at(start);
+
+        for now in [start, start + secs(1.0)] {
+            assert_eq!(ByteRate(0), meter.current_at(now));
+            assert_eq!(ByteRate(0), meter.average_at(now));
+            assert_eq!(ByteRate(0), meter.ewma_at(now));
+        }
+        assert_eq!(ByteSize(0), meter.total());
+    }
+
+    #[test]
+    fn no_time_elapsed() {
+        let start = Instant::now();
+        let mut meter = ThroughputMeter::starting_at(start);
+        meter.record_at(ByteSize::mib(1), start);
+
+        assert_eq!(ByteSize::mib(1), meter.total());
+        assert_eq!(ByteRate(0), meter.current_at(start));
+        assert_eq!(ByteRate(0), meter.average_at(start));
+        assert_eq!(ByteRate(0), meter.ewma_at(start));
+
+        assert_eq!(ByteRate::kib(2), meter.average_at(start + secs(512.0)));
+    }
+
+    #[test]
+    fn current() {
+        let start = Instant::now();
+        let mut meter = ThroughputMeter::starting_at(start).with_window(secs(2.0));
+
+        // half a window in, only the elapsed time counts
+        meter.record_at(ByteSize::kib(1), start + secs(0.5));
+        assert_eq!(ByteRate::kib(2), meter.current_at(start + secs(0.5)));
+
+        for tenths in 6..=40 {
+            meter.record_at(ByteSize::kib(1), start + secs(f64::from(tenths) / 10.0));
+        }
+        assert_eq!(ByteRate::kib(10), meter.current_at(start + secs(4.0)));
+        assert!(meter.samples.len() <= 20);
+
+        // chunks drop out of the window as time passes
+        assert_eq!(ByteRate::kib(5), meter.current_at(start + secs(5.0)));
+        assert_eq!(ByteRate(0), meter.current_at(start + secs(6.0)));
+
+        // chunks recorded at the same time are merged
+        meter.record_at(ByteSize::kib(1), start + secs(4.0));
+        assert_eq!(ByteSize::kib(37), meter.total());
+        assert_eq!(ByteRate(10752), meter.current_at(start + secs(4.0)));
+    }
+
+    #[test]
+    fn average() {
+        let start = Instant::now();
+        let mut meter = ThroughputMeter::starting_at(start);
+
+        meter.record_at(ByteSize::mib(10), start + secs(1.0));
+        meter.record_at(ByteSize::mib(20), start + secs(2.0));
+
+        assert_eq!(ByteRate::mib(15), meter.average_at(start + secs(2.0)));
+        assert_eq!(ByteRate::mib(10), meter.average_at(start + secs(3.0)));
+    }
+
+    #[test]
+    fn ewma() {
+        let start = Instant::now();
+        let at = |secs: u32| start + Duration::from_secs(secs.into());
+        let mut meter = ThroughputMeter::starting_at(start).with_half_life(secs(1.0));
+
+        // the average starts from zero and converges on a constant rate
+        for secs in 1..=5 {
+            meter.record_at(ByteSize::mib(8), at(secs));
+        }
+        let ewma = meter.ewma_at(at(5)).0 as f64;
+        let expected = ByteRate::mib(8).0 as f64 * (1.0 - 0.5_f64.powi(5));
+        assert!((ewma - expected).abs() <= 1.0, "{ewma} != {expected}");
+
+        // after a stall of one half-life, it halves
+        let stalled = meter.ewma_at(at(6)).0;
+        assert!(stalled.abs_diff(ewma as u64 / 2) <= 1, "{stalled}");
+
+        // and it follows a change in rate, with the old rate's weight halving every second
+        let mut meter = ThroughputMeter::starting_at(start).with_half_life(secs(1.0));
+        for secs in 1..=30 {
+            let size = if secs <= 20 {
+                ByteSize::mib(8)
+            } else {
+                ByteSize::mib(2)
+            };
+            meter.record_at(size, at(secs));
+        }
+        let ewma = meter.ewma_at(at(30)).0;
+        let expected = ByteSize::mib(2).0 + ByteSize::mib(6).0 / 1024;
+        assert!(ewma.abs_diff(expected) <= 1, "{ewma} != {expected}");
+
+        // chunks recorded at the same time are averaged in together
+        let mut meter = ThroughputMeter::starting_at(start).with_half_life(secs(1.0));
+        meter.record_at(ByteSize::mib(1), start);
+        meter.record_at(ByteSize::mib(1), at(1));
+        assert_eq!(ByteRate::mib(1), meter.ewma_at(at(1)));
+    }
+
+    #[test]
+    fn builders() {
+        let meter = ThroughputMeter::new()
+            .with_window(secs(5.0))
+            .with_half_life(secs(10.0));
+
+        assert_eq!(secs(5.0), meter.window());
+        assert_eq!(secs(10.0), meter.half_life());
+        assert!(meter.start() <= Instant::now());
+
+        let meter = ThroughputMeter::default();
+        assert_eq!(ThroughputMeter::DEFAULT_WINDOW, meter.window());
+        assert_eq!(ThroughputMeter::DEFAULT_HALF_LIFE, meter.half_life());
+    }
+
+    #[test]
+    #[should_panic(expected = "window must not be zero")]
+    fn zero_window() {
+        let _ = ThroughputMeter::new().with_window(Duration::ZERO);
+    }
+}
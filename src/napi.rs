@@ -0,0 +1,68 @@
+use alloc::{format, string::String};
+
+use napi::{
+    bindgen_prelude::{BigInt, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue},
+    check_status, sys, Error, Result, Status, ValueType,
+};
+
+use crate::{parse::whole_bytes, ByteSize};
+
+/// Largest integer a JavaScript `number` represents exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "napi")))]
+impl TypeName for ByteSize {
+    fn type_name() -> &'static str {
+        "ByteSize"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Unknown
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "napi")))]
+impl ValidateNapiValue for ByteSize {}
+
+/// Converts from a whole, non-negative `number` or `bigint` number of bytes, or a `string` like
+/// `"1.5 GiB"`.
+#[cfg_attr(docsrs, doc(cfg(feature = "napi")))]
+impl FromNapiValue for ByteSize {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        let mut value_type = 0;
+        check_status!(sys::napi_typeof(env, napi_val, &mut value_type))?;
+
+        match ValueType::from(value_type) {
+            ValueType::Number => whole_bytes(f64::from_napi_value(env, napi_val)?).map_err(invalid),
+            ValueType::BigInt => match BigInt::from_napi_value(env, napi_val)?.get_u64() {
+                (false, bytes, true) => Ok(ByteSize(bytes)),
+                (true, ..) => Err(invalid("sizes can't be negative".into())),
+                (false, ..) => Err(invalid("size is too large to be a number of bytes".into())),
+            },
+            ValueType::String => String::from_napi_value(env, napi_val)?
+                .parse()
+                .map_err(invalid),
+            value_type => Err(Error::new(
+                Status::InvalidArg,
+                format!("expected a number, bigint, or string size, found {value_type}"),
+            )),
+        }
+    }
+}
+
+/// Converts to a `number` of bytes, or a `bigint` for sizes above `Number.MAX_SAFE_INTEGER`
+/// (8 PiB), which a `number` can't represent exactly.
+#[cfg_attr(docsrs, doc(cfg(feature = "napi")))]
+impl ToNapiValue for ByteSize {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        if val.0 <= MAX_SAFE_INTEGER {
+            f64::to_napi_value(env, val.0 as f64)
+        } else {
+            BigInt::to_napi_value(env, BigInt::from(val.0))
+        }
+    }
+}
+
+fn invalid(err: String) -> Error {
+    Error::new(Status::InvalidArg, err)
+}
@@ -0,0 +1,253 @@
+use core::fmt;
+
+use crate::ByteSize;
+
+/// Defines a newtype around [`ByteSize`] for a distinct kind of size.
+///
+/// The generated type derives the usual comparison and hashing traits and implements `Display`,
+/// `FromStr`, and conversions to and from [`ByteSize`]. With the `serde` crate feature, it also
+/// implements `Serialize` and `Deserialize`.
+///
+/// An optional range restricts the accepted sizes. Bounded types implement `TryFrom<ByteSize>`
+/// with [`OutOfRangeError`] instead of `From<ByteSize>`, and parsing or deserializing an
+/// out-of-range size fails.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{byte_newtype, ByteSize};
+///
+/// byte_newtype! {
+///     /// Size of the in-memory cache.
+///     pub struct CacheSize;
+/// }
+///
+/// byte_newtype! {
+///     /// Largest accepted request body.
+///     pub struct MaxUploadSize(ByteSize::kib(1)..=ByteSize::gib(2));
+/// }
+///
+/// # bytesize::set_default_format(bytesize::Format::Iec);
+/// let cache = CacheSize::from(ByteSize::mib(64));
+/// assert_eq!("64.0 MiB", cache.to_string());
+///
+/// let upload = "1.5 GiB".parse::<MaxUploadSize>().unwrap();
+/// assert_eq!(ByteSize::mib(1536), upload.get());
+///
+/// assert!(MaxUploadSize::try_from(ByteSize::gib(3)).is_err());
+/// ```
+#[macro_export]
+macro_rules! byte_newtype {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident;) => {
+        $crate::__byte_newtype_common!($(#[$meta])* $vis struct $name);
+
+        impl ::core::convert::From<$crate::ByteSize> for $name {
+            fn from(size: $crate::ByteSize) -> Self {
+                Self(size)
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::__private::String;
+
+            fn from_str(value: &str) -> ::core::result::Result<Self, Self::Err> {
+                value.parse().map(Self)
+            }
+        }
+    };
+
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($range:expr);) => {
+        $crate::__byte_newtype_common!($(#[$meta])* $vis struct $name);
+
+        impl ::core::convert::TryFrom<$crate::ByteSize> for $name {
+            type Error = $crate::OutOfRangeError;
+
+            fn try_from(size: $crate::ByteSize) -> ::core::result::Result<Self, Self::Error> {
+                if ::core::ops::RangeBounds::contains(&($range), &size) {
+                    Ok(Self(size))
+                } else {
+                    Err($crate::OutOfRangeError::new(size))
+                }
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::__private::String;
+
+            fn from_str(value: &str) -> ::core::result::Result<Self, Self::Err> {
+                let size = value.parse::<$crate::ByteSize>()?;
+                Self::try_from(size).map_err(|err| $crate::__private::ToString::to_string(&err))
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __byte_newtype_common {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($crate::ByteSize);
+
+        impl $name {
+            /// Returns the wrapped byte size.
+            #[allow(dead_code)]
+            pub const fn get(self) -> $crate::ByteSize {
+                self.0
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::ByteSize {
+            fn from(size: $name) -> Self {
+                size.0
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        $crate::__byte_newtype_serde!($name);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __byte_newtype_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde_core::Serialize for $name {
+            fn serialize<S>(&self, ser: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde_core::Serializer,
+            {
+                $crate::__private::serde_core::Serialize::serialize(&self.0, ser)
+            }
+        }
+
+        impl<'de> $crate::__private::serde_core::Deserialize<'de> for $name {
+            fn deserialize<D>(de: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde_core::Deserializer<'de>,
+            {
+                let size = <$crate::ByteSize as $crate::__private::serde_core::Deserialize<'de>>::deserialize(de)?;
+                Self::try_from(size).map_err(<D::Error as $crate::__private::serde_core::de::Error>::custom)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __byte_newtype_serde {
+    ($name:ident) => {};
+}
+
+/// Error returned when a size is outside the range of a [`byte_newtype!`](crate::byte_newtype)
+/// type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfRangeError {
+    size: ByteSize,
+}
+
+impl OutOfRangeError {
+    #[doc(hidden)]
+    pub const fn new(size: ByteSize) -> Self {
+        Self { size }
+    }
+
+    /// Returns the rejected size.
+    pub const fn size(&self) -> ByteSize {
+        self.size
+    }
+}
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte size {} is out of range", self.size)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for OutOfRangeError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use super::*;
+
+    byte_newtype! {
+        struct CacheSize;
+    }
+
+    byte_newtype! {
+        struct UploadSize(ByteSize::kib(1)..=ByteSize::gib(2));
+    }
+
+    byte_newtype! {
+        struct ChunkSize(ByteSize::mib(5)..);
+    }
+
+    #[test]
+    fn unbounded() {
+        let size = CacheSize::from(ByteSize::mib(64));
+        assert_eq!(ByteSize::mib(64), size.get());
+        assert_eq!(ByteSize::mib(64), ByteSize::from(size));
+        assert_eq!("64.0 MiB", size.to_string());
+        assert_eq!(size, "64 MiB".parse().unwrap());
+        assert!("64 MiBB".parse::<CacheSize>().is_err());
+    }
+
+    #[test]
+    fn bounded() {
+        assert_eq!(
+            ByteSize::kib(1),
+            UploadSize::try_from(ByteSize::kib(1)).unwrap().get(),
+        );
+        assert_eq!(
+            ByteSize::gib(2),
+            UploadSize::try_from(ByteSize::gib(2)).unwrap().get(),
+        );
+
+        let err = UploadSize::try_from(ByteSize::b(1023)).unwrap_err();
+        assert_eq!(ByteSize::b(1023), err.size());
+        assert_eq!("byte size 1023 B is out of range", err.to_string());
+
+        assert!(UploadSize::try_from(ByteSize::gib(2) + 1_u64).is_err());
+        assert!(ChunkSize::try_from(ByteSize(u64::MAX)).is_ok());
+        assert!(ChunkSize::try_from(ByteSize::mib(4)).is_err());
+    }
+
+    #[test]
+    fn bounded_parsing() {
+        assert_eq!(
+            ByteSize::mib(5),
+            "5 MiB".parse::<ChunkSize>().unwrap().get(),
+        );
+        assert_eq!(
+            "byte size 4.0 MiB is out of range",
+            "4 MiB".parse::<ChunkSize>().unwrap_err(),
+        );
+        assert!("5 MiBB".parse::<ChunkSize>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let size = serde_json::from_str::<UploadSize>(r#""1 MiB""#).unwrap();
+        assert_eq!(ByteSize::mib(1), size.get());
+        assert_eq!(r#""1.0 MiB""#, serde_json::to_string(&size).unwrap());
+
+        let err = serde_json::from_str::<UploadSize>("1").unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        let size = serde_json::from_str::<CacheSize>("1024").unwrap();
+        assert_eq!(ByteSize::kib(1), size.get());
+    }
+}
@@ -0,0 +1,135 @@
+//! OpenTelemetry attribute helpers.
+//!
+//! OpenTelemetry semantic conventions record sizes as plain integer attributes holding a number
+//! of bytes, e.g., `http.response.body.size = 1536`. The helpers here produce and read such
+//! attributes so instrumentation doesn't have to convert by hand. Attribute values are `i64`, so
+//! sizes above [`i64::MAX`] bytes (8 EiB) saturate.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::{otel, ByteSize};
+//! use opentelemetry::Value;
+//!
+//! let attributes = [
+//!     ByteSize::kib(4).to_key_value(otel::HTTP_REQUEST_BODY_SIZE),
+//!     ByteSize::mib(1).to_key_value(otel::HTTP_RESPONSE_BODY_SIZE),
+//! ];
+//!
+//! assert_eq!(Value::I64(1_048_576), attributes[1].value);
+//!
+//! assert_eq!(
+//!     Some(ByteSize::mib(1)),
+//!     ByteSize::from_attributes(&attributes, otel::HTTP_RESPONSE_BODY_SIZE),
+//! );
+//! ```
+
+use opentelemetry::{Key, KeyValue, Value};
+
+use crate::ByteSize;
+
+/// Size of the HTTP request body, in bytes.
+pub const HTTP_REQUEST_BODY_SIZE: &str = "http.request.body.size";
+
+/// Size of the HTTP response body, in bytes.
+pub const HTTP_RESPONSE_BODY_SIZE: &str = "http.response.body.size";
+
+/// Size of a messaging system's message body, in bytes.
+pub const MESSAGING_MESSAGE_BODY_SIZE: &str = "messaging.message.body.size";
+
+/// Compressed size of an RPC message, in bytes.
+pub const RPC_MESSAGE_COMPRESSED_SIZE: &str = "rpc.message.compressed_size";
+
+/// Uncompressed size of an RPC message, in bytes.
+pub const RPC_MESSAGE_UNCOMPRESSED_SIZE: &str = "rpc.message.uncompressed_size";
+
+/// Size of a file, in bytes.
+pub const FILE_SIZE: &str = "file.size";
+
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+impl From<ByteSize> for Value {
+    /// Converts to an integer attribute value holding the number of bytes, saturating at
+    /// [`i64::MAX`].
+    fn from(size: ByteSize) -> Self {
+        Value::I64(i64::try_from(size.0).unwrap_or(i64::MAX))
+    }
+}
+
+impl ByteSize {
+    /// Returns an attribute with the given `key` and the size, in bytes, as its value.
+    ///
+    /// Sizes above [`i64::MAX`] bytes saturate. See the [`otel`](crate::otel) module for
+    /// standard keys.
+    #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+    pub fn to_key_value(self, key: impl Into<Key>) -> KeyValue {
+        KeyValue::new(key, self)
+    }
+
+    /// Reads a size, in bytes, from a non-negative integer attribute `value`.
+    ///
+    /// Returns `None` for other values.
+    #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+    pub fn from_otel_value(value: &Value) -> Option<ByteSize> {
+        match *value {
+            Value::I64(bytes) => u64::try_from(bytes).ok().map(ByteSize),
+            _ => None,
+        }
+    }
+
+    /// Finds the attribute with the given `key` and reads its value as a size, in bytes.
+    ///
+    /// Returns `None` if there is no such attribute or its value isn't a non-negative integer.
+    #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+    pub fn from_attributes<'a>(
+        attributes: impl IntoIterator<Item = &'a KeyValue>,
+        key: &str,
+    ) -> Option<ByteSize> {
+        attributes
+            .into_iter()
+            .find(|attribute| attribute.key.as_str() == key)
+            .and_then(|attribute| ByteSize::from_otel_value(&attribute.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_key_value() {
+        let attribute = ByteSize::kib(4).to_key_value(FILE_SIZE);
+        assert_eq!(FILE_SIZE, attribute.key.as_str());
+        assert_eq!(Value::I64(4096), attribute.value);
+
+        assert_eq!(Value::I64(i64::MAX), Value::from(ByteSize::eib(8)));
+        assert_eq!(Value::I64(i64::MAX), Value::from(ByteSize(u64::MAX)));
+    }
+
+    #[test]
+    fn from_attributes() {
+        let attributes = [
+            KeyValue::new("http.request.method", "GET"),
+            KeyValue::new(HTTP_REQUEST_BODY_SIZE, -1_i64),
+            KeyValue::new(RPC_MESSAGE_COMPRESSED_SIZE, 1.5),
+            ByteSize::mib(3).to_key_value(HTTP_RESPONSE_BODY_SIZE),
+        ];
+
+        assert_eq!(
+            Some(ByteSize::mib(3)),
+            ByteSize::from_attributes(&attributes, HTTP_RESPONSE_BODY_SIZE),
+        );
+        assert_eq!(
+            None,
+            ByteSize::from_attributes(&attributes, "http.request.method")
+        );
+        assert_eq!(
+            None,
+            ByteSize::from_attributes(&attributes, HTTP_REQUEST_BODY_SIZE)
+        );
+        assert_eq!(
+            None,
+            ByteSize::from_attributes(&attributes, RPC_MESSAGE_COMPRESSED_SIZE)
+        );
+        assert_eq!(None, ByteSize::from_attributes(&attributes, FILE_SIZE));
+    }
+}
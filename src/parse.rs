@@ -1,4 +1,4 @@
-use alloc::{borrow::ToOwned as _, format, string::String};
+use alloc::{borrow::ToOwned as _, format, string::String, vec, vec::Vec};
 use core::{fmt, str};
 
 use super::ByteSize;
@@ -7,15 +7,73 @@ impl str::FromStr for ByteSize {
     type Err = String;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ParseOptions::new().parse(value)
+    }
+}
+
+/// Options for parsing byte size strings.
+///
+/// The default options match [`ByteSize`]'s `FromStr` impl.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, ParseOptions};
+///
+/// // "mB" would be millibytes, which are rejected by default
+/// assert!("5 mB".parse::<ByteSize>().is_err());
+///
+/// let lenient = ParseOptions::new().lenient();
+/// assert_eq!(ByteSize::mb(5), lenient.parse("5 mB").unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    lenient: bool,
+}
+
+impl ParseOptions {
+    /// Returns the default parsing options.
+    pub const fn new() -> Self {
+        Self { lenient: false }
+    }
+
+    /// Accept common mistakes in unit case, treating `mB` (millibytes) as `MB` (megabytes).
+    #[must_use]
+    pub const fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Parses a byte size string, e.g., `1.5 KiB` or `512`.
+    pub fn parse(&self, value: &str) -> Result<ByteSize, String> {
         if let Ok(v) = value.parse::<u64>() {
-            return Ok(Self(v));
+            return Ok(ByteSize(v));
         }
-        let number = take_while(value, |c| c.is_ascii_digit() || c == '.');
-        match number.parse::<f64>() {
+
+        // number and unit are split on an ASCII boundary so slicing `value` is always valid
+        let number_len = value
+            .bytes()
+            .position(|b| !(b.is_ascii_digit() || b == b'.'))
+            .unwrap_or(value.len());
+        let (number, suffix) = value.split_at(number_len);
+
+        match Decimal::parse(number.as_bytes()) {
             Ok(v) => {
-                let suffix = skip_while(&value[number.len()..], char::is_whitespace);
+                let suffix = suffix.trim_start();
+
+                if self.lenient && suffix == "mB" {
+                    return Ok(ByteSize(v.scale(Unit::MegaByte.factor())));
+                }
+
+                if is_sub_byte_unit(suffix.as_bytes()) {
+                    return Err(format!(
+                        "{suffix:?} is a fraction of a byte, which is not supported since bytes \
+                        are integral; for megabytes, use \"MB\""
+                    ));
+                }
+
                 match suffix.parse::<Unit>() {
-                    Ok(u) => Ok(Self((v * u) as u64)),
+                    Ok(u) => Ok(ByteSize(v.scale(u.factor()))),
                     Err(error) => Err(format!(
                         "couldn't parse {suffix:?} into a known SI unit, {error}"
                     )),
@@ -26,28 +84,218 @@ impl str::FromStr for ByteSize {
     }
 }
 
-fn take_while<P>(s: &str, mut predicate: P) -> &str
-where
-    P: FnMut(char) -> bool,
-{
-    let offset = s
-        .chars()
-        .take_while(|ch| predicate(*ch))
-        .map(|ch| ch.len_utf8())
-        .sum();
-    &s[..offset]
+/// Returns true for units smaller than a byte, like `mB` (millibytes) or `µB` (microbytes).
+///
+/// These are usually mistyped megabytes, so they are rejected with a dedicated error rather than
+/// being read case-insensitively.
+pub(crate) const fn is_sub_byte_unit(unit: &[u8]) -> bool {
+    matches!(
+        unit,
+        b"mB" | b"uB" | b"\xc2\xb5B" /* MICRO SIGN */ | b"\xce\xbcB" /* GREEK SMALL LETTER MU */
+    )
+}
+
+/// Matches the strings accepted by `ByteSize`'s `FromStr` impl: a plain integer number of bytes,
+/// or a decimal number followed by a unit symbol, ignoring case except that `mB` (millibytes) is
+/// rejected.
+#[cfg(any(test, feature = "schemars", feature = "utoipa"))]
+pub(crate) const SIZE_PATTERN: &str = concat!(
+    r"^(?:[0-9]+|(?:[0-9]+\.?[0-9]*|\.[0-9]+)\s*",
+    r"(?:[bB]|[kKgGtTpPeE][iI]?[bB]?|M[iI]?[bB]?|m(?:[iI][bB]?|b)?))$",
+);
+
+/// Converts a floating-point number of bytes, e.g., from JavaScript, failing unless it's a whole,
+/// non-negative number in range.
+#[cfg(any(
+    feature = "async-graphql",
+    feature = "bson",
+    feature = "juniper",
+    feature = "napi"
+))]
+pub(crate) fn whole_bytes(bytes: f64) -> Result<ByteSize, String> {
+    // 2^64, the first value above `u64::MAX`
+    const LIMIT: f64 = 18_446_744_073_709_551_616.0;
+
+    if bytes.is_sign_negative() && bytes != 0.0 {
+        Err(format!(
+            "{bytes} is not a valid size, sizes can't be negative"
+        ))
+    } else if bytes >= LIMIT {
+        Err(format!("{bytes} is too large to be a number of bytes"))
+    } else if bytes.fract() != 0.0 {
+        Err(format!("{bytes} is not a whole number of bytes"))
+    } else {
+        Ok(ByteSize(bytes as u64))
+    }
+}
+
+/// Maximum number of fractional digits that are taken into account when parsing.
+///
+/// Keeping the fraction below 10^19 lets it be scaled by any unit factor in 128-bit math. Digits
+/// past this point are worth less than one byte, even for exbibytes, and are ignored.
+const MAX_FRACTION_DIGITS: u32 = 19;
+
+/// Non-negative decimal number parsed from a byte string without allocating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Decimal {
+    /// Integer part, saturated at `u128::MAX`, so integers too large for a `u64` can be detected.
+    integer: u128,
+
+    /// Fractional digits, as an integer.
+    fraction: u64,
+
+    /// Number of fractional digits held in `fraction`.
+    fraction_digits: u32,
+}
+
+impl Decimal {
+    /// Parses a string of ASCII digits with at most one decimal point.
+    ///
+    /// Either side of the decimal point may be empty (e.g., `5.` or `.5`) but not both.
+    pub(crate) const fn parse(src: &[u8]) -> Result<Self, DecimalParseError> {
+        if src.is_empty() {
+            return Err(DecimalParseError::Empty);
+        }
+
+        let mut decimal = Decimal {
+            integer: 0,
+            fraction: 0,
+            fraction_digits: 0,
+        };
+        let mut seen_point = false;
+        let mut seen_digit = false;
+
+        // manual loop since iterators can not be used in const fns
+        let mut idx = 0;
+        while idx < src.len() {
+            match src[idx] {
+                b @ b'0'..=b'9' => {
+                    let digit = b - b'0';
+                    seen_digit = true;
+
+                    if !seen_point {
+                        decimal.integer = decimal
+                            .integer
+                            .saturating_mul(10)
+                            .saturating_add(digit as u128);
+                    } else if decimal.fraction_digits < MAX_FRACTION_DIGITS {
+                        decimal.fraction = decimal.fraction * 10 + digit as u64;
+                        decimal.fraction_digits += 1;
+                    }
+                }
+                b'.' if !seen_point => seen_point = true,
+                _ => return Err(DecimalParseError::Invalid),
+            }
+
+            idx += 1;
+        }
+
+        if !seen_digit {
+            return Err(DecimalParseError::Invalid);
+        }
+
+        Ok(decimal)
+    }
+
+    /// Multiplies by `factor`, truncating any fractional byte and saturating at `u64::MAX`.
+    pub(crate) const fn scale(self, factor: u64) -> u64 {
+        match self.checked_scale(factor) {
+            Some(bytes) => bytes,
+            None => u64::MAX,
+        }
+    }
+
+    /// Multiplies by `factor`, truncating any fractional byte, or returns `None` if the result
+    /// doesn't fit in a `u64`.
+    pub(crate) const fn checked_scale(self, factor: u64) -> Option<u64> {
+        let bytes = self.scale_wide(factor);
+
+        if bytes > u64::MAX as u128 {
+            None
+        } else {
+            Some(bytes as u64)
+        }
+    }
+
+    /// Multiplies by `factor` in 128-bit math, truncating any fractional part.
+    ///
+    /// Saturates at `u128::MAX`, which is only reached by integers far out of range of a `u64`.
+    pub(crate) const fn scale_wide(self, factor: u64) -> u128 {
+        let fraction = self.fraction as u128 * factor as u128 / 10_u128.pow(self.fraction_digits);
+
+        self.integer
+            .saturating_mul(factor as u128)
+            .saturating_add(fraction)
+    }
+}
+
+/// Parses a byte size string in a const context, panicking if it is invalid.
+///
+/// Used by the [`bytesize!`](crate::bytesize!) macro. Only ASCII whitespace is accepted between
+/// the number and unit. Unlike `FromStr`, sizes that don't fit in a `u64` are rejected instead of
+/// saturating, so they fail to compile.
+pub const fn parse_const(src: &str) -> ByteSize {
+    let src = src.as_bytes();
+
+    let mut number_len = 0;
+    let mut is_integer = true;
+    while number_len < src.len() {
+        match src[number_len] {
+            b'0'..=b'9' => {}
+            b'.' => is_integer = false,
+            _ => break,
+        }
+        number_len += 1;
+    }
+    let (number, mut suffix) = src.split_at(number_len);
+
+    while let [first, rest @ ..] = suffix {
+        if !first.is_ascii_whitespace() {
+            break;
+        }
+        suffix = rest;
+    }
+
+    let decimal = match Decimal::parse(number) {
+        Ok(decimal) => decimal,
+        Err(_) => panic!("invalid number in byte size"),
+    };
+
+    if is_sub_byte_unit(suffix) {
+        panic!("sub-byte unit in byte size");
+    }
+
+    let factor = match Unit::from_ascii(suffix) {
+        Some(unit) => unit.factor(),
+        // plain integers are bytes
+        None if suffix.is_empty() && is_integer => 1,
+        None if suffix.is_empty() => panic!("missing unit after decimal number in byte size"),
+        None => panic!(
+            "unknown unit in byte size, expected one of: B, KB, MB, GB, TB, PB, EB, KiB, MiB, GiB, \
+             TiB, PiB, EiB"
+        ),
+    };
+
+    match decimal.checked_scale(factor) {
+        Some(bytes) => ByteSize(bytes),
+        None => panic!("byte size does not fit in a u64"),
+    }
+}
+
+/// Error returned when the numeric part of a byte size string is malformed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum DecimalParseError {
+    Empty,
+    Invalid,
 }
 
-fn skip_while<P>(s: &str, mut predicate: P) -> &str
-where
-    P: FnMut(char) -> bool,
-{
-    let offset: usize = s
-        .chars()
-        .skip_while(|ch| predicate(*ch))
-        .map(|ch| ch.len_utf8())
-        .sum();
-    &s[(s.len() - offset)..]
+impl fmt::Display for DecimalParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("cannot parse number from empty string"),
+            Self::Invalid => f.write_str("invalid number literal"),
+        }
+    }
 }
 
 /// Scale unit.
@@ -63,7 +311,7 @@ where
 /// "gibibyte".parse::<Unit>().unwrap_err();
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Unit {
     /// Single byte.
     Byte,
@@ -108,7 +356,34 @@ pub enum Unit {
 }
 
 impl Unit {
-    fn factor(&self) -> u64 {
+    /// Returns the unit prefix exponent, e.g., 2 for both megabytes and mebibytes.
+    pub(crate) const fn exponent(&self) -> usize {
+        match self {
+            Self::Byte => 0,
+            Self::KiloByte | Self::KibiByte => 1,
+            Self::MegaByte | Self::MebiByte => 2,
+            Self::GigaByte | Self::GibiByte => 3,
+            Self::TeraByte | Self::TebiByte => 4,
+            Self::PetaByte | Self::PebiByte => 5,
+            Self::ExaByte | Self::ExbiByte => 6,
+        }
+    }
+
+    /// Returns true for IEC (binary) units.
+    pub(crate) const fn is_binary(&self) -> bool {
+        matches!(
+            self,
+            Self::KibiByte
+                | Self::MebiByte
+                | Self::GibiByte
+                | Self::TebiByte
+                | Self::PebiByte
+                | Self::ExbiByte
+        )
+    }
+
+    /// Returns the number of bytes in one unit.
+    pub(crate) const fn factor(&self) -> u64 {
         match self {
             Self::Byte => 1,
             // decimal units
@@ -202,45 +477,128 @@ impl str::FromStr for Unit {
     type Err = UnitParseError;
 
     fn from_str(unit: &str) -> Result<Self, Self::Err> {
-        match () {
-            _ if unit.eq_ignore_ascii_case("b") => Ok(Self::Byte),
-            _ if unit.eq_ignore_ascii_case("k") | unit.eq_ignore_ascii_case("kb") => {
-                Ok(Self::KiloByte)
-            }
-            _ if unit.eq_ignore_ascii_case("m") | unit.eq_ignore_ascii_case("mb") => {
-                Ok(Self::MegaByte)
-            }
-            _ if unit.eq_ignore_ascii_case("g") | unit.eq_ignore_ascii_case("gb") => {
-                Ok(Self::GigaByte)
-            }
-            _ if unit.eq_ignore_ascii_case("t") | unit.eq_ignore_ascii_case("tb") => {
-                Ok(Self::TeraByte)
-            }
-            _ if unit.eq_ignore_ascii_case("p") | unit.eq_ignore_ascii_case("pb") => {
-                Ok(Self::PetaByte)
-            }
-            _ if unit.eq_ignore_ascii_case("e") | unit.eq_ignore_ascii_case("eb") => {
-                Ok(Self::ExaByte)
-            }
-            _ if unit.eq_ignore_ascii_case("ki") | unit.eq_ignore_ascii_case("kib") => {
-                Ok(Self::KibiByte)
-            }
-            _ if unit.eq_ignore_ascii_case("mi") | unit.eq_ignore_ascii_case("mib") => {
-                Ok(Self::MebiByte)
-            }
-            _ if unit.eq_ignore_ascii_case("gi") | unit.eq_ignore_ascii_case("gib") => {
-                Ok(Self::GibiByte)
-            }
-            _ if unit.eq_ignore_ascii_case("ti") | unit.eq_ignore_ascii_case("tib") => {
-                Ok(Self::TebiByte)
-            }
-            _ if unit.eq_ignore_ascii_case("pi") | unit.eq_ignore_ascii_case("pib") => {
-                Ok(Self::PebiByte)
-            }
-            _ if unit.eq_ignore_ascii_case("ei") | unit.eq_ignore_ascii_case("eib") => {
-                Ok(Self::ExbiByte)
+        // `from_ascii` ignores case, so millibytes would otherwise be read as megabytes
+        let parsed = if is_sub_byte_unit(unit.as_bytes()) {
+            None
+        } else {
+            Self::from_ascii(unit.as_bytes())
+        };
+
+        parsed.ok_or_else(|| UnitParseError {
+            unit: to_string_truncate(unit),
+            suggestion: suggest_unit(unit),
+        })
+    }
+}
+
+/// Symbols and singular long names of units, used to suggest corrections for unknown units.
+const UNIT_NAMES: [(&str, &str); 13] = [
+    ("B", "byte"),
+    ("kB", "kilobyte"),
+    ("MB", "megabyte"),
+    ("GB", "gigabyte"),
+    ("TB", "terabyte"),
+    ("PB", "petabyte"),
+    ("EB", "exabyte"),
+    ("KiB", "kibibyte"),
+    ("MiB", "mebibyte"),
+    ("GiB", "gibibyte"),
+    ("TiB", "tebibyte"),
+    ("PiB", "pebibyte"),
+    ("EiB", "exbibyte"),
+];
+
+/// Returns the unit symbol closest to an unknown `unit`, if any is close enough to be a typo.
+///
+/// Long names, like `mebibytes`, map to their symbol. Otherwise, a symbol within an edit distance of
+/// one, ignoring case, is suggested, preferring one of the same length, so `MBi` suggests `MiB`
+/// rather than `MB`. Single characters are too ambiguous to correct, and non-ASCII input is unlikely
+/// to be a typo. Sub-byte units, like `mB`, suggest `MB`.
+fn suggest_unit(unit: &str) -> Option<&'static str> {
+    if is_sub_byte_unit(unit.as_bytes()) {
+        return Some("MB");
+    }
+
+    let lower = unit.to_ascii_lowercase();
+    let singular = lower.strip_suffix('s').unwrap_or(&lower);
+
+    if let Some((symbol, _)) = UNIT_NAMES.iter().find(|(_, name)| *name == singular) {
+        return Some(symbol);
+    }
+
+    if lower.len() < 2 || !lower.is_ascii() {
+        return None;
+    }
+
+    UNIT_NAMES
+        .iter()
+        .map(|(symbol, _)| *symbol)
+        .filter(|symbol| {
+            edit_distance(lower.as_bytes(), symbol.to_ascii_lowercase().as_bytes()) <= 1
+        })
+        .min_by_key(|symbol| symbol.len() != lower.len())
+}
+
+/// Returns the optimal string alignment distance between `a` and `b`.
+///
+/// This is the Levenshtein distance, with swapping two adjacent characters counted as one edit.
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    // rows for the previous two prefixes of `a`, and the current one
+    let mut before_prev = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                curr[j] = curr[j].min(before_prev[j - 2] + 1);
             }
-            _ => Err(UnitParseError(to_string_truncate(unit))),
+        }
+
+        before_prev.clone_from(&prev);
+        prev.clone_from(&curr);
+    }
+
+    prev[b.len()]
+}
+
+impl Unit {
+    /// Parses a unit symbol, ignoring ASCII case.
+    pub(crate) const fn from_ascii(unit: &[u8]) -> Option<Self> {
+        const MAX_UNIT_LEN: usize = 3;
+
+        if unit.len() > MAX_UNIT_LEN {
+            return None;
+        }
+
+        let mut lower = [0; MAX_UNIT_LEN];
+        let mut idx = 0;
+        while idx < unit.len() {
+            lower[idx] = unit[idx].to_ascii_lowercase();
+            idx += 1;
+        }
+
+        match lower.split_at(unit.len()).0 {
+            b"b" => Some(Self::Byte),
+            b"k" | b"kb" => Some(Self::KiloByte),
+            b"m" | b"mb" => Some(Self::MegaByte),
+            b"g" | b"gb" => Some(Self::GigaByte),
+            b"t" | b"tb" => Some(Self::TeraByte),
+            b"p" | b"pb" => Some(Self::PetaByte),
+            b"e" | b"eb" => Some(Self::ExaByte),
+            b"ki" | b"kib" => Some(Self::KibiByte),
+            b"mi" | b"mib" => Some(Self::MebiByte),
+            b"gi" | b"gib" => Some(Self::GibiByte),
+            b"ti" | b"tib" => Some(Self::TebiByte),
+            b"pi" | b"pib" => Some(Self::PebiByte),
+            b"ei" | b"eib" => Some(Self::ExbiByte),
+            _ => None,
         }
     }
 }
@@ -269,16 +627,36 @@ fn to_string_truncate(unit: &str) -> String {
 }
 
 /// Error returned when parsing a [`Unit`] fails.
+///
+/// If the unit looks like a typo of a known unit, the message suggests the correct symbol.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::Unit;
+///
+/// let err = "MiBB".parse::<Unit>().unwrap_err();
+/// assert_eq!(r#"Failed to parse unit "MiB...", did you mean "MiB"?"#, err.to_string());
+/// ```
 #[derive(Debug)]
-pub struct UnitParseError(String);
+pub struct UnitParseError {
+    unit: String,
+    suggestion: Option<&'static str>,
+}
 
 impl fmt::Display for UnitParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Failed to parse unit \"{}\"", self.0)
+        write!(f, "Failed to parse unit \"{}\"", self.unit)?;
+
+        match self.suggestion {
+            Some(suggestion) => write!(f, ", did you mean \"{suggestion}\"?"),
+            None => Ok(()),
+        }
     }
 }
 
 #[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::error::Error for UnitParseError {}
 
 #[cfg(test)]
@@ -298,6 +676,39 @@ mod tests {
         assert_eq!("foo...", to_string_truncate("foobar"));
     }
 
+    #[test]
+    fn unit_suggestions() {
+        for (unit, suggestion) in [
+            ("MiBB", Some("MiB")),
+            ("MBi", Some("MiB")),
+            ("GBs", Some("GB")),
+            ("kibs", Some("KiB")),
+            ("Bi", Some("B")),
+            ("TeB", Some("TiB")),
+            ("bytes", Some("B")),
+            ("Gigabyte", Some("GB")),
+            ("mebibytes", Some("MiB")),
+            ("x", None),
+            ("bit", None),
+            ("parsecs", None),
+            ("mB", Some("MB")),
+            ("µB", Some("MB")),
+        ] {
+            assert_eq!(suggestion, suggest_unit(unit), "unit: {unit}");
+        }
+
+        assert_eq!(0, edit_distance(b"mib", b"mib"));
+        assert_eq!(1, edit_distance(b"mbi", b"mib"));
+        assert_eq!(2, edit_distance(b"kibb", b"kb"));
+        assert_eq!(3, edit_distance(b"", b"kib"));
+
+        let err = "5 gibs".parse::<ByteSize>().unwrap_err();
+        assert!(err.ends_with(r#"did you mean "GiB"?"#), "{err}");
+
+        let err = "5 x".parse::<ByteSize>().unwrap_err();
+        assert!(!err.contains("did you mean"), "{err}");
+    }
+
     #[test]
     fn when_ok() {
         // shortcut for writing test cases
@@ -340,6 +751,142 @@ mod tests {
         assert!(parse("1 000 B").is_err());
     }
 
+    #[test]
+    fn sub_byte_units() {
+        for input in ["5mB", "5 mB", "5 uB", "5 µB", "5 μB"] {
+            let err = input.parse::<ByteSize>().unwrap_err();
+            assert!(err.contains("fraction of a byte"), "{input}: {err}");
+        }
+
+        // other case variations of megabytes are still accepted
+        for input in ["5 MB", "5 mb", "5 Mb", "5 M", "5 m"] {
+            assert_eq!(ByteSize::mb(5), input.parse().unwrap(), "{input}");
+        }
+
+        let lenient = ParseOptions::new().lenient();
+        assert_eq!(ByteSize::mb(5), lenient.parse("5 mB").unwrap());
+        assert_eq!(ByteSize::mb(5), lenient.parse("5 MB").unwrap());
+        assert!(lenient.parse("5 µB").is_err());
+
+        for unit in ["mB", "uB", "µB", "μB"] {
+            assert!(unit.parse::<Unit>().is_err(), "{unit}");
+        }
+        let err = "mB".parse::<Unit>().unwrap_err();
+        assert_eq!(
+            r#"Failed to parse unit "mB", did you mean "MB"?"#,
+            err.to_string()
+        );
+        assert_eq!(Unit::MegaByte, "mb".parse().unwrap());
+        assert_eq!(Unit::MegaByte, "Mb".parse().unwrap());
+    }
+
+    #[test]
+    #[should_panic = "sub-byte unit in byte size"]
+    fn const_parsing_sub_byte_unit() {
+        parse_const("5 mB");
+    }
+
+    #[test]
+    fn decimal_parsing() {
+        fn decimal(integer: u128, fraction: u64, fraction_digits: u32) -> Decimal {
+            Decimal {
+                integer,
+                fraction,
+                fraction_digits,
+            }
+        }
+
+        assert_eq!(Decimal::parse(b"42"), Ok(decimal(42, 0, 0)));
+        assert_eq!(Decimal::parse(b"4.35"), Ok(decimal(4, 35, 2)));
+        assert_eq!(Decimal::parse(b"5."), Ok(decimal(5, 0, 0)));
+        assert_eq!(Decimal::parse(b".5"), Ok(decimal(0, 5, 1)));
+        assert_eq!(
+            Decimal::parse(b"0.12345678901234567890123"),
+            Ok(decimal(0, 1_234_567_890_123_456_789, 19)),
+        );
+        assert_eq!(
+            Decimal::parse(b"99999999999999999999999"),
+            Ok(decimal(99_999_999_999_999_999_999_999, 0, 0)),
+        );
+        assert_eq!(Decimal::parse(&[b'9'; 40]), Ok(decimal(u128::MAX, 0, 0)),);
+
+        assert_eq!(Decimal::parse(b""), Err(DecimalParseError::Empty));
+        assert_eq!(Decimal::parse(b"."), Err(DecimalParseError::Invalid));
+        assert_eq!(Decimal::parse(b"1.2.3"), Err(DecimalParseError::Invalid));
+        assert_eq!(Decimal::parse(b"1a"), Err(DecimalParseError::Invalid));
+    }
+
+    #[test]
+    fn exact_decimal_scaling() {
+        // shortcut for writing test cases
+        fn parse(s: &str) -> u64 {
+            s.parse::<ByteSize>().unwrap().0
+        }
+
+        // these suffer from rounding errors when computed using floating point math
+        assert_eq!(parse("4.35 KB"), 4_350);
+        assert_eq!(parse("0.29 MB"), 290_000);
+        assert_eq!(parse("1.1 EB"), 1_100_000_000_000_000_000);
+
+        // fractional bytes are truncated
+        assert_eq!(parse("1.0001 KiB"), 1_024);
+
+        // out-of-range values saturate
+        assert_eq!(parse("16 EiB"), u64::MAX);
+        assert_eq!(parse("99999999999999999999999 B"), u64::MAX);
+    }
+
+    #[test]
+    fn const_parsing() {
+        const SIZE: ByteSize = parse_const("1.5 KiB");
+        assert_eq!(SIZE, ByteSize::b(1_536));
+
+        assert_eq!(parse_const("0"), ByteSize::b(0));
+        assert_eq!(parse_const("500"), ByteSize::b(500));
+        assert_eq!(parse_const("4.35kB"), ByteSize::b(4_350));
+        assert_eq!(parse_const("3 \t MB"), ByteSize::mb(3));
+        assert_eq!(parse_const("12 PiB"), ByteSize::pib(12));
+        assert_eq!(parse_const("1K"), ByteSize::kb(1));
+        assert_eq!(parse_const("1.5Ki"), ByteSize::b(1_536));
+        assert_eq!(parse_const(".5 KiB"), ByteSize::b(512));
+        assert_eq!(parse_const("12.5MiB"), ByteSize::kib(12_800));
+        assert_eq!(
+            parse_const("15.99 EiB"),
+            ByteSize::b(18_435_214_858_663_483_146)
+        );
+        assert_eq!(parse_const("18446744073709551615"), ByteSize::b(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic = "byte size does not fit in a u64"]
+    fn const_parsing_overflow() {
+        parse_const("99 EiB");
+    }
+
+    #[test]
+    #[should_panic = "byte size does not fit in a u64"]
+    fn const_parsing_overflow_bytes() {
+        parse_const("18446744073709551616");
+    }
+
+    #[test]
+    #[should_panic = "missing unit after decimal number in byte size"]
+    fn const_parsing_trailing_point() {
+        parse_const("5.");
+    }
+
+    #[test]
+    #[should_panic = "unknown unit in byte size"]
+    fn const_parsing_bad_unit() {
+        parse_const("12.5 MiBB");
+    }
+
+    #[test]
+    #[should_panic = "invalid number in byte size"]
+    fn const_parsing_bad_number() {
+        parse_const("1.2.3 MiB");
+    }
+
     #[test]
     fn to_and_from_str() {
         // shortcut for writing test cases
@@ -353,4 +900,35 @@ mod tests {
             128 * Unit::GibiByte,
         );
     }
+
+    #[test]
+    fn size_pattern_matches_parser() {
+        let pattern = regex::Regex::new(SIZE_PATTERN).unwrap();
+
+        for value in [
+            "0", "512", "1.5 GiB", "1.5GiB", "2 gb", "3K", "4 ki", ".5 MiB", "5. kb", "7 b",
+            "1 EiB", "10\tMB", "1 m", "1 mb", "1 Mb", "1 mib",
+        ] {
+            assert!(pattern.is_match(value), "value: {value}");
+            assert!(value.parse::<ByteSize>().is_ok(), "value: {value}");
+        }
+
+        for value in [
+            "",
+            "1.5",
+            "-1 KiB",
+            "1..5 KiB",
+            "KiB",
+            " 1 KiB",
+            "1 KiB ",
+            "1 kibibyte",
+            "1 bit",
+            "1 mB",
+            "1 GiBs",
+            ".",
+        ] {
+            assert!(!pattern.is_match(value), "value: {value}");
+            assert!(value.parse::<ByteSize>().is_err(), "value: {value}");
+        }
+    }
 }
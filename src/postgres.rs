@@ -0,0 +1,76 @@
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::{
+    sql::{from_i64, to_i64, BoxError},
+    ByteSize,
+};
+
+/// Reads an `int8` (`BIGINT`) value, failing for negative values.
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+impl<'a> FromSql<'a> for ByteSize {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        from_i64(i64::from_sql(ty, raw)?)
+    }
+
+    accepts!(INT8);
+}
+
+/// Writes an `int8` (`BIGINT`) value, failing for sizes above [`i64::MAX`] bytes (8 EiB).
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+impl ToSql for ByteSize {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        to_i64(*self)?.to_sql(ty, out)
+    }
+
+    accepts!(INT8);
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = BytesMut::new();
+        ByteSize::mib(3)
+            .to_sql_checked(&Type::INT8, &mut buf)
+            .unwrap();
+
+        assert_eq!(3_145_728_i64.to_be_bytes().as_slice(), &buf[..]);
+        assert_eq!(
+            ByteSize::mib(3),
+            ByteSize::from_sql(&Type::INT8, &buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn checks() {
+        assert!(<ByteSize as ToSql>::accepts(&Type::INT8));
+        assert!(!<ByteSize as ToSql>::accepts(&Type::INT4));
+        assert!(!<ByteSize as FromSql<'_>>::accepts(&Type::NUMERIC));
+
+        let mut buf = BytesMut::new();
+        let err = ByteSize::eib(8)
+            .to_sql_checked(&Type::INT8, &mut buf)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        let err = ByteSize::kib(1)
+            .to_sql_checked(&Type::INT4, &mut buf)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("cannot convert"), "{err}");
+
+        let err = ByteSize::from_sql(&Type::INT8, &(-1_i64).to_be_bytes()).unwrap_err();
+        assert_eq!(
+            "-1 is not a valid size, sizes can't be negative",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::{format, string::String};
+use core::{fmt, str};
+
+use crate::{structured, Display, Format, Unit};
+
+/// A user's preferred way of showing sizes, e.g., as stored in an application's settings.
+///
+/// Apply it to a [`Display`] with [`Display::with_preference()`]. A preference is written, and
+/// parsed, as the units followed by an optional `short` flag:
+///
+/// | String      | Meaning                                          |
+/// |-------------|--------------------------------------------------|
+/// | `auto`      | Keep the display's own style.                    |
+/// | `iec`       | IEC (binary) units, e.g., `1.5 MiB`.             |
+/// | `si`        | SI (decimal) units, e.g., `1.6 MB`.              |
+/// | `MiB`       | Always use the given unit, e.g., `1536.0 KiB`.   |
+/// | `iec short` | Any of the above in a short style, e.g., `1.5M`. |
+///
+/// Keywords are case-insensitive, and units are parsed like [`Unit`]'s `FromStr` impl. With the
+/// `serde` feature, preferences are (de)serialized as these strings.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::{ByteSize, Unit, UnitPreference};
+///
+/// let preference: UnitPreference = "si short".parse().unwrap();
+/// assert_eq!(UnitPreference::si().short(), preference);
+///
+/// let size = ByteSize::mib(3);
+/// assert_eq!("3.1M", size.display().with_preference(preference).to_string());
+///
+/// let preference = UnitPreference::unit(Unit::KibiByte);
+/// assert_eq!("3072.0 KiB", size.display().with_preference(preference).to_string());
+/// assert_eq!("KiB", preference.to_string());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UnitPreference {
+    units: PreferredUnits,
+    short: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+enum PreferredUnits {
+    #[default]
+    Auto,
+    Iec,
+    Si,
+    Unit(Unit),
+}
+
+impl UnitPreference {
+    /// Returns a preference which keeps the display's own style.
+    pub const fn auto() -> Self {
+        Self {
+            units: PreferredUnits::Auto,
+            short: false,
+        }
+    }
+
+    /// Returns a preference for IEC (binary) units.
+    pub const fn iec() -> Self {
+        Self {
+            units: PreferredUnits::Iec,
+            short: false,
+        }
+    }
+
+    /// Returns a preference for SI (decimal) units.
+    pub const fn si() -> Self {
+        Self {
+            units: PreferredUnits::Si,
+            short: false,
+        }
+    }
+
+    /// Returns a preference for always showing sizes in `unit`.
+    pub const fn unit(unit: Unit) -> Self {
+        Self {
+            units: PreferredUnits::Unit(unit),
+            short: false,
+        }
+    }
+
+    /// Prefers a short style, e.g., `1.5M`.
+    #[must_use]
+    pub const fn short(mut self) -> Self {
+        self.short = true;
+        self
+    }
+
+    /// Applies the preference to `display`.
+    pub(crate) fn apply(self, mut display: Display) -> Display {
+        match self.units {
+            PreferredUnits::Auto => {}
+            PreferredUnits::Iec => display.format = Format::Iec,
+            PreferredUnits::Si => display.format = Format::Si,
+            PreferredUnits::Unit(unit) => display.unit = Some(unit),
+        }
+
+        if self.short {
+            display.format = match display.format {
+                Format::Iec => Format::IecShort,
+                Format::Si => Format::SiShort,
+                Format::IecBits => Format::IecBitsShort,
+                Format::SiBits => Format::SiBitsShort,
+                format => format,
+            };
+        }
+
+        display
+    }
+}
+
+impl fmt::Display for UnitPreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = match self.units {
+            PreferredUnits::Auto => "auto",
+            PreferredUnits::Iec => "iec",
+            PreferredUnits::Si => "si",
+            PreferredUnits::Unit(unit) => structured::token_of(unit),
+        };
+
+        if self.short {
+            write!(f, "{units} short")
+        } else {
+            f.write_str(units)
+        }
+    }
+}
+
+impl str::FromStr for UnitPreference {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut words = value.split_ascii_whitespace();
+
+        let preference = match words.next() {
+            Some(word) if word.eq_ignore_ascii_case("auto") => Self::auto(),
+            Some(word) if word.eq_ignore_ascii_case("iec") => Self::iec(),
+            Some(word) if word.eq_ignore_ascii_case("si") => Self::si(),
+            Some(word) => match word.parse() {
+                Ok(unit) => Self::unit(unit),
+                Err(_) => {
+                    return Err(format!(
+                        "couldn't parse {value:?} into a unit preference, unknown units {word:?}"
+                    ))
+                }
+            },
+            None => return Err(format!("couldn't parse {value:?} into a unit preference")),
+        };
+
+        match (words.next(), words.next()) {
+            (None, _) => Ok(preference),
+            (Some(word), None) if word.eq_ignore_ascii_case("short") => Ok(preference.short()),
+            _ => Err(format!(
+                "couldn't parse {value:?} into a unit preference, expected only \"short\" after \
+                 the units"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use super::*;
+    use crate::ByteSize;
+
+    #[test]
+    fn parse_and_display() {
+        for (string, preference) in [
+            ("auto", UnitPreference::auto()),
+            ("iec", UnitPreference::iec()),
+            ("si", UnitPreference::si()),
+            ("MiB", UnitPreference::unit(Unit::MebiByte)),
+            ("kB", UnitPreference::unit(Unit::KiloByte)),
+            ("B short", UnitPreference::unit(Unit::Byte).short()),
+            ("auto short", UnitPreference::auto().short()),
+            ("iec short", UnitPreference::iec().short()),
+        ] {
+            assert_eq!(Ok(preference), string.parse(), "string: {string}");
+            assert_eq!(string, preference.to_string());
+        }
+
+        assert_eq!(Ok(UnitPreference::si().short()), "  SI   Short ".parse());
+        assert_eq!(Ok(UnitPreference::unit(Unit::MegaByte)), "mb".parse());
+        assert_eq!(UnitPreference::auto(), UnitPreference::default());
+
+        for string in ["", "short", "binary", "iec short short", "iec long", "MiBs"] {
+            assert!(
+                string.parse::<UnitPreference>().is_err(),
+                "string: {string}"
+            );
+        }
+    }
+
+    #[test]
+    fn apply() {
+        #[track_caller]
+        fn assert_preference(expected: &str, display: Display, preference: &str) {
+            let preference = preference.parse().unwrap();
+            assert_eq!(expected, display.with_preference(preference).to_string());
+        }
+
+        let size = ByteSize::kib(1536);
+
+        assert_preference("1.5 MiB", size.display(), "auto");
+        assert_preference("1.6 MB", size.display().si(), "auto");
+        assert_preference("1.6 MB", size.display(), "si");
+        assert_preference("1.5 MiB", size.display().si(), "iec");
+        assert_preference("1.5M", size.display(), "auto short");
+        assert_preference("1.6M", size.display(), "si short");
+        assert_preference("12.6M", size.display().si_bits(), "auto short");
+        assert_preference("1536.0 KiB", size.display(), "KiB");
+        assert_preference("1572.9 kB", size.display(), "kB");
+        assert_preference("1572.9k", size.display(), "kB short");
+        assert_preference("1.5M", size.display().coreutils(), "auto short");
+    }
+}
@@ -0,0 +1,126 @@
+//! Strategies for property testing with `proptest`.
+//!
+//! `ByteSize` implements `Arbitrary`, generating any size and shrinking towards zero. The
+//! strategies here narrow that down, e.g., to sizes below a limit, or to [`realistic()`] sizes
+//! spread evenly over orders of magnitude, which exercise every unit instead of mostly huge sizes.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::ByteSize;
+//! use proptest::prelude::*;
+//!
+//! fn chunks(total: ByteSize, chunk: ByteSize) -> u64 {
+//!     total.as_u64().div_ceil(chunk.as_u64())
+//! }
+//!
+//! proptest! {
+//!     fn chunks_cover_total(
+//!         total in bytesize::proptest::realistic(),
+//!         chunk in bytesize::proptest::between(ByteSize::b(1), ByteSize::mib(1)),
+//!     ) {
+//!         prop_assert!(chunks(total, chunk) * chunk.as_u64() >= total.as_u64());
+//!     }
+//! }
+//!
+//! chunks_cover_total();
+//! ```
+
+use ::proptest::{
+    arbitrary::{any, Arbitrary},
+    strategy::{Map, Strategy},
+};
+
+use crate::ByteSize;
+
+/// Largest size generated by [`realistic()`], 1 TiB.
+const REALISTIC_MAX_BITS: u32 = 40;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+impl Arbitrary for ByteSize {
+    type Parameters = ();
+    type Strategy = Map<<u64 as Arbitrary>::Strategy, fn(u64) -> Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any::<u64>().prop_map(ByteSize)
+    }
+}
+
+/// Generates sizes from zero up to and including `max`, shrinking towards zero.
+pub fn up_to(max: ByteSize) -> impl Strategy<Value = ByteSize> {
+    between(ByteSize(0), max)
+}
+
+/// Generates sizes from `min` up to and including `max`, shrinking towards `min`.
+///
+/// # Panics
+///
+/// Panics if `min` is greater than `max`.
+pub fn between(min: ByteSize, max: ByteSize) -> impl Strategy<Value = ByteSize> {
+    assert!(min <= max, "min ({min}) is greater than max ({max})");
+    (min.0..=max.0).prop_map(ByteSize)
+}
+
+/// Generates sizes from zero up to 1 TiB, spread evenly over orders of magnitude.
+///
+/// Sizes are about as likely to be a few bytes as a few kilobytes or gigabytes, like the files and
+/// payloads that code handles in practice. Shrinks towards smaller orders of magnitude first.
+pub fn realistic() -> impl Strategy<Value = ByteSize> {
+    (0..=REALISTIC_MAX_BITS).prop_flat_map(|bits| (0..=(1_u64 << bits) - 1).prop_map(ByteSize))
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::{prop_assert, strategy::ValueTree as _, test_runner::TestRunner};
+
+    use super::*;
+
+    ::proptest::proptest! {
+        #[test]
+        fn up_to_max(size in up_to(ByteSize::kib(4))) {
+            prop_assert!(size <= ByteSize::kib(4));
+        }
+
+        #[test]
+        fn between_bounds(size in between(ByteSize::mib(1), ByteSize::mib(2))) {
+            prop_assert!((ByteSize::mib(1)..=ByteSize::mib(2)).contains(&size));
+        }
+
+        #[test]
+        fn round_trip(size: ByteSize) {
+            prop_assert!(size.to_string_exact().parse::<ByteSize>() == Ok(size));
+        }
+    }
+
+    #[test]
+    fn realistic_spread() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = realistic();
+
+        let sizes = (0..4100)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+            .inspect(|&size| assert!(size < ByteSize::tib(1)))
+            .collect::<alloc::vec::Vec<_>>();
+
+        // 10 or 11 of the 41 orders of magnitude fall in each unit below TiB
+        let units = [
+            (0, 1 << 10),
+            (1 << 10, 1 << 20),
+            (1 << 20, 1 << 30),
+            (1 << 30, 1 << 40),
+        ];
+        for (low, high) in units {
+            let count = sizes
+                .iter()
+                .filter(|size| (low..high).contains(&size.0))
+                .count();
+            assert!((850..1250).contains(&count), "{low}: {count}");
+        }
+    }
+
+    #[test]
+    #[should_panic = "min (2 B) is greater than max (1 B)"]
+    fn between_empty() {
+        let _ = between(ByteSize(2), ByteSize(1));
+    }
+}
@@ -0,0 +1,109 @@
+use core::convert::Infallible;
+
+use pyo3::{
+    exceptions::PyValueError,
+    types::{PyInt, PyString, PyStringMethods as _},
+    Borrowed, Bound, FromPyObject, IntoPyObject, PyAny, PyErr, Python,
+};
+
+use crate::ByteSize;
+
+/// Converts to a Python `int` number of bytes.
+#[cfg_attr(docsrs, doc(cfg(feature = "pyo3")))]
+impl<'py> IntoPyObject<'py> for ByteSize {
+    type Target = PyInt;
+    type Output = Bound<'py, PyInt>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.0.into_pyobject(py)
+    }
+}
+
+/// Converts to a Python `int` number of bytes.
+#[cfg_attr(docsrs, doc(cfg(feature = "pyo3")))]
+impl<'py> IntoPyObject<'py> for &ByteSize {
+    type Target = PyInt;
+    type Output = Bound<'py, PyInt>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.0.into_pyobject(py)
+    }
+}
+
+/// Extracts a size from a Python `int` number of bytes, or a `str` like `"1.5GiB"`.
+///
+/// Strings that don't parse raise `ValueError`. Other objects are extracted like `u64`, so negative
+/// ints raise `OverflowError` and other types raise `TypeError`.
+#[cfg_attr(docsrs, doc(cfg(feature = "pyo3")))]
+impl FromPyObject<'_, '_> for ByteSize {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> Result<Self, Self::Error> {
+        match obj.cast::<PyString>() {
+            Ok(string) => string.to_str()?.parse().map_err(PyValueError::new_err),
+            Err(_) => obj.extract().map(ByteSize),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use pyo3::{
+        exceptions::{PyOverflowError, PyTypeError},
+        types::{PyAnyMethods as _, PyDict, PyDictMethods as _},
+    };
+
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        Python::initialize();
+        Python::attach(|py| {
+            let int = ByteSize::kib(3).into_pyobject(py).unwrap();
+            assert_eq!(3072, int.extract::<u64>().unwrap());
+            assert_eq!(ByteSize::kib(3), int.extract::<ByteSize>().unwrap());
+
+            let locals = PyDict::new(py);
+            locals.set_item("size", ByteSize::mib(1)).unwrap();
+            let doubled = py
+                .eval(c"size * 2", None, Some(&locals))
+                .unwrap()
+                .extract::<ByteSize>()
+                .unwrap();
+            assert_eq!(ByteSize::mib(2), doubled);
+
+            let string = PyString::new(py, "1.5GiB");
+            assert_eq!(ByteSize::mib(1536), string.extract::<ByteSize>().unwrap());
+        });
+    }
+
+    #[test]
+    fn errors() {
+        Python::initialize();
+        Python::attach(|py| {
+            let err = PyString::new(py, "1.5 parsecs")
+                .extract::<ByteSize>()
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert!(err.to_string().contains("parsecs"), "{err}");
+
+            let err = (-1_i64)
+                .into_pyobject(py)
+                .unwrap()
+                .extract::<ByteSize>()
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+
+            let err = 1.5_f64
+                .into_pyobject(py)
+                .unwrap()
+                .extract::<ByteSize>()
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+        });
+    }
+}
@@ -0,0 +1,33 @@
+use alloc::boxed::Box;
+
+use quickcheck::{Arbitrary, Gen};
+
+use crate::ByteSize;
+
+/// Generates any size, like `u64`, and shrinks towards zero.
+#[cfg_attr(docsrs, doc(cfg(feature = "quickcheck")))]
+impl Arbitrary for ByteSize {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self(u64::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink().map(Self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn shrink() {
+        let shrunk = ByteSize::kib(1).shrink().collect::<Vec<_>>();
+        assert_eq!(Some(&ByteSize(0)), shrunk.first());
+        assert!(shrunk.iter().all(|&size| size < ByteSize::kib(1)));
+
+        assert_eq!(0, ByteSize(0).shrink().count());
+    }
+}
@@ -0,0 +1,260 @@
+//! Random sampling of sizes with the `rand` crate.
+//!
+//! `ByteSize` implements `SampleUniform`, so ranges of sizes can be sampled like ranges of
+//! integers, e.g., with `Rng::random_range()` or [`ByteSize::random_range()`].
+//!
+//! Real-world sizes, like file or request body sizes, are spread over many orders of magnitude.
+//! Sampling them uniformly mostly yields sizes close to the upper bound, so [`LogUniform`] and
+//! [`ByteSize::random_log_range()`] sample log-uniformly instead: each order of magnitude in the
+//! range is equally likely.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::{rand::LogUniform, ByteSize};
+//! use rand::{distr::Distribution as _, rngs::SmallRng, Rng as _, SeedableRng as _};
+//!
+//! let mut rng = SmallRng::seed_from_u64(42);
+//!
+//! let size = rng.random_range(ByteSize::kib(1)..ByteSize::mib(1));
+//! assert!((ByteSize::kib(1)..ByteSize::mib(1)).contains(&size));
+//!
+//! let size = ByteSize::random_log_range(&mut rng, ..=ByteSize::gib(1));
+//! assert!(size <= ByteSize::gib(1));
+//!
+//! let payloads = LogUniform::new_inclusive(ByteSize::b(1), ByteSize::mib(16)).unwrap();
+//! let sizes = payloads.sample_iter(rng).take(100);
+//! assert!(sizes.into_iter().all(|size| size <= ByteSize::mib(16)));
+//! ```
+
+use core::ops::{Bound, RangeBounds};
+
+use ::rand::{
+    distr::{
+        uniform::{Error, SampleBorrow, SampleUniform, UniformInt, UniformSampler},
+        Distribution,
+    },
+    Rng,
+};
+
+use crate::ByteSize;
+
+/// Uniform sampler for `ByteSize`, used by its `SampleUniform` impl.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniformByteSize(UniformInt<u64>);
+
+impl UniformSampler for UniformByteSize {
+    type X = ByteSize;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        UniformInt::new(low.borrow().0, high.borrow().0).map(Self)
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        UniformInt::new_inclusive(low.borrow().0, high.borrow().0).map(Self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        ByteSize(self.0.sample(rng))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+impl SampleUniform for ByteSize {
+    type Sampler = UniformByteSize;
+}
+
+/// Log-uniform distribution of sizes between two bounds.
+///
+/// Each order of magnitude in the range is about equally likely, e.g., sampling between 1 B and
+/// 1 MiB yields about as many sizes under 1 KiB as over it. Zero is allowed as a lower bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogUniform {
+    low: u64,
+    high: u64,
+    log_low: f64,
+    log_span: f64,
+}
+
+impl LogUniform {
+    /// Constructs a distribution over `low..high`.
+    ///
+    /// Fails if the range is empty.
+    pub fn new(low: ByteSize, high: ByteSize) -> Result<Self, Error> {
+        match high.0.checked_sub(1) {
+            Some(high) if low.0 <= high => Self::new_inclusive(low, ByteSize(high)),
+            _ => Err(Error::EmptyRange),
+        }
+    }
+
+    /// Constructs a distribution over `low..=high`.
+    ///
+    /// Fails if the range is empty.
+    pub fn new_inclusive(low: ByteSize, high: ByteSize) -> Result<Self, Error> {
+        if low > high {
+            return Err(Error::EmptyRange);
+        }
+
+        // shifted by one, so a lower bound of zero works, and widened by one so `high` is as
+        // likely as its neighbors after rounding down
+        let log_low = (low.0 as f64).ln_1p();
+        let log_high = (high.0 as f64 + 1.0).ln_1p();
+
+        Ok(Self {
+            low: low.0,
+            high: high.0,
+            log_low,
+            log_span: log_high - log_low,
+        })
+    }
+}
+
+impl Distribution<ByteSize> for LogUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ByteSize {
+        let log = self.log_low + rng.random::<f64>() * self.log_span;
+
+        // float rounding can land just outside the bounds
+        ByteSize((log.exp_m1().floor() as u64).clamp(self.low, self.high))
+    }
+}
+
+impl ByteSize {
+    /// Returns a size sampled uniformly from `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn random_range<R: Rng + ?Sized>(rng: &mut R, range: impl RangeBounds<ByteSize>) -> Self {
+        let (low, high) = inclusive_bounds(range);
+        UniformByteSize::sample_single_inclusive(low, high, rng).expect("range is empty")
+    }
+
+    /// Returns a size sampled log-uniformly from `range`. See [`LogUniform`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn random_log_range<R: Rng + ?Sized>(
+        rng: &mut R,
+        range: impl RangeBounds<ByteSize>,
+    ) -> Self {
+        let (low, high) = inclusive_bounds(range);
+        LogUniform::new_inclusive(low, high)
+            .expect("range is empty")
+            .sample(rng)
+    }
+}
+
+/// Converts `range` to inclusive bounds, which are reversed if it's empty.
+fn inclusive_bounds(range: impl RangeBounds<ByteSize>) -> (ByteSize, ByteSize) {
+    let low = match range.start_bound() {
+        Bound::Included(&low) => low,
+        Bound::Excluded(&low) => match low.0.checked_add(1) {
+            Some(low) => ByteSize(low),
+            None => return (ByteSize(u64::MAX), ByteSize(0)),
+        },
+        Bound::Unbounded => ByteSize(0),
+    };
+
+    let high = match range.end_bound() {
+        Bound::Included(&high) => high,
+        Bound::Excluded(&high) => match high.0.checked_sub(1) {
+            Some(high) => ByteSize(high),
+            None => return (ByteSize(u64::MAX), ByteSize(0)),
+        },
+        Bound::Unbounded => ByteSize(u64::MAX),
+    };
+
+    (low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rand::{rngs::SmallRng, SeedableRng as _};
+
+    use super::*;
+
+    fn rng() -> SmallRng {
+        SmallRng::seed_from_u64(0x5eed)
+    }
+
+    #[test]
+    fn uniform() {
+        let mut rng = rng();
+
+        for _ in 0..1000 {
+            let size = rng.random_range(ByteSize::kib(1)..ByteSize::kib(2));
+            assert!((ByteSize::kib(1)..ByteSize::kib(2)).contains(&size));
+
+            let size = ByteSize::random_range(&mut rng, ..=ByteSize::b(10));
+            assert!(size <= ByteSize::b(10));
+        }
+
+        assert_eq!(
+            ByteSize(7),
+            ByteSize::random_range(&mut rng, ByteSize(7)..=ByteSize(7))
+        );
+        assert!(UniformByteSize::new(ByteSize(1), ByteSize(1)).is_err());
+    }
+
+    #[test]
+    fn log_uniform() {
+        let mut rng = rng();
+        let dist = LogUniform::new_inclusive(ByteSize::b(1), ByteSize::mib(1)).unwrap();
+
+        let sizes = (0..10_000).map(|_| dist.sample(&mut rng));
+        let small = sizes
+            .inspect(|size| assert!((ByteSize::b(1)..=ByteSize::mib(1)).contains(size)))
+            .filter(|&size| size < ByteSize::kib(1))
+            .count();
+
+        // half the orders of magnitude are below 1 KiB
+        assert!((4500..5500).contains(&small), "{small}");
+    }
+
+    #[test]
+    fn log_uniform_bounds() {
+        let mut rng = rng();
+
+        let dist = LogUniform::new(ByteSize(0), ByteSize(2)).unwrap();
+        let zeros = (0..1000)
+            .map(|_| dist.sample(&mut rng))
+            .inspect(|size| assert!(size.0 < 2))
+            .filter(|size| size.0 == 0)
+            .count();
+        // ln(2) / ln(3) of the log-scaled range is below 1
+        assert!((550..700).contains(&zeros), "{zeros}");
+
+        let dist = LogUniform::new_inclusive(ByteSize(0), ByteSize(u64::MAX)).unwrap();
+        for _ in 0..1000 {
+            dist.sample(&mut rng);
+        }
+
+        assert_eq!(
+            ByteSize(u64::MAX),
+            ByteSize::random_log_range(&mut rng, ByteSize(u64::MAX)..)
+        );
+        assert_eq!(
+            Err(Error::EmptyRange),
+            LogUniform::new(ByteSize(1), ByteSize(1))
+        );
+        assert_eq!(
+            Err(Error::EmptyRange),
+            LogUniform::new_inclusive(ByteSize(2), ByteSize(1))
+        );
+        assert_eq!(
+            (ByteSize(u64::MAX), ByteSize(0)),
+            inclusive_bounds(..ByteSize(0))
+        );
+    }
+}
@@ -0,0 +1,790 @@
+use alloc::{format, string::String};
+use core::{fmt, iter, ops, str, time::Duration};
+
+use crate::{
+    display,
+    parse::{is_sub_byte_unit, Decimal},
+    ByteSize, Unit, EB, EIB, GB, GIB, KB, KIB, MB, MIB, PB, PIB, TB, TIB,
+};
+
+/// Number of nanoseconds in a second.
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Transfer rate, in bytes per second.
+///
+/// Rates are whole numbers of bytes per second, so they can be compared, hashed, and added
+/// exactly, like [`ByteSize`]. They're displayed like a `ByteSize` followed by `/s`, honoring
+/// the [default format](crate::set_default_format()), precision, and width.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bytesize::{ByteRate, ByteSize};
+///
+/// # bytesize::set_default_format(bytesize::Format::Iec);
+/// let rate = ByteRate::from_bytes_per(ByteSize::mib(123), Duration::from_secs(10));
+/// assert_eq!("12.3 MiB/s", rate.to_string());
+///
+/// assert!(ByteRate::mbps(100) < ByteRate::mb(100));
+/// assert_eq!(ByteRate::mb(25), ByteRate::mbps(100) + ByteRate::mbps(100));
+///
+/// assert_eq!(ByteRate::mbps(100), "100Mbps".parse().unwrap());
+/// assert_eq!(ByteRate::kib(512), "512 KiB/sec".parse().unwrap());
+///
+/// assert_eq!(ByteSize::mib(30), ByteRate::mib(3) * Duration::from_secs(10));
+/// assert_eq!(Duration::from_secs(10), ByteSize::mib(30) / ByteRate::mib(3));
+/// ```
+#[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub struct ByteRate(pub u64);
+
+macro_rules! byte_constructors {
+    ($($name:ident = $factor:ident, $unit:literal;)*) => {
+        $(
+            #[doc = concat!("Constructs a rate from a quantity of ", $unit, " per second.")]
+            #[inline(always)]
+            pub const fn $name(rate: u64) -> ByteRate {
+                ByteRate(rate * $factor)
+            }
+        )*
+    };
+}
+
+macro_rules! bit_constructors {
+    ($($name:ident = $factor:literal, $unit:literal;)*) => {
+        $(
+            #[doc = concat!("Constructs a rate from a quantity of ", $unit, " per second.")]
+            ///
+            /// The rate is truncated to whole bytes per second.
+            #[inline(always)]
+            pub const fn $name(rate: u64) -> ByteRate {
+                ByteRate(rate * ($factor / 8))
+            }
+        )*
+    };
+}
+
+impl ByteRate {
+    /// Constructs a rate from a quantity of bytes per second.
+    #[inline(always)]
+    pub const fn b(rate: u64) -> ByteRate {
+        ByteRate(rate)
+    }
+
+    byte_constructors! {
+        kb = KB, "kilobytes";
+        kib = KIB, "kibibytes";
+        mb = MB, "megabytes";
+        mib = MIB, "mebibytes";
+        gb = GB, "gigabytes";
+        gib = GIB, "gibibytes";
+        tb = TB, "terabytes";
+        tib = TIB, "tebibytes";
+        pb = PB, "petabytes";
+        pib = PIB, "pebibytes";
+        eb = EB, "exabytes";
+        eib = EIB, "exbibytes";
+    }
+
+    /// Constructs a rate from a quantity of bits per second.
+    ///
+    /// The rate is truncated to whole bytes per second.
+    #[inline(always)]
+    pub const fn bps(rate: u64) -> ByteRate {
+        ByteRate(rate / 8)
+    }
+
+    bit_constructors! {
+        kbps = 1_000, "kilobits";
+        mbps = 1_000_000, "megabits";
+        gbps = 1_000_000_000, "gigabits";
+        tbps = 1_000_000_000_000, "terabits";
+    }
+
+    /// Constructs the rate of transferring `size` in `duration`.
+    ///
+    /// The rate is truncated to whole bytes per second and saturates at `u64::MAX`. Transferring
+    /// any data in no time at all is also treated as the maximum rate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytesize::{ByteRate, ByteSize};
+    ///
+    /// assert_eq!(
+    ///     ByteRate::kib(4),
+    ///     ByteRate::from_bytes_per(ByteSize::kib(1), Duration::from_millis(250)),
+    /// );
+    /// ```
+    pub fn from_bytes_per(size: ByteSize, duration: Duration) -> ByteRate {
+        let nanos = duration.as_nanos();
+
+        if nanos == 0 {
+            return ByteRate(if size.0 == 0 { 0 } else { u64::MAX });
+        }
+
+        let rate = u128::from(size.0) * NANOS_PER_SEC / nanos;
+        ByteRate(u64::try_from(rate).unwrap_or(u64::MAX))
+    }
+
+    /// Returns the number of bytes per second.
+    #[inline(always)]
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the number of bits per second, saturating at `u64::MAX`.
+    #[inline(always)]
+    pub const fn as_bps(&self) -> u64 {
+        self.0.saturating_mul(8)
+    }
+
+    /// Returns the size transferred each second at this rate.
+    #[inline(always)]
+    pub const fn per_second(&self) -> ByteSize {
+        ByteSize(self.0)
+    }
+
+    /// Adds two rates, returning `None` if it overflows.
+    #[must_use]
+    pub const fn checked_add(self, rhs: ByteRate) -> Option<ByteRate> {
+        match self.0.checked_add(rhs.0) {
+            Some(rate) => Some(ByteRate(rate)),
+            None => None,
+        }
+    }
+
+    /// Subtracts a rate, returning `None` if it underflows.
+    #[must_use]
+    pub const fn checked_sub(self, rhs: ByteRate) -> Option<ByteRate> {
+        match self.0.checked_sub(rhs.0) {
+            Some(rate) => Some(ByteRate(rate)),
+            None => None,
+        }
+    }
+
+    /// Adds two rates, saturating at `u64::MAX` bytes per second.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: ByteRate) -> ByteRate {
+        ByteRate(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtracts a rate, saturating at zero.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: ByteRate) -> ByteRate {
+        ByteRate(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiplies a rate by a scalar, returning `None` if it overflows.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: u64) -> Option<ByteRate> {
+        match self.0.checked_mul(rhs) {
+            Some(rate) => Some(ByteRate(rate)),
+            None => None,
+        }
+    }
+
+    /// Divides a rate by a scalar, returning `None` if `rhs` is zero.
+    #[must_use]
+    pub const fn checked_div(self, rhs: u64) -> Option<ByteRate> {
+        match self.0.checked_div(rhs) {
+            Some(rate) => Some(ByteRate(rate)),
+            None => None,
+        }
+    }
+
+    /// Returns the size transferred in `duration` at this rate, returning `None` if it overflows.
+    ///
+    /// The size is truncated to whole bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytesize::{ByteRate, ByteSize};
+    ///
+    /// let rate = ByteRate::mib(4);
+    /// assert_eq!(
+    ///     Some(ByteSize::mib(1)),
+    ///     rate.checked_mul_duration(Duration::from_millis(250)),
+    /// );
+    /// assert_eq!(None, rate.checked_mul_duration(Duration::MAX));
+    /// ```
+    #[must_use]
+    pub fn checked_mul_duration(self, duration: Duration) -> Option<ByteSize> {
+        // can't overflow, since `(2^64 - 1)^2 + 2^64 - 1 < 2^128`
+        let bytes = u128::from(self.0) * u128::from(duration.as_secs())
+            + u128::from(self.0) * u128::from(duration.subsec_nanos()) / NANOS_PER_SEC;
+
+        u64::try_from(bytes).ok().map(ByteSize)
+    }
+}
+
+impl ByteSize {
+    /// Returns the rate of transferring this size in `duration`, returning `None` if `duration` is
+    /// zero or the rate overflows.
+    ///
+    /// The rate is truncated to whole bytes per second. See [`ByteRate::from_bytes_per()`] for a
+    /// saturating version.
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    #[must_use]
+    pub fn checked_div_duration(self, duration: Duration) -> Option<ByteRate> {
+        let nanos = duration.as_nanos();
+
+        if nanos == 0 {
+            return None;
+        }
+
+        let rate = u128::from(self.0) * NANOS_PER_SEC / nanos;
+        u64::try_from(rate).ok().map(ByteRate)
+    }
+
+    /// Returns the time taken to transfer this size at `rate`, returning `None` if `rate` is zero.
+    ///
+    /// The duration is truncated to whole nanoseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bytesize::{ByteRate, ByteSize};
+    ///
+    /// assert_eq!(
+    ///     Some(Duration::from_millis(2500)),
+    ///     ByteSize::mib(10).checked_div_rate(ByteRate::mib(4)),
+    /// );
+    /// assert_eq!(None, ByteSize::mib(10).checked_div_rate(ByteRate(0)));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    #[must_use]
+    pub const fn checked_div_rate(self, rate: ByteRate) -> Option<Duration> {
+        if rate.0 == 0 {
+            return None;
+        }
+
+        let secs = self.0 / rate.0;
+        // less than a billion, since the remainder is less than the rate
+        let nanos = (self.0 % rate.0) as u128 * NANOS_PER_SEC / rate.0 as u128;
+
+        Some(Duration::new(secs, nanos as u32))
+    }
+}
+
+impl From<u64> for ByteRate {
+    fn from(rate: u64) -> Self {
+        ByteRate(rate)
+    }
+}
+
+impl From<ByteRate> for u64 {
+    fn from(rate: ByteRate) -> Self {
+        rate.0
+    }
+}
+
+/// Parses a rate, e.g., `10 MB/s`, `1 GiB/s`, `512 KiB/sec`, or `100Mbps`.
+///
+/// The unit is followed by `/s`, `/sec`, `/second`, or `ps`. Like sizes, units ignore case, except
+/// that a lowercase `b` is bits and an uppercase `B` is bytes, so `Mbps` is megabits per second and
+/// `MB/s` is megabytes per second. Bits can also be written as `bit`, e.g., `1.5 Mibit/s`. A plain
+/// integer is a number of bytes per second. Rates are truncated to whole bytes per second.
+impl str::FromStr for ByteRate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(rate) = value.parse::<u64>() {
+            return Ok(ByteRate(rate));
+        }
+
+        // number and unit are split on an ASCII boundary so slicing `value` is always valid
+        let number_len = value
+            .bytes()
+            .position(|b| !(b.is_ascii_digit() || b == b'.'))
+            .unwrap_or(value.len());
+        let (number, suffix) = value.split_at(number_len);
+
+        let number = Decimal::parse(number.as_bytes())
+            .map_err(|error| format!("couldn't parse {value:?} into a ByteRate, {error}"))?;
+
+        let suffix = suffix.trim();
+        let Some(unit) = ["/second", "/sec", "/s", "ps"]
+            .iter()
+            .find_map(|per_second| strip_suffix_ignore_case(suffix, per_second))
+        else {
+            return Err(format!(
+                "{suffix:?} is not a rate, use a unit per second like \"MB/s\" or \"Mbps\""
+            ));
+        };
+        let unit = unit.trim_end();
+
+        if is_sub_byte_unit(unit.as_bytes()) {
+            return Err(format!(
+                "{unit:?} is a fraction of a byte, which is not supported since bytes are \
+                integral; for megabytes, use \"MB\", or \"Mb\" for megabits"
+            ));
+        }
+
+        let (unit, bits) = match strip_suffix_ignore_case(unit, "bits")
+            .or_else(|| strip_suffix_ignore_case(unit, "bit"))
+        {
+            Some(prefix) => (prefix, true),
+            None => (unit, unit.ends_with('b')),
+        };
+
+        let factor = match unit {
+            "" => 1,
+            unit => match Unit::from_ascii(unit.as_bytes()) {
+                Some(unit) => unit.factor(),
+                None => return Err(format!("couldn't parse {unit:?} into a known unit")),
+            },
+        };
+
+        // bits are converted before saturating, so huge bit rates aren't cut to an eighth
+        let bytes = number.scale_wide(factor);
+        let bytes = if bits { bytes / 8 } else { bytes };
+        Ok(ByteRate(u64::try_from(bytes).unwrap_or(u64::MAX)))
+    }
+}
+
+/// Returns `value` without `suffix`, ignoring ASCII case, if it ends with it.
+fn strip_suffix_ignore_case<'a>(value: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = value.len().checked_sub(suffix.len())?;
+
+    match (value.get(..split), value.get(split..)) {
+        (Some(rest), Some(end)) if end.eq_ignore_ascii_case(suffix) => Some(rest),
+        _ => None,
+    }
+}
+
+impl fmt::Display for ByteRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut display = self.per_second().display();
+        display.format = display::default_format();
+
+        if f.width().is_none() {
+            display.write_with_precision(f, f.precision())?;
+            f.write_str("/s")
+        } else {
+            let mut buf = String::new();
+            display.write_with_precision(&mut buf, f.precision())?;
+            buf.push_str("/s");
+            display::pad(f, &buf)
+        }
+    }
+}
+
+impl fmt::Debug for ByteRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} bytes/s)", self, self.0)
+    }
+}
+
+impl ops::Add<ByteRate> for ByteRate {
+    type Output = ByteRate;
+
+    #[inline(always)]
+    fn add(self, rhs: ByteRate) -> ByteRate {
+        ByteRate(self.0 + rhs.0)
+    }
+}
+
+impl ops::AddAssign<ByteRate> for ByteRate {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: ByteRate) {
+        self.0 += rhs.0
+    }
+}
+
+impl ops::Sub<ByteRate> for ByteRate {
+    type Output = ByteRate;
+
+    #[inline(always)]
+    fn sub(self, rhs: ByteRate) -> ByteRate {
+        ByteRate(self.0 - rhs.0)
+    }
+}
+
+impl ops::SubAssign<ByteRate> for ByteRate {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: ByteRate) {
+        self.0 -= rhs.0
+    }
+}
+
+impl iter::Sum<ByteRate> for ByteRate {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = ByteRate>,
+    {
+        iter.fold(Self::default(), ops::Add::add)
+    }
+}
+
+impl<'a> iter::Sum<&'a ByteRate> for ByteRate {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a ByteRate>,
+    {
+        iter.copied().sum()
+    }
+}
+
+// implemented per type rather than for `T: Into<u64>`, which would rule out `Duration` operands
+macro_rules! scalar_ops {
+    ($($t:ty),*) => {
+        $(
+            impl ops::Mul<$t> for ByteRate {
+                type Output = ByteRate;
+
+                #[inline(always)]
+                fn mul(self, rhs: $t) -> ByteRate {
+                    ByteRate(self.0 * u64::from(rhs))
+                }
+            }
+
+            impl ops::Mul<ByteRate> for $t {
+                type Output = ByteRate;
+
+                #[inline(always)]
+                fn mul(self, rhs: ByteRate) -> ByteRate {
+                    ByteRate(u64::from(self) * rhs.0)
+                }
+            }
+
+            impl ops::MulAssign<$t> for ByteRate {
+                #[inline(always)]
+                fn mul_assign(&mut self, rhs: $t) {
+                    self.0 *= u64::from(rhs);
+                }
+            }
+
+            impl ops::Div<$t> for ByteRate {
+                type Output = ByteRate;
+
+                #[inline(always)]
+                fn div(self, rhs: $t) -> ByteRate {
+                    ByteRate(self.0 / u64::from(rhs))
+                }
+            }
+
+            impl ops::DivAssign<$t> for ByteRate {
+                #[inline(always)]
+                fn div_assign(&mut self, rhs: $t) {
+                    self.0 /= u64::from(rhs);
+                }
+            }
+        )*
+    };
+}
+
+scalar_ops!(u8, u16, u32, u64);
+
+/// Returns the size transferred in a duration at this rate, truncated to whole bytes.
+///
+/// # Panics
+///
+/// Panics if the size overflows. See [`ByteRate::checked_mul_duration()`] for a checked version.
+impl ops::Mul<Duration> for ByteRate {
+    type Output = ByteSize;
+
+    fn mul(self, rhs: Duration) -> ByteSize {
+        self.checked_mul_duration(rhs)
+            .expect("overflow when multiplying rate by duration")
+    }
+}
+
+/// Returns the size transferred in this duration at a rate, truncated to whole bytes.
+///
+/// # Panics
+///
+/// Panics if the size overflows. See [`ByteRate::checked_mul_duration()`] for a checked version.
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+impl ops::Mul<ByteRate> for Duration {
+    type Output = ByteSize;
+
+    fn mul(self, rhs: ByteRate) -> ByteSize {
+        rhs * self
+    }
+}
+
+/// Returns the rate of transferring this size in a duration, truncated to whole bytes per second.
+///
+/// # Panics
+///
+/// Panics if the duration is zero or the rate overflows. See [`ByteSize::checked_div_duration()`]
+/// for a checked version, or [`ByteRate::from_bytes_per()`] for a saturating version.
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+impl ops::Div<Duration> for ByteSize {
+    type Output = ByteRate;
+
+    fn div(self, rhs: Duration) -> ByteRate {
+        assert!(!rhs.is_zero(), "attempt to divide size by zero duration");
+        self.checked_div_duration(rhs)
+            .expect("overflow when dividing size by duration")
+    }
+}
+
+/// Returns the time taken to transfer this size at a rate, truncated to whole nanoseconds.
+///
+/// # Panics
+///
+/// Panics if the rate is zero. See [`ByteSize::checked_div_rate()`] for a checked version.
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+impl ops::Div<ByteRate> for ByteSize {
+    type Output = Duration;
+
+    fn div(self, rhs: ByteRate) -> Duration {
+        self.checked_div_rate(rhs)
+            .expect("attempt to divide size by zero rate")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString as _};
+
+    use super::*;
+
+    #[test]
+    fn constructors() {
+        assert_eq!(ByteRate(1536), ByteRate::b(1536));
+        assert_eq!(ByteRate(1_000), ByteRate::kb(1));
+        assert_eq!(ByteRate(1_048_576), ByteRate::mib(1));
+        assert_eq!(ByteRate(EIB), ByteRate::eib(1));
+
+        assert_eq!(ByteRate(1), ByteRate::bps(15));
+        assert_eq!(ByteRate(125), ByteRate::kbps(1));
+        assert_eq!(ByteRate::mb(125), ByteRate::gbps(1));
+        assert_eq!(ByteRate::tb(125), ByteRate::tbps(1000));
+        assert_eq!(8_000_000, ByteRate::mbps(8).as_bps());
+        assert_eq!(u64::MAX, ByteRate(u64::MAX).as_bps());
+    }
+
+    #[test]
+    fn from_bytes_per() {
+        let rate = |size, millis| ByteRate::from_bytes_per(size, Duration::from_millis(millis));
+
+        assert_eq!(ByteRate::mib(2), rate(ByteSize::mib(1), 500));
+        assert_eq!(ByteRate(333), rate(ByteSize(1000), 3000));
+        assert_eq!(ByteRate(0), rate(ByteSize(0), 1000));
+        assert_eq!(ByteRate(0), rate(ByteSize(0), 0));
+        assert_eq!(ByteRate(u64::MAX), rate(ByteSize(1), 0));
+        assert_eq!(ByteRate(u64::MAX), rate(ByteSize(u64::MAX), 1));
+        assert_eq!(
+            ByteRate(1),
+            ByteRate::from_bytes_per(ByteSize::kib(1), Duration::from_secs(1024))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0 B/s", ByteRate(0).to_string());
+        assert_eq!("12.3 MiB/s", ByteRate(12_897_484).to_string());
+        assert_eq!("12.30 MiB/s", format!("{:.2}", ByteRate(12_897_484)));
+        assert_eq!("  1.0 KiB/s", format!("{:>11}", ByteRate::kib(1)));
+        assert_eq!("1.0 KiB/s |", format!("{:10}|", ByteRate::kib(1)));
+        assert_eq!(
+            "1.0 KiB/s (1024 bytes/s)",
+            format!("{:?}", ByteRate::kib(1))
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        let parse = |value: &str| value.parse::<ByteRate>().unwrap();
+
+        assert_eq!(ByteRate::mb(10), parse("10 MB/s"));
+        assert_eq!(ByteRate::gib(1), parse("1 GiB/s"));
+        assert_eq!(ByteRate::mbps(100), parse("100Mbps"));
+        assert_eq!(ByteRate::kib(512), parse("512 KiB/sec"));
+        assert_eq!(ByteRate::kib(64), parse("512 kib/Second"));
+        assert_eq!(ByteRate::mb(10), parse("10MBps"));
+        assert_eq!(ByteRate::mbps(10), parse("10 mb/s"));
+        assert_eq!(ByteRate::mbps(10), parse("10 Mbit/s"));
+        assert_eq!(ByteRate::mbps(10), parse("10 mbits/s"));
+        assert_eq!(ByteRate::mib(3), parse("24 Mibit/s"));
+        assert_eq!(ByteRate::b(3), parse("3 B/s"));
+        assert_eq!(ByteRate::b(3), parse("24bps"));
+        assert_eq!(ByteRate::b(3), parse("24 bit/s"));
+        assert_eq!(ByteRate::b(3), parse("3 /s"));
+        assert_eq!(ByteRate::b(1536), parse("1536"));
+        assert_eq!(ByteRate::b(1536), parse("1.5 KiB/s"));
+        assert_eq!(ByteRate::b(187), parse("1.5 kbps"));
+        assert_eq!(ByteRate(u64::MAX), parse("20 EiB/s"));
+
+        // bit rates are converted to bytes before saturating
+        assert_eq!(ByteRate(12_500_000_000_000_000_000), parse("100 Ebit/s"));
+        assert_eq!(ByteRate(127 << 57), parse("127 Eibps"));
+        assert_eq!(ByteRate(u64::MAX / 8), parse("18446744073709551615 bit/s"));
+        assert_eq!(ByteRate(u64::MAX), parse("148 Ebit/s"));
+        assert_eq!(ByteRate(u64::MAX), parse("128 Eibit/s"));
+    }
+
+    #[test]
+    fn parsing_errors() {
+        let parse = |value: &str| value.parse::<ByteRate>().unwrap_err();
+
+        assert!(parse("10 MB").contains("not a rate"));
+        assert!(parse("10 mB/s").contains("fraction of a byte"));
+        assert!(parse("10 parsecs/s").contains("known unit"));
+        assert!(parse("1.2.3 MB/s").contains("invalid number"));
+        assert!(parse("ten MB/s").contains("empty"));
+        assert!(parse("").contains("empty"));
+        assert!(parse("10 MB/é").contains("not a rate"));
+        assert!(parse("10 é/s").contains("known unit"));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        use crate::Format;
+
+        for format in [Format::Iec, Format::Si, Format::IecBits, Format::SiBits] {
+            for rate in [ByteRate::b(1), ByteRate::kib(1), ByteRate::mbps(100)] {
+                let mut display = rate.per_second().display();
+                display.format = format;
+                let string = format!("{display}/s");
+
+                let parsed = string.parse::<ByteRate>().unwrap();
+                let mut reparsed = parsed.per_second().display();
+                reparsed.format = format;
+
+                assert_eq!(string, format!("{reparsed}/s"));
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut rate = ByteRate::kib(1) + ByteRate::kib(2);
+        assert_eq!(ByteRate::kib(3), rate);
+
+        rate -= ByteRate::kib(1);
+        rate *= 3_u8;
+        assert_eq!(ByteRate::kib(6), rate);
+        assert_eq!(ByteRate::kib(3), rate / 2_u32);
+        assert_eq!(ByteRate::kib(12), 2_u16 * rate);
+
+        rate /= 6_u64;
+        rate += ByteRate::b(1);
+        assert_eq!(ByteRate(1025), rate);
+
+        assert_eq!(
+            ByteRate::kib(6),
+            [ByteRate::kib(1), ByteRate::kib(2), ByteRate::kib(3)]
+                .iter()
+                .sum()
+        );
+
+        assert_eq!(None, ByteRate(u64::MAX).checked_add(ByteRate(1)));
+        assert_eq!(None, ByteRate(0).checked_sub(ByteRate(1)));
+        assert_eq!(
+            ByteRate(u64::MAX),
+            ByteRate(u64::MAX).saturating_add(ByteRate(1))
+        );
+        assert_eq!(ByteRate(0), ByteRate(0).saturating_sub(ByteRate(1)));
+    }
+
+    #[test]
+    fn duration_arithmetic() {
+        let secs = Duration::from_secs;
+        let millis = Duration::from_millis;
+
+        assert_eq!(ByteSize::mib(30), ByteRate::mib(3) * secs(10));
+        assert_eq!(ByteSize::kib(1), millis(250) * ByteRate::kib(4));
+        assert_eq!(ByteSize(333), ByteRate(1000) * millis(333));
+        assert_eq!(ByteSize(0), ByteRate(999) * Duration::from_nanos(1000));
+        assert_eq!(ByteSize(0), ByteRate(u64::MAX) * Duration::ZERO);
+        assert_eq!(ByteSize(u64::MAX), ByteRate(u64::MAX) * secs(1));
+        assert_eq!(ByteSize(u64::MAX), ByteRate(1) * secs(u64::MAX));
+
+        assert_eq!(ByteRate::mib(3), ByteSize::mib(30) / secs(10));
+        assert_eq!(ByteRate::kib(4), ByteSize::kib(1) / millis(250));
+        assert_eq!(ByteRate(333), ByteSize(1000) / secs(3));
+
+        assert_eq!(secs(10), ByteSize::mib(30) / ByteRate::mib(3));
+        assert_eq!(millis(250), ByteSize::kib(1) / ByteRate::kib(4));
+        assert_eq!(
+            Duration::new(3, 333_333_333),
+            ByteSize(1000) / ByteRate(300)
+        );
+        assert_eq!(secs(u64::MAX), ByteSize(u64::MAX) / ByteRate(1));
+        assert_eq!(Duration::ZERO, ByteSize(0) / ByteRate(1));
+
+        // round trips
+        let size = ByteSize::gib(5);
+        let rate = ByteRate::mbps(100);
+        assert_eq!(size, (size / rate) * rate);
+        assert_eq!(rate, size / (size / rate));
+    }
+
+    #[test]
+    fn checked_duration_arithmetic() {
+        assert_eq!(
+            None,
+            ByteRate(u64::MAX).checked_mul_duration(Duration::new(1, 1))
+        );
+        assert_eq!(None, ByteRate(2).checked_mul_duration(Duration::MAX));
+        assert_eq!(
+            Some(ByteSize(u64::MAX)),
+            ByteRate(1).checked_mul_duration(Duration::MAX)
+        );
+
+        assert_eq!(None, ByteSize(1).checked_div_duration(Duration::ZERO));
+        assert_eq!(None, ByteSize(0).checked_div_duration(Duration::ZERO));
+        assert_eq!(
+            None,
+            ByteSize(u64::MAX).checked_div_duration(Duration::from_nanos(1))
+        );
+        assert_eq!(
+            Some(ByteRate(0)),
+            ByteSize(1).checked_div_duration(Duration::MAX)
+        );
+
+        assert_eq!(None, ByteSize(1).checked_div_rate(ByteRate(0)));
+        assert_eq!(
+            Some(Duration::from_nanos(1)),
+            ByteSize(1).checked_div_rate(ByteRate::b(1_000_000_000))
+        );
+
+        assert_eq!(Some(ByteRate::kib(2)), ByteRate::kib(1).checked_mul(2));
+        assert_eq!(None, ByteRate(u64::MAX).checked_mul(2));
+        assert_eq!(Some(ByteRate::kib(1)), ByteRate::kib(2).checked_div(2));
+        assert_eq!(None, ByteRate::kib(2).checked_div(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn mul_duration_overflow() {
+        let _ = ByteRate(2) * Duration::MAX;
+    }
+
+    #[test]
+    #[should_panic(expected = "zero duration")]
+    fn div_zero_duration() {
+        let _ = ByteSize(1) / Duration::ZERO;
+    }
+
+    #[test]
+    #[should_panic(expected = "zero rate")]
+    fn div_zero_rate() {
+        let _ = ByteSize(1) / ByteRate(0);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(ByteRate::mbps(100) < ByteRate::mb(100));
+        assert!(ByteRate::kib(1) > ByteRate::kb(1));
+        assert_eq!(
+            Some(ByteRate::gib(1)),
+            [ByteRate::mib(1), ByteRate::gib(1), ByteRate::kib(1)]
+                .into_iter()
+                .max()
+        );
+    }
+}
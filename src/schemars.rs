@@ -0,0 +1,57 @@
+use alloc::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{parse::SIZE_PATTERN, ByteSize};
+
+/// Describes the human-readable serde representation: a non-negative integer number of bytes or a
+/// string like `"1.5 GiB"`.
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl JsonSchema for ByteSize {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ByteSize")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("bytesize::ByteSize")
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A size in bytes, as an integer number of bytes or a string with a \
+                unit, e.g., \"1.5 GiB\".",
+            "anyOf": [
+                {
+                    "type": "integer",
+                    "format": "uint64",
+                    "minimum": 0,
+                },
+                {
+                    "type": "string",
+                    "pattern": SIZE_PATTERN,
+                },
+            ],
+            "examples": [1536, "1.5 KiB", "2 GB"],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema() {
+        let schema = schemars::schema_for!(ByteSize);
+
+        assert_eq!(
+            Some("ByteSize"),
+            schema.get("title").and_then(|v| v.as_str())
+        );
+
+        let any_of = schema.get("anyOf").and_then(|v| v.as_array()).unwrap();
+        assert_eq!(Some("integer"), any_of[0]["type"].as_str());
+        assert_eq!(Some("string"), any_of[1]["type"].as_str());
+        assert_eq!(Some(SIZE_PATTERN), any_of[1]["pattern"].as_str());
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::string::{String, ToString as _};
+
+use ::sea_orm::{
+    sea_query::{ArrayType, ColumnType, Nullable, ValueType, ValueTypeErr},
+    ColIdx, DbErr, QueryResult, TryGetError, TryGetable, Value,
+};
+
+use crate::{sql::from_i64, ByteSize};
+
+/// Stored as a `BIGINT`. Sizes above [`i64::MAX`] bytes (8 EiB) saturate, since this conversion
+/// can't fail.
+#[cfg_attr(docsrs, doc(cfg(feature = "sea-orm")))]
+impl From<ByteSize> for Value {
+    fn from(size: ByteSize) -> Self {
+        Value::BigInt(Some(size.0.try_into().unwrap_or(i64::MAX)))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "sea-orm")))]
+impl Nullable for ByteSize {
+    fn null() -> Value {
+        Value::BigInt(None)
+    }
+}
+
+/// Fails for negative values.
+#[cfg_attr(docsrs, doc(cfg(feature = "sea-orm")))]
+impl ValueType for ByteSize {
+    fn try_from(value: Value) -> Result<Self, ValueTypeErr> {
+        from_i64(<i64 as ValueType>::try_from(value)?).map_err(|_| ValueTypeErr)
+    }
+
+    fn type_name() -> String {
+        "ByteSize".to_string()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::BigInt
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::BigInteger
+    }
+}
+
+/// Fails for negative values.
+#[cfg_attr(docsrs, doc(cfg(feature = "sea-orm")))]
+impl TryGetable for ByteSize {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        let value = i64::try_get_by(res, index)?;
+        from_i64(value).map_err(|err| TryGetError::DbErr(DbErr::Type(err.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, string::String, vec};
+
+    use ::sea_orm::{
+        ColumnTrait as _, DbBackend, EntityTrait as _, FromQueryResult as _, ProxyRow,
+        QueryFilter as _, QueryTrait as _, Values,
+    };
+
+    use super::*;
+
+    fn row(value: Value) -> QueryResult {
+        QueryResult::from(ProxyRow::new(BTreeMap::from([(
+            String::from("size"),
+            value,
+        )])))
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(Value::BigInt(Some(1024)), Value::from(ByteSize::kib(1)));
+        assert_eq!(
+            Value::BigInt(Some(i64::MAX)),
+            Value::from(ByteSize(u64::MAX))
+        );
+        assert_eq!(Value::BigInt(None), ByteSize::null());
+
+        assert_eq!(
+            ByteSize::mib(1),
+            <ByteSize as ValueType>::try_from(Value::BigInt(Some(1_048_576))).unwrap()
+        );
+        assert!(<ByteSize as ValueType>::try_from(Value::BigInt(Some(-1))).is_err());
+        assert!(<ByteSize as ValueType>::try_from(Value::BigInt(None)).is_err());
+        assert_eq!(ColumnType::BigInteger, ByteSize::column_type());
+    }
+
+    #[test]
+    fn query_results() {
+        let res = row(Value::BigInt(Some(2048)));
+        assert_eq!(
+            ByteSize::kib(2),
+            res.try_get::<ByteSize>("", "size").unwrap()
+        );
+        assert_eq!(
+            Some(ByteSize::kib(2)),
+            res.try_get::<Option<ByteSize>>("", "size").unwrap()
+        );
+
+        let res = row(Value::BigInt(None));
+        assert_eq!(None, res.try_get::<Option<ByteSize>>("", "size").unwrap());
+
+        let err = row(Value::BigInt(Some(-1)))
+            .try_get::<ByteSize>("", "size")
+            .unwrap_err();
+        assert_eq!(
+            DbErr::Type("-1 is not a valid size, sizes can't be negative".to_string()),
+            err
+        );
+    }
+
+    mod upload {
+        use ::sea_orm::entity::prelude::*;
+
+        use crate::ByteSize;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "upload")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub size: ByteSize,
+            pub limit: Option<ByteSize>,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[test]
+    fn entity() {
+        let query = upload::Entity::find()
+            .filter(upload::Column::Size.gt(ByteSize::mib(1)))
+            .build(DbBackend::Postgres);
+        assert_eq!(
+            Some(Values(vec![Value::BigInt(Some(1_048_576))])),
+            query.values
+        );
+
+        let res = QueryResult::from(ProxyRow::new(BTreeMap::from([
+            (String::from("id"), Value::Int(Some(1))),
+            (String::from("size"), Value::BigInt(Some(1024))),
+            (String::from("limit"), Value::BigInt(None)),
+        ])));
+        let model = upload::Model::from_query_result(&res, "").unwrap();
+        assert_eq!(ByteSize::kib(1), model.size);
+        assert_eq!(None, model.limit);
+    }
+}
@@ -1,51 +1,99 @@
+//! Serde helpers for `ByteSize` fields.
+//!
+//! `ByteSize` implements `Serialize` and `Deserialize` itself. Human-readable formats use a
+//! string, e.g., `"1.5 MiB"`, while binary formats use the plain byte count. Integers, floats,
+//! and strings are all accepted when deserializing human-readable formats.
+//!
+//! Floats are byte counts, e.g., `1.5e9`. Like fractional bytes in strings, any fractional part is
+//! truncated, so `1536.9` is 1536 bytes. Negative, NaN, and out-of-range floats are rejected.
+//!
+//! The modules here are for use with `#[serde(with = "...")]` when a field needs a different
+//! representation: [`string`] always uses a string, [`flexible`] accepts either form in any
+//! self-describing format, and modules like [`as_mib`] use a plain integer count of a fixed unit.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::ByteSize;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Limits {
+//!     #[serde(with = "bytesize::serde::as_mb")]
+//!     memory_mb: ByteSize,
+//! }
+//!
+//! let limits: Limits = serde_json::from_str(r#"{"memory_mb":512}"#).unwrap();
+//! assert_eq!(ByteSize::mb(512), limits.memory_mb);
+//! assert_eq!(r#"{"memory_mb":512}"#, serde_json::to_string(&limits).unwrap());
+//! ```
+
 use alloc::string::{String, ToString as _};
 use core::fmt;
 
 use serde_core::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::ByteSize;
+use crate::{ByteSize, UnitPreference};
 
-impl<'de> Deserialize<'de> for ByteSize {
-    fn deserialize<D>(de: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        struct ByteSizeVisitor;
+/// Accepts an integer or float number of bytes, or a string to parse.
+struct ByteSizeVisitor;
 
-        impl de::Visitor<'_> for ByteSizeVisitor {
-            type Value = ByteSize;
+impl de::Visitor<'_> for ByteSizeVisitor {
+    type Value = ByteSize;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str("an integer or string")
-            }
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a number or string")
+    }
 
-            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
-                if let Ok(val) = u64::try_from(value) {
-                    Ok(ByteSize(val))
-                } else {
-                    Err(E::invalid_value(
-                        de::Unexpected::Signed(value),
-                        &"integer overflow",
-                    ))
-                }
-            }
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        if let Ok(val) = u64::try_from(value) {
+            Ok(ByteSize(val))
+        } else {
+            Err(E::invalid_value(
+                de::Unexpected::Signed(value),
+                &"integer overflow",
+            ))
+        }
+    }
 
-            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
-                Ok(ByteSize(value))
-            }
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(ByteSize(value))
+    }
 
-            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
-                if let Ok(val) = value.parse() {
-                    Ok(val)
-                } else {
-                    Err(E::invalid_value(
-                        de::Unexpected::Str(value),
-                        &"parsable string",
-                    ))
-                }
-            }
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        // 2^64, the first value that doesn't fit after truncation
+        const LIMIT: f64 = 18_446_744_073_709_551_616.0;
+
+        // also rejects NaN
+        if (0.0..LIMIT).contains(&value) {
+            // truncates towards zero
+            Ok(ByteSize(value as u64))
+        } else {
+            Err(E::invalid_value(
+                de::Unexpected::Float(value),
+                &"a non-negative byte count that fits in u64",
+            ))
         }
+    }
 
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        if let Ok(val) = value.parse() {
+            Ok(val)
+        } else {
+            Err(E::invalid_value(
+                de::Unexpected::Str(value),
+                &"parsable string",
+            ))
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         if de.is_human_readable() {
             de.deserialize_any(ByteSizeVisitor)
         } else {
@@ -54,6 +102,7 @@ impl<'de> Deserialize<'de> for ByteSize {
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl Serialize for ByteSize {
     fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
     where
@@ -67,6 +116,262 @@ impl Serialize for ByteSize {
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for UnitPreference {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PreferenceVisitor;
+
+        impl de::Visitor<'_> for PreferenceVisitor {
+            type Value = UnitPreference;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a unit preference string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        de.deserialize_str(PreferenceVisitor)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for UnitPreference {
+    fn serialize<S>(&self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.collect_str(self)
+    }
+}
+
+/// Always (de)serializes a `ByteSize` as a string, even in binary formats.
+///
+/// Sizes are written exactly, using [`ByteSize::to_string_exact()`], so they parse back to the
+/// same value. Any string accepted by [`FromStr`](core::str::FromStr) can be read.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::ByteSize;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "bytesize::serde::string")]
+///     cache: ByteSize,
+/// }
+///
+/// let config = Config { cache: ByteSize::kib(1536) };
+///
+/// let json = serde_json::to_string(&config).unwrap();
+/// assert_eq!(r#"{"cache":"1536 KiB"}"#, json);
+/// assert_eq!(config, serde_json::from_str(&json).unwrap());
+/// assert!(serde_json::from_str::<Config>(r#"{"cache":1024}"#).is_err());
+/// ```
+pub mod string {
+    use core::fmt;
+
+    use serde_core::{de, Deserializer, Serializer};
+
+    use crate::ByteSize;
+
+    /// Serializes `size` as an exact string.
+    pub fn serialize<S>(size: &ByteSize, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ser.serialize_str(&size.to_string_exact())
+    }
+
+    /// Deserializes a size from a string.
+    pub fn deserialize<'de, D>(de: D) -> Result<ByteSize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StringVisitor;
+
+        impl de::Visitor<'_> for StringVisitor {
+            type Value = ByteSize;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a byte size string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value
+                    .parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &"parsable string"))
+            }
+        }
+
+        de.deserialize_str(StringVisitor)
+    }
+}
+
+/// Accepts either an integer number of bytes or a string, in any self-describing format.
+///
+/// `ByteSize`'s own `Deserialize` impl already accepts both in human-readable formats, like JSON
+/// and TOML, but expects an integer from binary formats. Use this module for self-describing
+/// binary formats, like CBOR or MessagePack, whose data may contain either form. Serialization is
+/// the same as `ByteSize`'s own `Serialize` impl.
+///
+/// Non-self-describing formats, like bincode, are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use bytesize::ByteSize;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "bytesize::serde::flexible")]
+///     cache: ByteSize,
+/// }
+///
+/// let from_number: Config = serde_json::from_str(r#"{"cache":1048576}"#).unwrap();
+/// let from_string: Config = serde_json::from_str(r#"{"cache":"1 MiB"}"#).unwrap();
+/// assert_eq!(from_number, from_string);
+/// ```
+pub mod flexible {
+    use serde_core::{Deserializer, Serialize as _, Serializer};
+
+    use crate::ByteSize;
+
+    /// Serializes `size` the same way as `ByteSize`'s `Serialize` impl.
+    pub fn serialize<S>(size: &ByteSize, ser: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        size.serialize(ser)
+    }
+
+    /// Deserializes a size from either an integer number of bytes or a string.
+    pub fn deserialize<'de, D>(de: D) -> Result<ByteSize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        de.deserialize_any(super::ByteSizeVisitor)
+    }
+}
+
+/// Serializes `size` as an integer count of `factor`, failing if it isn't a whole number.
+pub(crate) fn serialize_in<S>(
+    size: &ByteSize,
+    factor: u64,
+    unit: &str,
+    ser: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if size.0 % factor != 0 {
+        return Err(serde_core::ser::Error::custom(format_args!(
+            "{} bytes is not a whole number of {unit}",
+            size.0,
+        )));
+    }
+
+    ser.serialize_u64(size.0 / factor)
+}
+
+/// Deserializes a size from a non-negative integer count of `factor`.
+pub(crate) fn deserialize_in<'de, D>(
+    de: D,
+    factor: u64,
+    unit: &'static str,
+) -> Result<ByteSize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct CountVisitor {
+        factor: u64,
+        unit: &'static str,
+    }
+
+    impl CountVisitor {
+        fn scale<E: de::Error>(&self, count: u64) -> Result<ByteSize, E> {
+            count.checked_mul(self.factor).map(ByteSize).ok_or_else(|| {
+                E::invalid_value(de::Unexpected::Unsigned(count), &"a size that fits in u64")
+            })
+        }
+    }
+
+    impl de::Visitor<'_> for CountVisitor {
+        type Value = ByteSize;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "a non-negative integer number of {}", self.unit)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            match u64::try_from(value) {
+                Ok(count) => self.scale(count),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Signed(value), &self)),
+            }
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            self.scale(value)
+        }
+    }
+
+    de.deserialize_u64(CountVisitor { factor, unit })
+}
+
+macro_rules! unit_modules {
+    ($($module:ident => $factor:ident, $unit:literal;)*) => {
+        $(
+            #[doc = concat!("(De)serializes a `ByteSize` as a plain integer number of ", $unit, ".")]
+            ///
+            /// Serializing fails if the size isn't a whole number of the unit, rather than losing
+            /// precision. Deserializing fails if the size overflows.
+            pub mod $module {
+                use serde_core::{Deserializer, Serializer};
+
+                use crate::ByteSize;
+
+                #[doc = concat!("Serializes `size` as a number of ", $unit, ".")]
+                pub fn serialize<S>(size: &ByteSize, ser: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    super::serialize_in(size, crate::$factor, $unit, ser)
+                }
+
+                #[doc = concat!("Deserializes a size from a number of ", $unit, ".")]
+                pub fn deserialize<'de, D>(de: D) -> Result<ByteSize, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    super::deserialize_in(de, crate::$factor, $unit)
+                }
+            }
+        )*
+    };
+}
+
+unit_modules! {
+    as_kb => KB, "kilobytes";
+    as_mb => MB, "megabytes";
+    as_gb => GB, "gigabytes";
+    as_tb => TB, "terabytes";
+    as_pb => PB, "petabytes";
+    as_eb => EB, "exabytes";
+    as_kib => KIB, "kibibytes";
+    as_mib => MIB, "mebibytes";
+    as_gib => GIB, "gibibytes";
+    as_tib => TIB, "tebibytes";
+    as_pib => PIB, "pebibytes";
+    as_eib => EIB, "exbibytes";
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,6 +399,42 @@ mod tests {
         assert_eq!(s.x, "9223372036854775807".parse::<ByteSize>().unwrap());
     }
 
+    #[test]
+    fn test_serde_float() {
+        #[derive(Debug, Deserialize)]
+        struct S {
+            x: ByteSize,
+        }
+
+        #[track_caller]
+        fn assert_json(expected: ByteSize, x: &str) {
+            let s = serde_json::from_str::<S>(&alloc::format!(r#"{{ "x": {x} }}"#)).unwrap();
+            assert_eq!(expected, s.x);
+        }
+
+        assert_json(ByteSize::gb(1) + ByteSize::mb(500), "1.5e9");
+        assert_json(ByteSize(1536), "1536.9");
+        assert_json(ByteSize(0), "0.0");
+        assert_json(ByteSize(0), "-0.0");
+        assert_json(ByteSize(0), "0.999");
+        assert_json(ByteSize(1 << 63), "9.223372036854775808e18");
+        assert_json(ByteSize(u64::MAX - 2047), "18446744073709549568.0");
+
+        for x in ["-1.0", "-0.5", "1.8446744073709552e19", "1e300"] {
+            let err = serde_json::from_str::<S>(&alloc::format!(r#"{{ "x": {x} }}"#)).unwrap_err();
+            assert!(
+                err.to_string().contains("non-negative byte count"),
+                "x: {x}, err: {err}",
+            );
+        }
+
+        let s = toml::from_str::<S>("x = 2.5e3").unwrap();
+        assert_eq!(s.x, ByteSize(2500));
+
+        let s = toml::from_str::<S>("x = nan");
+        assert!(s.is_err());
+    }
+
     #[test]
     fn test_serde_json() {
         let json = serde_json::to_string(&ByteSize::mib(1)).unwrap();
@@ -102,4 +443,166 @@ mod tests {
         let deserialized = serde_json::from_str::<ByteSize>(&json).unwrap();
         assert_eq!(deserialized.0, 1048576);
     }
+
+    #[test]
+    fn test_string() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Configure as _, Token};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+        struct S {
+            #[serde(with = "crate::serde::string")]
+            x: ByteSize,
+        }
+
+        let tokens = |value| {
+            [
+                Token::Struct { name: "S", len: 1 },
+                Token::Str("x"),
+                Token::Str(value),
+                Token::StructEnd,
+            ]
+        };
+
+        let s = S { x: ByteSize(1537) };
+        assert_tokens(&s.compact(), &tokens("1537 B"));
+        assert_tokens(&s.readable(), &tokens("1537 B"));
+
+        let s = S {
+            x: ByteSize::eib(15),
+        };
+        assert_tokens(&s.compact(), &tokens("15 EiB"));
+
+        assert_de_tokens_error::<S>(
+            &[
+                Token::Struct { name: "S", len: 1 },
+                Token::Str("x"),
+                Token::U64(1024),
+            ],
+            "invalid type: integer `1024`, expected a byte size string",
+        );
+
+        let s = toml::from_str::<S>(r#"x = "2.5 MiB""#).unwrap();
+        assert_eq!(s.x, ByteSize::kib(2560));
+        assert_eq!("x = \"2560 KiB\"\n", toml::to_string(&s).unwrap());
+    }
+
+    #[test]
+    fn test_unit_preference() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Settings {
+            units: UnitPreference,
+        }
+
+        let settings = toml::from_str::<Settings>(r#"units = "iec short""#).unwrap();
+        assert_eq!(UnitPreference::iec().short(), settings.units);
+        assert_eq!(
+            "units = \"iec short\"\n",
+            toml::to_string(&settings).unwrap()
+        );
+
+        let err = toml::from_str::<Settings>(r#"units = "binary""#).unwrap_err();
+        assert!(err.to_string().contains("unknown units"), "{err}");
+    }
+
+    #[test]
+    fn test_flexible() {
+        use serde_test::{assert_de_tokens, assert_tokens, Configure as _, Token};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+        struct S {
+            #[serde(with = "crate::serde::flexible")]
+            x: ByteSize,
+        }
+
+        let tokens = |value| {
+            [
+                Token::Struct { name: "S", len: 1 },
+                Token::Str("x"),
+                value,
+                Token::StructEnd,
+            ]
+        };
+
+        let s = S {
+            x: ByteSize::kib(4),
+        };
+        assert_tokens(&s.compact(), &tokens(Token::U64(4096)));
+        assert_tokens(&s.readable(), &tokens(Token::Str("4.0 KiB")));
+
+        // both forms are accepted, regardless of format
+        assert_de_tokens(&s.compact(), &tokens(Token::Str("4 KiB")));
+        assert_de_tokens(&s.readable(), &tokens(Token::I64(4096)));
+    }
+
+    #[test]
+    fn test_unit_modules() {
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Token};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct S {
+            #[serde(with = "crate::serde::as_mib")]
+            mib: ByteSize,
+            #[serde(with = "crate::serde::as_kb")]
+            kb: ByteSize,
+        }
+
+        assert_tokens(
+            &S {
+                mib: ByteSize::mib(512),
+                kb: ByteSize::kb(3),
+            },
+            &[
+                Token::Struct { name: "S", len: 2 },
+                Token::Str("mib"),
+                Token::U64(512),
+                Token::Str("kb"),
+                Token::U64(3),
+                Token::StructEnd,
+            ],
+        );
+
+        assert_ser_tokens_error(
+            &S {
+                mib: ByteSize::mib(1),
+                kb: ByteSize::b(1500),
+            },
+            &[
+                Token::Struct { name: "S", len: 2 },
+                Token::Str("mib"),
+                Token::U64(1),
+                Token::Str("kb"),
+            ],
+            "1500 bytes is not a whole number of kilobytes",
+        );
+
+        assert_de_tokens_error::<S>(
+            &[
+                Token::Struct { name: "S", len: 2 },
+                Token::Str("mib"),
+                Token::I64(-1),
+            ],
+            "invalid value: integer `-1`, expected a non-negative integer number of mebibytes",
+        );
+
+        assert_de_tokens_error::<S>(
+            &[
+                Token::Struct { name: "S", len: 2 },
+                Token::Str("mib"),
+                Token::U64(1 << 50),
+            ],
+            "invalid value: integer `1125899906842624`, expected a size that fits in u64",
+        );
+
+        assert_de_tokens_error::<S>(
+            &[
+                Token::Struct { name: "S", len: 2 },
+                Token::Str("mib"),
+                Token::Str("1 MiB"),
+            ],
+            "invalid type: string \"1 MiB\", expected a non-negative integer number of mebibytes",
+        );
+
+        let s = serde_json::from_str::<S>(r#"{ "mib": 2, "kb": 0 }"#).unwrap();
+        assert_eq!(s.mib, ByteSize::mib(2));
+    }
 }
@@ -0,0 +1,197 @@
+//! Adapters for the `serde_with` crate.
+//!
+//! The modules in [`serde`](crate::serde) pick a representation for a whole field. These adapters
+//! implement `SerializeAs` and `DeserializeAs` instead, so they can also pick one for sizes nested
+//! inside other types, like `Vec<ByteSize>`, map values, or `Option<ByteSize>`.
+//!
+//! Each adapter has the same representation as the module of the matching name: [`HumanReadable`]
+//! matches [`serde::string`](crate::serde::string), [`AsBytes`] is a plain integer number of
+//! bytes, and unit adapters like [`AsKib`] match [`serde::as_kib`](crate::serde::as_kib).
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use bytesize::{
+//!     serde_with::{AsMib, HumanReadable},
+//!     ByteSize,
+//! };
+//! use serde::{Deserialize, Serialize};
+//! use serde_with::serde_as;
+//!
+//! #[serde_as]
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde_as(as = "Vec<AsMib>")]
+//!     buffers: Vec<ByteSize>,
+//!
+//!     #[serde_as(as = "BTreeMap<_, HumanReadable>")]
+//!     quotas: BTreeMap<String, ByteSize>,
+//! }
+//!
+//! let json = r#"{"buffers":[1,4],"quotas":{"alice":"1536 KiB"}}"#;
+//! let config: Config = serde_json::from_str(json).unwrap();
+//!
+//! assert_eq!(vec![ByteSize::mib(1), ByteSize::mib(4)], config.buffers);
+//! assert_eq!(ByteSize::kib(1536), config.quotas["alice"]);
+//! assert_eq!(json, serde_json::to_string(&config).unwrap());
+//! ```
+
+use ::serde_with::{DeserializeAs, SerializeAs};
+use serde_core::{Deserializer, Serializer};
+
+use crate::ByteSize;
+
+/// (De)serializes a `ByteSize` as an exact string, e.g., `"1536 KiB"`, even in binary formats.
+///
+/// Same as [`serde::string`](crate::serde::string).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanReadable;
+
+impl SerializeAs<ByteSize> for HumanReadable {
+    fn serialize_as<S>(source: &ByteSize, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::serde::string::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, ByteSize> for HumanReadable {
+    fn deserialize_as<D>(deserializer: D) -> Result<ByteSize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::serde::string::deserialize(deserializer)
+    }
+}
+
+/// (De)serializes a `ByteSize` as a plain integer number of bytes, even in human-readable formats.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsBytes;
+
+impl SerializeAs<ByteSize> for AsBytes {
+    fn serialize_as<S>(source: &ByteSize, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::serde::serialize_in(source, 1, "bytes", serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, ByteSize> for AsBytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<ByteSize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::serde::deserialize_in(deserializer, 1, "bytes")
+    }
+}
+
+macro_rules! unit_adapters {
+    ($($adapter:ident => $module:ident, $unit:literal;)*) => {
+        $(
+            #[doc = concat!("(De)serializes a `ByteSize` as a plain integer number of ", $unit, ".")]
+            ///
+            #[doc = concat!("Same as [`serde::", stringify!($module), "`](crate::serde::", stringify!($module), ").")]
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct $adapter;
+
+            impl SerializeAs<ByteSize> for $adapter {
+                fn serialize_as<S>(source: &ByteSize, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    crate::serde::$module::serialize(source, serializer)
+                }
+            }
+
+            impl<'de> DeserializeAs<'de, ByteSize> for $adapter {
+                fn deserialize_as<D>(deserializer: D) -> Result<ByteSize, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    crate::serde::$module::deserialize(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+unit_adapters! {
+    AsKb => as_kb, "kilobytes";
+    AsMb => as_mb, "megabytes";
+    AsGb => as_gb, "gigabytes";
+    AsTb => as_tb, "terabytes";
+    AsPb => as_pb, "petabytes";
+    AsEb => as_eb, "exabytes";
+    AsKib => as_kib, "kibibytes";
+    AsMib => as_mib, "mebibytes";
+    AsGib => as_gib, "gibibytes";
+    AsTib => as_tib, "tebibytes";
+    AsPib => as_pib, "pebibytes";
+    AsEib => as_eib, "exbibytes";
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
+    use ::serde_with::serde_as;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[test]
+    fn collections() {
+        #[serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct S {
+            #[serde_as(as = "Vec<HumanReadable>")]
+            strings: Vec<ByteSize>,
+
+            #[serde_as(as = "BTreeMap<_, AsKib>")]
+            kib: BTreeMap<String, ByteSize>,
+
+            #[serde_as(as = "Option<AsBytes>")]
+            bytes: Option<ByteSize>,
+        }
+
+        let s = S {
+            strings: vec![ByteSize::b(1), ByteSize::kib(2)],
+            kib: BTreeMap::from([(String::from("cache"), ByteSize::mib(2))]),
+            bytes: Some(ByteSize::kib(1)),
+        };
+
+        let json = r#"{"strings":["1 B","2 KiB"],"kib":{"cache":2048},"bytes":1024}"#;
+        assert_eq!(json, serde_json::to_string(&s).unwrap());
+        assert_eq!(s, serde_json::from_str(json).unwrap());
+
+        let toml = "strings = [\"1 B\", \"2 KiB\"]\nbytes = 1024\n\n[kib]\ncache = 2048\n";
+        assert_eq!(s, toml::from_str(toml).unwrap());
+    }
+
+    #[test]
+    fn unit_errors() {
+        #[serde_as]
+        #[derive(Debug, Serialize, Deserialize)]
+        struct S {
+            #[serde_as(as = "Vec<AsMb>")]
+            sizes: Vec<ByteSize>,
+        }
+
+        let err = serde_json::to_string(&S {
+            sizes: vec![ByteSize::kb(1500)],
+        })
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("1500000 bytes is not a whole number of megabytes"),
+            "{err}"
+        );
+
+        assert!(serde_json::from_str::<S>(r#"{"sizes":[-1]}"#).is_err());
+        assert!(serde_json::from_str::<S>(r#"{"sizes":["1 MB"]}"#).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::{boxed::Box, string::ToString as _};
+
+use serde_core::ser::SerializeStruct as _;
+use slog::{Key, Record, SerdeValue, Serializer, Value};
+
+use crate::ByteSize;
+
+/// Nested representation of a size, with the number of `bytes` and a `human` string.
+#[derive(Debug, Clone, Copy)]
+struct Nested(ByteSize);
+
+impl serde_core::Serialize for Nested {
+    fn serialize<S: serde_core::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nested = serializer.serialize_struct("ByteSize", 2)?;
+        nested.serialize_field("bytes", &self.0 .0)?;
+        nested.serialize_field("human", &self.0.to_string())?;
+        nested.end()
+    }
+}
+
+impl Value for Nested {
+    fn serialize(
+        &self,
+        _record: &Record<'_>,
+        key: Key,
+        serializer: &mut dyn Serializer,
+    ) -> slog::Result {
+        serializer.emit_serde(key, self)
+    }
+}
+
+impl SerdeValue for Nested {
+    /// Writes both forms in one string, e.g., `1.5 KiB (1536 B)`, for drains without nested values.
+    fn serialize_fallback(&self, key: Key, serializer: &mut dyn Serializer) -> slog::Result {
+        serializer.emit_arguments(key, &format_args!("{} ({} B)", self.0, self.0 .0))
+    }
+
+    fn as_serde(&self) -> &dyn erased_serde::Serialize {
+        self
+    }
+
+    fn to_sendable(&self) -> Box<dyn SerdeValue + Send + 'static> {
+        Box::new(*self)
+    }
+}
+
+/// Logged as a nested value with the number of `bytes` and a `human` string formatted like
+/// `ByteSize`'s `Display` impl, e.g., `{"bytes":1536,"human":"1.5 KiB"}` with `slog-json`.
+///
+/// Drains that don't support nested values get both in one string instead, e.g.,
+/// `1.5 KiB (1536 B)`.
+#[cfg_attr(docsrs, doc(cfg(feature = "slog")))]
+impl Value for ByteSize {
+    fn serialize(
+        &self,
+        record: &Record<'_>,
+        key: Key,
+        serializer: &mut dyn Serializer,
+    ) -> slog::Result {
+        Value::serialize(&Nested(*self), record, key, serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+    use core::fmt;
+    use std::sync::{Arc, Mutex};
+
+    use slog::{info, o, Drain, Logger, OwnedKVList, KV as _};
+
+    use super::*;
+
+    /// Drain collecting the key-value pairs of each record.
+    #[derive(Default)]
+    struct Collect {
+        nested: bool,
+        pairs: Mutex<Vec<(String, String)>>,
+    }
+
+    struct CollectSerializer<'a>(&'a Collect, Vec<(String, String)>);
+
+    impl Serializer for CollectSerializer<'_> {
+        fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments<'_>) -> slog::Result {
+            self.1.push((key.to_string(), val.to_string()));
+            Ok(())
+        }
+
+        fn emit_serde(&mut self, key: Key, value: &dyn SerdeValue) -> slog::Result {
+            if self.0.nested {
+                let json = serde_json::to_string(value.as_serde()).unwrap();
+                self.1.push((key.to_string(), json));
+                Ok(())
+            } else {
+                value.serialize_fallback(key, self)
+            }
+        }
+    }
+
+    impl Drain for Collect {
+        type Ok = ();
+        type Err = slog::Never;
+
+        fn log(&self, record: &Record<'_>, _values: &OwnedKVList) -> Result<(), Self::Err> {
+            let mut serializer = CollectSerializer(self, Vec::new());
+            record.kv().serialize(record, &mut serializer).unwrap();
+            self.pairs.lock().unwrap().extend(serializer.1);
+            Ok(())
+        }
+    }
+
+    fn log(nested: bool) -> Vec<(String, String)> {
+        let drain = Arc::new(Collect {
+            nested,
+            ..Collect::default()
+        });
+        let logger = Logger::root(Arc::clone(&drain), o!());
+        info!(logger, "upload"; "size" => ByteSize::kib(1536));
+
+        let pairs = drain.pairs.lock().unwrap();
+        pairs.clone()
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(
+            [(
+                "size".to_string(),
+                r#"{"bytes":1572864,"human":"1.5 MiB"}"#.to_string()
+            )],
+            *log(true)
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        assert_eq!(
+            [("size".to_string(), "1.5 MiB (1572864 B)".to_string())],
+            *log(false)
+        );
+    }
+
+    #[test]
+    fn sendable() {
+        let sendable = Nested(ByteSize::b(1)).to_sendable();
+        assert_eq!(
+            r#"{"bytes":1,"human":"1 B"}"#,
+            serde_json::to_string(sendable.as_serde()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use speedy::{Context, Readable, Reader, Writable, Writer};
+
+use crate::ByteSize;
+
+/// Reads the byte count as a `u64`.
+#[cfg_attr(docsrs, doc(cfg(feature = "speedy")))]
+impl<'a, C: Context> Readable<'a, C> for ByteSize {
+    #[inline]
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        Ok(ByteSize(reader.read_u64()?))
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        <u64 as Readable<'a, C>>::minimum_bytes_needed()
+    }
+}
+
+/// Writes the byte count as a `u64`.
+#[cfg_attr(docsrs, doc(cfg(feature = "speedy")))]
+impl<C: Context> Writable<C> for ByteSize {
+    #[inline]
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        writer.write_u64(self.0)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        <u64 as Writable<C>>::bytes_needed(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speedy::{BigEndian, LittleEndian};
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let size = ByteSize::mib(42);
+
+        let bytes = size.write_to_vec_with_ctx(LittleEndian::default()).unwrap();
+        assert_eq!(size.as_u64().to_le_bytes().as_slice(), bytes);
+        assert_eq!(
+            size,
+            ByteSize::read_from_buffer_with_ctx(LittleEndian::default(), &bytes).unwrap(),
+        );
+
+        let bytes = size.write_to_vec_with_ctx(BigEndian::default()).unwrap();
+        assert_eq!(size.as_u64().to_be_bytes().as_slice(), bytes);
+        assert_eq!(
+            size,
+            ByteSize::read_from_buffer_with_ctx(BigEndian::default(), &bytes).unwrap(),
+        );
+
+        assert!(ByteSize::read_from_buffer_with_ctx(LittleEndian::default(), &[0; 7]).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+//! Conversions shared by the database integrations, which store sizes as signed 64-bit integers.
+
+use alloc::{boxed::Box, format};
+
+use crate::ByteSize;
+
+/// Error type used by database drivers for conversion failures.
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Converts `size` to an `i64`, failing for sizes above [`i64::MAX`] bytes (8 EiB).
+pub(crate) fn to_i64(size: ByteSize) -> Result<i64, BoxError> {
+    i64::try_from(size.0).map_err(|_| {
+        Box::from(format!(
+            "{} bytes is too large to store as a signed 64-bit integer",
+            size.0
+        ))
+    })
+}
+
+/// Converts a stored `value` back to a size, failing for negative values.
+pub(crate) fn from_i64(value: i64) -> Result<ByteSize, BoxError> {
+    u64::try_from(value).map(ByteSize).map_err(|_| {
+        Box::from(format!(
+            "{value} is not a valid size, sizes can't be negative"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString as _;
+
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(1024, to_i64(ByteSize::kib(1)).unwrap());
+        assert_eq!(i64::MAX, to_i64(ByteSize(i64::MAX as u64)).unwrap());
+        assert_eq!(
+            "9223372036854775808 bytes is too large to store as a signed 64-bit integer",
+            to_i64(ByteSize::eib(8)).unwrap_err().to_string(),
+        );
+
+        assert_eq!(ByteSize::mib(1), from_i64(1_048_576).unwrap());
+        assert_eq!(ByteSize(i64::MAX as u64), from_i64(i64::MAX).unwrap());
+        assert_eq!(
+            "-1 is not a valid size, sizes can't be negative",
+            from_i64(-1).unwrap_err().to_string(),
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use sqlx_core::{
+    database::Database,
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    types::Type,
+};
+
+use crate::{
+    sql::{from_i64, to_i64},
+    ByteSize,
+};
+
+/// Stored as a signed 64-bit integer, e.g., `BIGINT` in PostgreSQL and MySQL or `INTEGER` in
+/// SQLite, in any database that supports `i64`.
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+impl<DB> Type<DB> for ByteSize
+where
+    DB: Database,
+    i64: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+    }
+}
+
+/// Fails for sizes above [`i64::MAX`] bytes (8 EiB).
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+impl<'q, DB> Encode<'q, DB> for ByteSize
+where
+    DB: Database,
+    i64: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        to_i64(*self)?.encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        size_of::<i64>()
+    }
+}
+
+/// Fails for negative values.
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+impl<'r, DB> Decode<'r, DB> for ByteSize
+where
+    DB: Database,
+    i64: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        from_i64(<i64 as Decode<'r, DB>>::decode(value)?)
+    }
+}
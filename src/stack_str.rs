@@ -0,0 +1,63 @@
+//! Allocation-free IEC formatting, shared by the logging integrations for embedded targets.
+
+use core::fmt;
+
+use crate::ByteSize;
+
+/// Formats `size` in IEC units, e.g., `1.5 KiB`, without allocating.
+pub(crate) fn iec(size: ByteSize) -> Option<StackStr> {
+    let mut buf = StackStr::default();
+    size.display()
+        .iec()
+        .write_with_precision(&mut buf, None)
+        .ok()?;
+    Some(buf)
+}
+
+/// Fixed-capacity string, long enough for any IEC size, e.g., `1023.9 KiB`.
+#[derive(Default)]
+pub(crate) struct StackStr {
+    buf: [u8; 16],
+    len: usize,
+}
+
+impl StackStr {
+    pub(crate) fn as_str(&self) -> &str {
+        // only whole `str`s are written
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for StackStr {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iec_strings() {
+        assert_eq!("0 B", iec(ByteSize(0)).unwrap().as_str());
+        assert_eq!("1023 B", iec(ByteSize(1023)).unwrap().as_str());
+        assert_eq!("1.5 KiB", iec(ByteSize::b(1536)).unwrap().as_str());
+        assert_eq!(
+            "1023.9 MiB",
+            iec(ByteSize::kib(1_048_474)).unwrap().as_str()
+        );
+        assert_eq!("16.0 EiB", iec(ByteSize(u64::MAX)).unwrap().as_str());
+
+        for exp in 0..64 {
+            let size = ByteSize((1 << exp) | ((1 << exp) - 1));
+            assert!(iec(size).is_some(), "{size}");
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use alloc::{format, string::String};
+use core::str;
+
+use crate::{ByteSize, Unit};
+
+/// Tokens used for the `unit` parameter.
+const UNIT_TOKENS: [(Unit, &str); 13] = [
+    (Unit::Byte, "B"),
+    (Unit::KiloByte, "kB"),
+    (Unit::MegaByte, "MB"),
+    (Unit::GigaByte, "GB"),
+    (Unit::TeraByte, "TB"),
+    (Unit::PetaByte, "PB"),
+    (Unit::ExaByte, "EB"),
+    (Unit::KibiByte, "KiB"),
+    (Unit::MebiByte, "MiB"),
+    (Unit::GibiByte, "GiB"),
+    (Unit::TebiByte, "TiB"),
+    (Unit::PebiByte, "PiB"),
+    (Unit::ExbiByte, "EiB"),
+];
+
+/// Largest integer allowed in a structured field, see RFC 8941 §3.3.1.
+const MAX_INTEGER: u64 = 999_999_999_999_999;
+
+/// Returns the symbol of `unit`, e.g., `kB` or `MiB`.
+pub(crate) fn token_of(unit: Unit) -> &'static str {
+    UNIT_TOKENS
+        .iter()
+        .find(|(candidate, _)| *candidate == unit)
+        .map_or("B", |(_, token)| token)
+}
+
+impl ByteSize {
+    /// Formats the size as an HTTP structured field integer item (RFC 8941), e.g., `1536` or
+    /// `12;unit=MiB`.
+    ///
+    /// The size is written as a whole number of `unit`, with a `unit` parameter, if it divides
+    /// evenly, and as a number of bytes otherwise. Parse it back with
+    /// [`from_structured_field()`](Self::from_structured_field).
+    ///
+    /// Returns `None` if the count has more than the 15 digits allowed in a structured field, e.g.,
+    /// for sizes of 10<sup>15</sup> bytes or more written in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::{ByteSize, Unit};
+    ///
+    /// let size = ByteSize::kib(1536);
+    /// assert_eq!(Some("1572864".to_owned()), size.to_structured_field(Unit::Byte));
+    /// assert_eq!(Some("1536;unit=KiB".to_owned()), size.to_structured_field(Unit::KibiByte));
+    ///
+    /// assert_eq!(None, ByteSize::pb(1).to_structured_field(Unit::Byte));
+    /// assert_eq!(Some("1;unit=PB".to_owned()), ByteSize::pb(1).to_structured_field(Unit::PetaByte));
+    /// ```
+    #[doc(alias("rfc8941", "sfv"))]
+    pub fn to_structured_field(&self, unit: Unit) -> Option<String> {
+        let bytes = self.0;
+
+        if unit != Unit::Byte && bytes % unit.factor() == 0 {
+            let count = bytes / unit.factor();
+            (count <= MAX_INTEGER).then(|| format!("{count};unit={}", token_of(unit)))
+        } else {
+            (bytes <= MAX_INTEGER).then(|| format!("{bytes}"))
+        }
+    }
+
+    /// Parses an HTTP structured field item (RFC 8941), e.g., `1536` or `12;unit=MiB`.
+    ///
+    /// The item must be a non-negative integer. An optional `unit` parameter, which must be a
+    /// token with one of the unit symbols written by
+    /// [`to_structured_field()`](Self::to_structured_field), scales the count.
+    /// Other parameters are checked for valid syntax and otherwise ignored, as required by the
+    /// RFC. Unlike [`FromStr`](str::FromStr), parsing is strict: unit symbols are case-sensitive
+    /// and no other whitespace is allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not a valid integer item, if the `unit` parameter is not a
+    /// known unit, or if the size overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytesize::ByteSize;
+    ///
+    /// assert_eq!(Ok(ByteSize::b(1536)), ByteSize::from_structured_field("1536"));
+    /// assert_eq!(Ok(ByteSize::mib(12)), ByteSize::from_structured_field("12;unit=MiB"));
+    ///
+    /// assert!(ByteSize::from_structured_field("12 MiB").is_err());
+    /// assert!(ByteSize::from_structured_field("12;unit=mib").is_err());
+    /// ```
+    pub fn from_structured_field(value: &str) -> Result<ByteSize, String> {
+        // leading and trailing spaces are discarded when parsing a field
+        let mut input = value.trim_matches(' ').as_bytes();
+
+        let count = parse_count(&mut input)
+            .map_err(|err| format!("couldn't parse {value:?} as a structured field, {err}"))?;
+
+        let mut unit = Unit::Byte;
+
+        while let [b';', rest @ ..] = input {
+            input = trim_spaces(rest);
+
+            let key = parse_key(&mut input)
+                .map_err(|err| format!("couldn't parse {value:?} as a structured field, {err}"))?;
+
+            let token = match input {
+                [b'=', rest @ ..] => {
+                    input = rest;
+                    parse_bare_item(&mut input).map_err(|err| {
+                        format!("couldn't parse {value:?} as a structured field, {err}")
+                    })?
+                }
+                // a parameter without a value is boolean true
+                _ => None,
+            };
+
+            if key == "unit" {
+                unit = token
+                    .and_then(|token| {
+                        UNIT_TOKENS
+                            .iter()
+                            .find(|(_, candidate)| *candidate == token)
+                    })
+                    .map(|(unit, _)| *unit)
+                    .ok_or_else(|| {
+                        format!("unknown unit parameter in structured field {value:?}")
+                    })?;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(format!(
+                "couldn't parse {value:?} as a structured field, unexpected trailing characters"
+            ));
+        }
+
+        count
+            .checked_mul(unit.factor())
+            .map(ByteSize)
+            .ok_or_else(|| format!("structured field {value:?} overflows a byte size"))
+    }
+}
+
+fn trim_spaces(input: &[u8]) -> &[u8] {
+    let start = input.iter().position(|&b| b != b' ').unwrap_or(input.len());
+    &input[start..]
+}
+
+/// Splits off the longest prefix of `input` whose bytes match `pred`.
+fn take_while<'a>(input: &mut &'a [u8], pred: impl Fn(u8) -> bool) -> &'a [u8] {
+    let len = input.iter().position(|&b| !pred(b)).unwrap_or(input.len());
+    let (taken, rest) = input.split_at(len);
+    *input = rest;
+    taken
+}
+
+fn parse_count(input: &mut &[u8]) -> Result<u64, &'static str> {
+    if input.starts_with(b"-") {
+        return Err("sizes can't be negative");
+    }
+
+    let digits = take_while(input, |b| b.is_ascii_digit());
+
+    if input.starts_with(b".") {
+        return Err("expected an integer but found a decimal");
+    }
+
+    match digits.len() {
+        0 => Err("expected an integer"),
+        1..=15 => Ok(digits
+            .iter()
+            .fold(0, |count, digit| count * 10 + u64::from(digit - b'0'))),
+        _ => Err("integers are limited to 15 digits"),
+    }
+}
+
+fn parse_key<'a>(input: &mut &'a [u8]) -> Result<&'a str, &'static str> {
+    match input.first() {
+        Some(b'a'..=b'z' | b'*') => {}
+        _ => return Err("expected a parameter key"),
+    }
+
+    let key = take_while(
+        input,
+        |b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*'),
+    );
+
+    // keys only contain ASCII characters
+    Ok(str::from_utf8(key).unwrap_or_default())
+}
+
+/// Parses a bare item, returning its value only if it is a token.
+fn parse_bare_item<'a>(input: &mut &'a [u8]) -> Result<Option<&'a str>, &'static str> {
+    match input.first() {
+        Some(b'-' | b'0'..=b'9') => parse_number(input).map(|()| None),
+        Some(b'"') => parse_string(input).map(|()| None),
+        Some(b':') => parse_byte_sequence(input).map(|()| None),
+        Some(b'?') => match input {
+            [b'?', b'0' | b'1', rest @ ..] => {
+                *input = rest;
+                Ok(None)
+            }
+            _ => Err("invalid boolean"),
+        },
+        Some(b'a'..=b'z' | b'A'..=b'Z' | b'*') => {
+            let token = take_while(input, |b| {
+                b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~:/".contains(&b)
+            });
+
+            // tokens only contain ASCII characters
+            Ok(Some(str::from_utf8(token).unwrap_or_default()))
+        }
+        _ => Err("expected a parameter value"),
+    }
+}
+
+fn parse_number(input: &mut &[u8]) -> Result<(), &'static str> {
+    if let [b'-', rest @ ..] = input {
+        *input = rest;
+    }
+
+    let integer = take_while(input, |b| b.is_ascii_digit()).len();
+
+    let [b'.', rest @ ..] = input else {
+        return match integer {
+            1..=15 => Ok(()),
+            _ => Err("invalid integer"),
+        };
+    };
+
+    *input = rest;
+    let fraction = take_while(input, |b| b.is_ascii_digit()).len();
+
+    match (integer, fraction) {
+        (1..=12, 1..=3) => Ok(()),
+        _ => Err("invalid decimal"),
+    }
+}
+
+fn parse_string(input: &mut &[u8]) -> Result<(), &'static str> {
+    let mut rest = &input[1..];
+
+    loop {
+        match rest {
+            [b'"', tail @ ..] => {
+                *input = tail;
+                return Ok(());
+            }
+            [b'\\', b'"' | b'\\', tail @ ..] => rest = tail,
+            [b' '..=b'~', tail @ ..] if rest[0] != b'\\' => rest = tail,
+            _ => return Err("invalid string"),
+        }
+    }
+}
+
+fn parse_byte_sequence(input: &mut &[u8]) -> Result<(), &'static str> {
+    let mut rest = &input[1..];
+    take_while(&mut rest, |b| {
+        b.is_ascii_alphanumeric() || b"+/=".contains(&b)
+    });
+
+    match rest {
+        [b':', tail @ ..] => {
+            *input = tail;
+            Ok(())
+        }
+        _ => Err("invalid byte sequence"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        #[track_caller]
+        fn assert_item(expected: &str, size: ByteSize, unit: Unit) {
+            assert_eq!(Some(expected), size.to_structured_field(unit).as_deref());
+            assert_eq!(Ok(size), ByteSize::from_structured_field(expected));
+        }
+
+        assert_item("0", ByteSize(0), Unit::Byte);
+        assert_item("1536", ByteSize(1536), Unit::Byte);
+        assert_item("3;unit=kB", ByteSize::kb(3), Unit::KiloByte);
+        assert_item("12;unit=MiB", ByteSize::mib(12), Unit::MebiByte);
+        assert_item("15;unit=EiB", ByteSize::eib(15), Unit::ExbiByte);
+        assert_item("999999999999999", ByteSize(MAX_INTEGER), Unit::Byte);
+        assert_item("1;unit=PB", ByteSize::pb(1), Unit::PetaByte);
+
+        // sizes that aren't a whole number of the unit fall back to bytes
+        assert_item("1025", ByteSize(1025), Unit::KibiByte);
+
+        // counts over 15 digits can't be represented
+        assert_eq!(
+            None,
+            ByteSize(MAX_INTEGER + 1).to_structured_field(Unit::Byte)
+        );
+        assert_eq!(None, ByteSize::pb(1).to_structured_field(Unit::Byte));
+        assert_eq!(None, ByteSize(u64::MAX).to_structured_field(Unit::KibiByte));
+        assert_eq!(
+            Some("1000000000000;unit=kB"),
+            ByteSize(MAX_INTEGER + 1)
+                .to_structured_field(Unit::KiloByte)
+                .as_deref()
+        );
+        assert_eq!(
+            None,
+            ByteSize(MAX_INTEGER + 2).to_structured_field(Unit::KiloByte)
+        );
+        assert_eq!(
+            None,
+            ByteSize::kib(MAX_INTEGER + 1).to_structured_field(Unit::KibiByte)
+        );
+    }
+
+    #[test]
+    fn parse() {
+        #[track_caller]
+        fn assert_parse(expected: ByteSize, value: &str) {
+            assert_eq!(Ok(expected), ByteSize::from_structured_field(value));
+        }
+
+        assert_parse(ByteSize(42), "42");
+        assert_parse(ByteSize(42), "  42 ");
+        assert_parse(ByteSize(0), "000");
+        assert_parse(ByteSize::kib(4), "4;unit=KiB");
+        assert_parse(ByteSize::kib(4), "4; unit=KiB");
+        assert_parse(ByteSize::kib(4), "4;unit=MiB;unit=KiB");
+        assert_parse(ByteSize::kb(4), "4;a;unit=kB;b=?0");
+        assert_parse(
+            ByteSize::mb(4),
+            r#"4;x="a \"b\" \\ c";y=:aGk=:;z=-1.5;unit=MB;w=*tok/en:1"#,
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for value in [
+            "",
+            " ",
+            "-1",
+            "1.5",
+            "1000000000000000",
+            "+1",
+            "1\t",
+            "1,2",
+            "1;",
+            "1;Unit=KiB",
+            "1;unit=kib",
+            "1;unit=XB",
+            "1;unit=\"KiB\"",
+            "1;unit",
+            "1;unit=",
+            "1;x=\"unterminated",
+            "1;x=\"\\n\"",
+            "1;x=:aGk=",
+            "1;x=?2",
+            "1;x=1.2345",
+            "1;x=1234567890123.5",
+            "1;x=1234567890123456",
+            "1;x=-",
+            "1 ;unit=KiB",
+            "1;unit=KiB, 2",
+            "18;unit=EiB",
+            "KiB",
+        ] {
+            assert!(
+                ByteSize::from_structured_field(value).is_err(),
+                "value: {value:?}",
+            );
+        }
+
+        assert!(ByteSize::from_structured_field("-1")
+            .unwrap_err()
+            .contains("negative"));
+        assert!(ByteSize::from_structured_field("18;unit=EiB")
+            .unwrap_err()
+            .contains("overflows"));
+    }
+}
@@ -0,0 +1,368 @@
+//! Counting adapters for Tokio's asynchronous I/O traits.
+//!
+//! [`CountingReader`] and [`CountingWriter`] mirror the synchronous
+//! [`CountingReader`](crate::CountingReader) and [`CountingWriter`](crate::CountingWriter), but
+//! count into a [`ByteCounter`], which can be cloned and shared with other tasks, e.g., to report
+//! the progress of uploads handled by a server.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytesize::{
+//!     tokio::{ByteCounter, CountingWriter},
+//!     ByteSize,
+//! };
+//! use tokio::io::AsyncWriteExt as _;
+//!
+//! # futures::executor::block_on(async {
+//! let uploaded = ByteCounter::new();
+//!
+//! let mut first = CountingWriter::with_counter(tokio::io::sink(), uploaded.clone());
+//! let mut second = CountingWriter::with_counter(tokio::io::sink(), uploaded.clone());
+//! first.write_all(&[0; 1024]).await.unwrap();
+//! second.write_all(&[0; 512]).await.unwrap();
+//!
+//! assert_eq!(ByteSize::b(1536), uploaded.get());
+//! assert_eq!(ByteSize::b(1536), first.count());
+//! # });
+//! ```
+
+use alloc::sync::Arc;
+use core::{
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{ready, Context, Poll},
+};
+use std::io::{self, IoSlice};
+
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, ReadBuf};
+
+#[cfg(feature = "unstable")]
+use crate::ThroughputMeter;
+use crate::{io::adapter_accessors, ByteSize};
+
+/// Atomic count of bytes, shared by its clones.
+///
+/// Counts saturate at `u64::MAX` bytes.
+#[derive(Debug, Clone, Default)]
+pub struct ByteCounter(Arc<AtomicU64>);
+
+impl ByteCounter {
+    /// Constructs a counter at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes counted.
+    pub fn get(&self) -> ByteSize {
+        ByteSize(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Adds `size` to the count.
+    pub fn add(&self, size: ByteSize) {
+        // `fetch_add` would wrap around
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                Some(count.saturating_add(size.0))
+            });
+    }
+
+    /// Resets the count to zero, returning the number of bytes counted until then.
+    pub fn reset(&self) -> ByteSize {
+        ByteSize(self.0.swap(0, Ordering::Relaxed))
+    }
+}
+
+/// Meter of an adapter's transfer rate, which is only available with the `unstable` feature.
+#[cfg(feature = "unstable")]
+type Meter = Option<ThroughputMeter>;
+#[cfg(not(feature = "unstable"))]
+type Meter = ();
+
+/// Records `bytes` transferred through an adapter.
+#[cfg_attr(not(feature = "unstable"), allow(unused_variables))]
+fn record(counter: &ByteCounter, meter: &mut Meter, bytes: usize) {
+    let size = ByteSize(bytes as u64);
+    counter.add(size);
+
+    #[cfg(feature = "unstable")]
+    if let Some(meter) = meter {
+        meter.record(size);
+    }
+}
+
+macro_rules! counting_adapter {
+    ($name:ident, $inner:ident, $bytes:literal) => {
+        impl<$inner> $name<$inner> {
+            #[doc = concat!("Wraps `inner`, counting the bytes ", $bytes, " it into a new counter.")]
+            pub fn new(inner: $inner) -> Self {
+                Self::with_counter(inner, ByteCounter::new())
+            }
+
+            #[doc = concat!("Wraps `inner`, counting the bytes ", $bytes, " it into `counter`.")]
+            pub fn with_counter(inner: $inner, counter: ByteCounter) -> Self {
+                Self {
+                    inner,
+                    counter,
+                    meter: Meter::default(),
+                }
+            }
+
+            adapter_accessors!($inner, meter);
+
+            /// Returns the number of bytes in the counter.
+            ///
+            /// This includes bytes counted by other adapters sharing the counter.
+            pub fn count(&self) -> ByteSize {
+                self.counter.get()
+            }
+
+            /// Returns the counter, which can be cloned to share it with other tasks.
+            pub const fn counter(&self) -> &ByteCounter {
+                &self.counter
+            }
+
+            /// Returns a pinned mutable reference to the wrapped value.
+            ///
+            /// Bytes transferred directly through it are not counted.
+            pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut $inner> {
+                self.project().inner
+            }
+        }
+    };
+}
+
+pin_project! {
+    /// Asynchronous reader adapter counting the bytes read through it.
+    ///
+    /// Buffered reads through [`AsyncBufRead`] are counted when they're consumed.
+    #[derive(Debug)]
+    pub struct CountingReader<R> {
+        #[pin]
+        inner: R,
+        counter: ByteCounter,
+        meter: Meter,
+    }
+}
+
+counting_adapter!(CountingReader, R, "read through");
+
+impl<R: AsyncRead> AsyncRead for CountingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        let filled = buf.filled().len();
+
+        ready!(this.inner.poll_read(cx, buf))?;
+        record(this.counter, this.meter, buf.filled().len() - filled);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncBufRead> AsyncBufRead for CountingReader<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+        this.inner.consume(amt);
+        record(this.counter, this.meter, amt);
+    }
+}
+
+pin_project! {
+    /// Asynchronous writer adapter counting the bytes written through it.
+    #[derive(Debug)]
+    pub struct CountingWriter<W> {
+        #[pin]
+        inner: W,
+        counter: ByteCounter,
+        meter: Meter,
+    }
+}
+
+counting_adapter!(CountingWriter, W, "written through");
+
+impl<W: AsyncWrite> AsyncWrite for CountingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+
+        let written = ready!(this.inner.poll_write(cx, buf))?;
+        record(this.counter, this.meter, written);
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+
+        let written = ready!(this.inner.poll_write_vectored(cx, bufs))?;
+        record(this.counter, this.meter, written);
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+    use std::thread;
+
+    use futures::executor::block_on;
+    use tokio::io::{AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _};
+
+    use super::*;
+
+    #[test]
+    fn counter() {
+        let counter = ByteCounter::new();
+        let shared = counter.clone();
+
+        counter.add(ByteSize::kib(1));
+        shared.add(ByteSize::kib(2));
+        assert_eq!(ByteSize::kib(3), counter.get());
+
+        assert_eq!(ByteSize::kib(3), shared.reset());
+        assert_eq!(ByteSize(0), counter.get());
+
+        counter.add(ByteSize(u64::MAX));
+        counter.add(ByteSize(1));
+        assert_eq!(ByteSize(u64::MAX), shared.get());
+    }
+
+    #[test]
+    fn counter_across_threads() {
+        let counter = ByteCounter::new();
+
+        let threads = (0..4)
+            .map(|_| {
+                let counter = counter.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        counter.add(ByteSize(3));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(ByteSize(12_000), counter.get());
+    }
+
+    #[test]
+    fn reader() {
+        block_on(async {
+            let data = vec![7_u8; 10_000];
+            let mut reader = CountingReader::new(&data[..]);
+
+            let mut buf = [0; 4096];
+            assert_eq!(4096, reader.read(&mut buf).await.unwrap());
+            assert_eq!(ByteSize(4096), reader.count());
+
+            let mut rest = Vec::new();
+            reader.read_to_end(&mut rest).await.unwrap();
+            assert_eq!(ByteSize(10_000), reader.count());
+            #[cfg(feature = "unstable")]
+            assert_eq!(None, reader.rate());
+            assert!(reader.into_inner().is_empty());
+        });
+    }
+
+    #[test]
+    fn buf_reader() {
+        block_on(async {
+            let mut reader = CountingReader::new(&b"one\ntwo\nthree\n"[..]);
+
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            assert_eq!(ByteSize(4), reader.count());
+
+            assert_eq!(b"two\nthree\n", reader.fill_buf().await.unwrap());
+            assert_eq!(ByteSize(4), reader.count());
+
+            Pin::new(&mut reader).consume(4);
+            assert_eq!(ByteSize(8), reader.count());
+            assert_eq!(b"three\n", *reader.get_ref());
+        });
+    }
+
+    #[test]
+    fn writer() {
+        block_on(async {
+            let counter = ByteCounter::new();
+            let mut writer = CountingWriter::with_counter(Vec::new(), counter.clone());
+
+            writer.write_all(b"hello, ").await.unwrap();
+            let written = writer
+                .write_vectored(&[IoSlice::new(b"a"), IoSlice::new(b"bc")])
+                .await
+                .unwrap();
+            assert_eq!(3, written);
+            writer.shutdown().await.unwrap();
+
+            assert_eq!(ByteSize(10), writer.count());
+            assert_eq!(ByteSize(10), counter.get());
+
+            // writes bypassing the adapter aren't counted
+            writer.get_mut().push(b'!');
+            assert_eq!(ByteSize(10), writer.count());
+            assert_eq!(b"hello, abc!", &writer.into_inner()[..]);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn meter() {
+        block_on(async {
+            let mut writer =
+                CountingWriter::new(tokio::io::sink()).with_meter(ThroughputMeter::new());
+            writer.write_all(&[0; 1024]).await.unwrap();
+            writer.write_all(&[0; 1024]).await.unwrap();
+
+            assert_eq!(ByteSize::kib(2), writer.meter().unwrap().total());
+            assert!(writer.rate().is_some());
+        });
+    }
+
+    #[test]
+    fn shared_counter() {
+        block_on(async {
+            let mut reader = CountingReader::new(&[0_u8; 100][..]);
+            let mut writer =
+                CountingWriter::with_counter(tokio::io::sink(), reader.counter().clone());
+
+            tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+            assert_eq!(ByteSize(200), reader.count());
+            assert_eq!(ByteSize(200), writer.count());
+        });
+    }
+}
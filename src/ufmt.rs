@@ -0,0 +1,73 @@
+use ufmt::{uDebug, uDisplay, uWrite, uwrite, Formatter};
+
+use crate::{stack_str::iec, ByteSize};
+
+/// Writes the size in IEC units, e.g., `1.5 KiB`, like [`ByteSize::display()`].
+///
+/// The size is formatted into a stack buffer, without allocating, and written as a `str`.
+#[cfg_attr(docsrs, doc(cfg(feature = "ufmt")))]
+impl uDisplay for ByteSize {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        match iec(*self) {
+            Some(buf) => f.write_str(buf.as_str()),
+            None => uwrite!(f, "{} B", self.0),
+        }
+    }
+}
+
+/// Writes the size in IEC units followed by the number of bytes, e.g., `1.5 KiB (1536 bytes)`,
+/// like `ByteSize`'s `Debug` impl.
+#[cfg_attr(docsrs, doc(cfg(feature = "ufmt")))]
+impl uDebug for ByteSize {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        uwrite!(f, "{} ({} bytes)", self, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use core::convert::Infallible;
+
+    use super::*;
+
+    /// Collects `ufmt` output, since `ufmt` only implements `uWrite` for `String` with `std`.
+    #[derive(Default)]
+    struct Output(String);
+
+    impl uWrite for Output {
+        type Error = Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn display() {
+        for (size, expected) in [
+            (ByteSize(0), "0 B"),
+            (ByteSize(1023), "1023 B"),
+            (ByteSize::b(1536), "1.5 KiB"),
+            (ByteSize(u64::MAX), "16.0 EiB"),
+        ] {
+            let mut out = Output::default();
+            uwrite!(out, "{}", size).unwrap();
+            assert_eq!(expected, out.0);
+        }
+    }
+
+    #[test]
+    fn debug() {
+        let mut out = Output::default();
+        uwrite!(out, "{:?}", ByteSize::b(1536)).unwrap();
+        assert_eq!("1.5 KiB (1536 bytes)", out.0);
+    }
+}
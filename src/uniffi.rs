@@ -0,0 +1,107 @@
+//! Bindings for Kotlin, Swift, and Python apps, using UniFFI.
+//!
+//! `ByteSize` is exported as a custom type backed by a `u64` number of bytes, so it can be used in
+//! the exported interfaces of other crates. The functions here are exported in the `bytesize`
+//! namespace, so apps can parse and format sizes exactly like the Rust core:
+//!
+//! ```kotlin
+//! val size = parseSize("1.5 GiB")  // 1610612736UL
+//! formatSize(size, null)           // "1.5 GiB"
+//! formatSize(size, "si short")     // "1.6G"
+//! ```
+
+use alloc::string::{String, ToString as _};
+use core::fmt;
+
+use crate::{ByteSize, UnitPreference};
+
+::uniffi::custom_newtype!(ByteSize, u64);
+
+/// Error for invalid sizes or styles.
+#[derive(Debug, Clone, PartialEq, Eq, ::uniffi::Error)]
+pub enum Error {
+    /// The input couldn't be parsed.
+    Invalid {
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { message } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::Invalid { message }
+    }
+}
+
+/// Parses a size, e.g., `"1.5 GiB"`.
+///
+/// Same as `ByteSize`'s `FromStr` impl.
+#[::uniffi::export]
+pub fn parse_size(input: &str) -> Result<ByteSize, Error> {
+    Ok(input.parse()?)
+}
+
+/// Formats a size, e.g., `"1.5 GiB"`.
+///
+/// The `style` is a [`UnitPreference`] string, e.g., `"si"`, `"iec short"`, or `"MiB"`. Without
+/// one, IEC units are used.
+#[::uniffi::export]
+pub fn format_size(size: ByteSize, style: Option<String>) -> Result<String, Error> {
+    let display = size.display().iec();
+
+    let display = match style {
+        Some(style) => display.with_preference(style.parse::<UnitPreference>()?),
+        None => display,
+    };
+
+    Ok(display.to_string())
+}
+
+/// Formats a size exactly, in the largest unit that divides it, e.g., `"1536 KiB"`.
+///
+/// See [`ByteSize::to_string_exact()`].
+#[::uniffi::export]
+pub fn format_size_exact(size: ByteSize) -> String {
+    size.to_string_exact()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UniFfiTag;
+
+    #[test]
+    fn functions() {
+        assert_eq!(Ok(ByteSize::mib(1536)), parse_size("1.5 GiB"));
+        assert!(parse_size("1.5 parsecs").is_err());
+
+        assert_eq!(Ok("1.5 GiB".into()), format_size(ByteSize::mib(1536), None));
+        assert_eq!(
+            Ok("1.6G".into()),
+            format_size(ByteSize::mib(1536), Some("si short".into()))
+        );
+        assert!(format_size(ByteSize::mib(1536), Some("metric".into())).is_err());
+
+        assert_eq!("1536 KiB", format_size_exact(ByteSize::kib(1536)));
+    }
+
+    #[test]
+    fn lowered_as_u64() {
+        let lowered = <ByteSize as ::uniffi::Lower<UniFfiTag>>::lower(ByteSize::kib(1));
+        assert_eq!(1024_u64, lowered);
+        assert_eq!(
+            ByteSize::kib(1),
+            <ByteSize as ::uniffi::Lift<UniFfiTag>>::try_lift(lowered).unwrap()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::borrow::Cow;
+
+use utoipa::{
+    openapi::{
+        schema::{AnyOfBuilder, KnownFormat, ObjectBuilder, Schema, SchemaFormat, Type},
+        RefOr,
+    },
+    PartialSchema, ToSchema,
+};
+
+use crate::{parse::SIZE_PATTERN, ByteSize};
+
+/// Describes the human-readable serde representation: a non-negative integer number of bytes or a
+/// string like `"1.5 GiB"`.
+#[cfg_attr(docsrs, doc(cfg(feature = "utoipa")))]
+impl PartialSchema for ByteSize {
+    fn schema() -> RefOr<Schema> {
+        AnyOfBuilder::new()
+            .description(Some(
+                "A size in bytes, as an integer number of bytes or a string with a unit, e.g., \
+                 \"1.5 GiB\".",
+            ))
+            .item(
+                ObjectBuilder::new()
+                    .schema_type(Type::Integer)
+                    .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int64)))
+                    .minimum(Some(0)),
+            )
+            .item(
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .pattern(Some(SIZE_PATTERN)),
+            )
+            .examples(["1.5 KiB", "2 GB"])
+            .into()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "utoipa")))]
+impl ToSchema for ByteSize {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("ByteSize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema() {
+        let schema = serde_json::to_value(ByteSize::schema()).unwrap();
+
+        let any_of = schema["anyOf"].as_array().unwrap();
+        assert_eq!("integer", any_of[0]["type"]);
+        assert_eq!("int64", any_of[0]["format"]);
+        assert_eq!(0, any_of[0]["minimum"]);
+        assert_eq!("string", any_of[1]["type"]);
+        assert_eq!(SIZE_PATTERN, any_of[1]["pattern"]);
+
+        assert_eq!("ByteSize", ByteSize::name());
+    }
+
+    #[test]
+    fn derived_schema() {
+        #[derive(ToSchema)]
+        #[allow(dead_code)]
+        struct Upload {
+            limit: ByteSize,
+        }
+
+        let mut schemas = alloc::vec::Vec::new();
+        Upload::schemas(&mut schemas);
+        assert!(schemas.iter().any(|(name, _)| name == "ByteSize"));
+
+        let upload = serde_json::to_value(Upload::schema()).unwrap();
+        assert_eq!(
+            "#/components/schemas/ByteSize",
+            upload["properties"]["limit"]["$ref"]
+        );
+    }
+}
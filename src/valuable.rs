@@ -0,0 +1,100 @@
+use alloc::string::ToString as _;
+
+use valuable::{Fields, NamedField, NamedValues, StructDef, Structable, Valuable, Value, Visit};
+
+use crate::ByteSize;
+
+/// Fields of the structured representation.
+static FIELDS: &[NamedField<'static>] = &[NamedField::new("bytes"), NamedField::new("human")];
+
+/// Inspected as a `ByteSize` struct with the number of `bytes` and a `human` string formatted like
+/// `ByteSize`'s `Display` impl, e.g., `{ bytes: 1536, human: "1.5 KiB" }`.
+///
+/// Structured logs can then be filtered on the exact size and still read by people, e.g., with
+/// `tracing::field::valuable()`.
+#[cfg_attr(docsrs, doc(cfg(feature = "valuable")))]
+impl Valuable for ByteSize {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let human = self.to_string();
+
+        visit.visit_named_fields(&NamedValues::new(
+            FIELDS,
+            &[Value::U64(self.0), Value::String(&human)],
+        ));
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "valuable")))]
+impl Structable for ByteSize {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("ByteSize", Fields::Named(FIELDS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use super::*;
+
+    /// Collects named fields as `(name, value)` strings.
+    #[derive(Default)]
+    struct Collect(Vec<(String, String)>);
+
+    impl Visit for Collect {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if let Value::Structable(value) = value {
+                value.visit(self);
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            for (field, value) in named_values {
+                let value = match value {
+                    Value::U64(bytes) => bytes.to_string(),
+                    Value::String(string) => string.to_string(),
+                    value => panic!("unexpected value: {value:?}"),
+                };
+                self.0.push((field.name().to_string(), value));
+            }
+        }
+    }
+
+    #[test]
+    fn fields() {
+        let mut collect = Collect::default();
+        valuable::visit(&ByteSize::kib(1536).as_value(), &mut collect);
+
+        assert_eq!(
+            [("bytes", "1572864"), ("human", "1.5 MiB")]
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+                .to_vec(),
+            collect.0,
+        );
+    }
+
+    #[test]
+    fn definition() {
+        let size = ByteSize::b(0);
+        let Value::Structable(structable) = size.as_value() else {
+            panic!("not a structable");
+        };
+
+        let definition = structable.definition();
+        assert_eq!("ByteSize", definition.name());
+        assert!(definition.is_static());
+
+        let Fields::Named(fields) = definition.fields() else {
+            panic!("not named fields");
+        };
+        let names = fields.iter().map(NamedField::name).collect::<Vec<_>>();
+        assert_eq!(["bytes", "human"], *names);
+    }
+}
@@ -0,0 +1,96 @@
+//! JavaScript bindings with `wasm-bindgen`.
+//!
+//! Exposes parsing and formatting to JavaScript, so a web frontend shows sizes exactly like a Rust
+//! backend. Sizes are passed as `bigint` numbers of bytes, and invalid input throws an `Error` with
+//! the same message as in Rust.
+//!
+//! ```js
+//! import { parse, format, fromUnit } from "./pkg/bytesize.js";
+//!
+//! parse("1.5 KiB");          // 1536n
+//! fromUnit(1.5, "KiB");      // 1536n
+//! format(1536n);             // "1.5 KiB"
+//! format(1536n, "si short"); // "1.5k"
+//! formatExact(1536n);        // "3 KiB"
+//! ```
+
+use alloc::{format, string::String};
+
+use wasm_bindgen::{prelude::wasm_bindgen, JsError};
+
+use crate::{ByteSize, UnitPreference};
+
+/// Parses a size, e.g., `"1.5 KiB"`, into a number of bytes.
+///
+/// Same as `ByteSize`'s `FromStr` impl.
+#[wasm_bindgen]
+pub fn parse(input: &str) -> Result<u64, JsError> {
+    input
+        .parse::<ByteSize>()
+        .map(|size| size.0)
+        .map_err(js_error)
+}
+
+/// Returns the number of bytes in `value` units, e.g., `fromUnit(1.5, "KiB")` is 1536.
+///
+/// Same as parsing `value` followed by `unit`, so fractions of a byte are rejected.
+#[wasm_bindgen(js_name = fromUnit)]
+pub fn from_unit(value: f64, unit: &str) -> Result<u64, JsError> {
+    parse(&format!("{value} {unit}"))
+}
+
+/// Formats a number of bytes, e.g., `"1.5 KiB"`.
+///
+/// The `style` is a [`UnitPreference`] string, e.g., `"si"`, `"iec short"`, or `"MiB"`. Without
+/// one, IEC units are used.
+#[wasm_bindgen]
+pub fn format(bytes: u64, style: Option<String>) -> Result<String, JsError> {
+    format_with(bytes, style.as_deref()).map_err(js_error)
+}
+
+/// Formats a number of bytes exactly, in the largest unit that divides it, e.g., `"3 KiB"`.
+///
+/// See [`ByteSize::to_string_exact()`].
+#[wasm_bindgen(js_name = formatExact)]
+pub fn format_exact(bytes: u64) -> String {
+    ByteSize(bytes).to_string_exact()
+}
+
+fn format_with(bytes: u64, style: Option<&str>) -> Result<String, String> {
+    let display = ByteSize(bytes).display().iec();
+
+    let display = match style {
+        Some(style) => display.with_preference(style.parse::<UnitPreference>()?),
+        None => display,
+    };
+
+    Ok(display.to_string())
+}
+
+fn js_error(err: String) -> JsError {
+    JsError::new(&err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings() {
+        assert_eq!(1536, parse("1.5 KiB").unwrap());
+        assert_eq!(1536, from_unit(1.5, "KiB").unwrap());
+        assert_eq!(1_500_000, from_unit(1.5, "MB").unwrap());
+        assert_eq!("1.5 KiB", format(1536, None).unwrap());
+        assert_eq!("3 KiB", format_exact(3072));
+    }
+
+    // errors can't be constructed outside of a JavaScript runtime
+    #[test]
+    fn styles() {
+        assert_eq!(Ok("1.5 KiB".into()), format_with(1536, None));
+        assert_eq!(Ok("1.5 KiB".into()), format_with(1536, Some("auto")));
+        assert_eq!(Ok("1.5k".into()), format_with(1536, Some("si short")));
+        assert_eq!(Ok("3072.0 KiB".into()), format_with(3 << 20, Some("KiB")));
+        assert!(format_with(1536, Some("metric")).is_err());
+    }
+}
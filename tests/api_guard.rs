@@ -0,0 +1,912 @@
+//! Public API contract.
+//!
+//! Every stable item is named here with its full signature, so removing or changing one fails to
+//! compile. Behavior that downstream code is known to rely on, like how unit symbols are parsed,
+//! is pinned with plain assertions. Changing this file is a breaking change and needs a major
+//! version bump, unless the item is gated behind the `unstable` feature.
+//!
+//! Trait impls and items added without being named here are caught by the `public_api` snapshot.
+
+#[cfg(feature = "unstable")]
+use core::time::Duration;
+use core::{fmt, hash::Hash, iter, ops, str::FromStr};
+
+#[cfg(feature = "unstable")]
+use bytesize::{ByteRate, Eta};
+use bytesize::{
+    ByteSize, Display, ExcessPrecision, Exponent, Format, FormatOptions, OutOfRangeError,
+    ParseOptions, Rounding, Separator, SubUnitBits, Unit, UnitLabels, UnitParseError,
+    UnitPreference,
+};
+
+fn assert_traits_clone<T: Clone + fmt::Debug + Default + Send + Sync>() {}
+
+fn assert_traits<T>()
+where
+    T: Copy + Clone + fmt::Debug + PartialEq + Eq + Send + Sync + 'static,
+{
+}
+
+#[test]
+fn constants() {
+    let _: [u64; 12] = [
+        bytesize::KB,
+        bytesize::MB,
+        bytesize::GB,
+        bytesize::TB,
+        bytesize::PB,
+        bytesize::EB,
+        bytesize::KIB,
+        bytesize::MIB,
+        bytesize::GIB,
+        bytesize::TIB,
+        bytesize::PIB,
+        bytesize::EIB,
+    ];
+
+    let _: [fn(u32) -> u64; 12] = [
+        bytesize::kb,
+        bytesize::kib,
+        bytesize::mb,
+        bytesize::mib,
+        bytesize::gb,
+        bytesize::gib,
+        bytesize::tb,
+        bytesize::tib,
+        bytesize::pb,
+        bytesize::pib,
+        bytesize::eb,
+        bytesize::eib,
+    ];
+}
+
+#[test]
+fn functions() {
+    use std::collections::BTreeMap;
+
+    let sizes = BTreeMap::<String, ByteSize>::new();
+    let _: Result<String, fmt::Error> = bytesize::to_canonical_json(&sizes, ByteSize::advertised);
+
+    let _: fn(Format) = bytesize::set_default_format;
+    let _: fn() -> Format = bytesize::default_format;
+    let _: fn(u32) -> Display = bytesize::display_of;
+    let _: fn(&[u8]) -> Display = bytesize::display_size_of_val;
+}
+
+#[test]
+fn write_to() {
+    let mut out = String::new();
+    let _: fmt::Result = ByteSize(0).write_to(&mut out);
+    let _: fmt::Result = ByteSize(0).display().write_to(&mut out);
+}
+
+#[test]
+fn byte_size() {
+    fn assert_byte_size_traits<T>()
+    where
+        T: Default
+            + Hash
+            + Ord
+            + fmt::Display
+            + FromStr<Err = String>
+            + ops::Add<T, Output = T>
+            + ops::Add<u64, Output = T>
+            + ops::AddAssign<T>
+            + ops::AddAssign<u64>
+            + ops::Sub<T, Output = T>
+            + ops::Sub<u64, Output = T>
+            + ops::SubAssign<T>
+            + ops::SubAssign<u64>
+            + ops::Mul<u64, Output = T>
+            + ops::MulAssign<u64>
+            + iter::Sum<T>
+            + for<'a> iter::Sum<&'a T>,
+        u64: ops::Mul<T, Output = T>,
+        f32: ops::Mul<T, Output = T>,
+        f64: ops::Mul<T, Output = T>,
+    {
+    }
+
+    assert_traits::<ByteSize>();
+    assert_byte_size_traits::<ByteSize>();
+
+    let _: ByteSize = ByteSize(0);
+    let _: u64 = ByteSize(0).0;
+
+    let _: [fn(u64) -> ByteSize; 13] = [
+        ByteSize::b,
+        ByteSize::kb,
+        ByteSize::kib,
+        ByteSize::mb,
+        ByteSize::mib,
+        ByteSize::gb,
+        ByteSize::gib,
+        ByteSize::tb,
+        ByteSize::tib,
+        ByteSize::pb,
+        ByteSize::pib,
+        ByteSize::eb,
+        ByteSize::eib,
+    ];
+
+    let _: fn(&ByteSize) -> u64 = ByteSize::as_u64;
+    let _: [fn(ByteSize) -> [u8; 8]; 2] = [ByteSize::to_le_bytes, ByteSize::to_be_bytes];
+    let _: [fn([u8; 8]) -> ByteSize; 2] = [ByteSize::from_le_bytes, ByteSize::from_be_bytes];
+    let _: fn(&ByteSize, Unit) -> Option<String> = ByteSize::to_structured_field;
+    let _: fn(&str) -> Result<ByteSize, String> = ByteSize::from_structured_field;
+    let _: [fn(&ByteSize) -> f64; 12] = [
+        ByteSize::as_kb,
+        ByteSize::as_kib,
+        ByteSize::as_mb,
+        ByteSize::as_mib,
+        ByteSize::as_gb,
+        ByteSize::as_gib,
+        ByteSize::as_tb,
+        ByteSize::as_tib,
+        ByteSize::as_pb,
+        ByteSize::as_pib,
+        ByteSize::as_eb,
+        ByteSize::as_eib,
+    ];
+
+    let _: fn(ByteSize, f32) -> ByteSize = ByteSize::mul_f32;
+    let _: fn(ByteSize, f64) -> ByteSize = ByteSize::mul_f64;
+    let _: fn(ByteSize, &[u64]) -> Option<Vec<ByteSize>> = ByteSize::distribute_weighted;
+    let _: fn(ByteSize, ByteSize) -> ByteSize = ByteSize::gcd;
+    let _: fn(ByteSize, ByteSize) -> Option<ByteSize> = ByteSize::checked_lcm;
+    let _: fn(ByteSize, u32) -> Option<ByteSize> = ByteSize::checked_pow;
+    let _: fn(ByteSize, [u32; 2]) -> Option<ByteSize> = ByteSize::try_product;
+
+    let _: fn(&ByteSize) -> String = ByteSize::sortable_string;
+    let _: fn(&ByteSize) -> String = ByteSize::to_string_exact;
+
+    let _: [fn(u64) -> ByteSize; 2] = [ByteSize::advertised_gb, ByteSize::advertised_tb];
+    let _: [fn(f64) -> ByteSize; 2] = [ByteSize::os_reported_gb, ByteSize::os_reported_tb];
+
+    let _: [fn(&ByteSize) -> Display; 3] = [
+        ByteSize::display,
+        ByteSize::os_visible,
+        ByteSize::advertised,
+    ];
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn byte_rate() {
+    fn assert_byte_rate_traits<T>()
+    where
+        T: Default
+            + Hash
+            + Ord
+            + fmt::Display
+            + FromStr<Err = String>
+            + From<u64>
+            + Into<u64>
+            + ops::Add<T, Output = T>
+            + ops::AddAssign<T>
+            + ops::Sub<T, Output = T>
+            + ops::SubAssign<T>
+            + ops::Mul<u64, Output = T>
+            + ops::MulAssign<u64>
+            + ops::Div<u64, Output = T>
+            + ops::DivAssign<u64>
+            + ops::Mul<Duration, Output = ByteSize>
+            + iter::Sum<T>
+            + for<'a> iter::Sum<&'a T>,
+        Duration: ops::Mul<T, Output = ByteSize>,
+        ByteSize: ops::Div<Duration, Output = T> + ops::Div<T, Output = Duration>,
+    {
+    }
+
+    assert_traits::<ByteRate>();
+    assert_byte_rate_traits::<ByteRate>();
+
+    let _: ByteRate = ByteRate(0);
+    let _: u64 = ByteRate(0).0;
+
+    let _: [fn(u64) -> ByteRate; 18] = [
+        ByteRate::b,
+        ByteRate::kb,
+        ByteRate::kib,
+        ByteRate::mb,
+        ByteRate::mib,
+        ByteRate::gb,
+        ByteRate::gib,
+        ByteRate::tb,
+        ByteRate::tib,
+        ByteRate::pb,
+        ByteRate::pib,
+        ByteRate::eb,
+        ByteRate::eib,
+        ByteRate::bps,
+        ByteRate::kbps,
+        ByteRate::mbps,
+        ByteRate::gbps,
+        ByteRate::tbps,
+    ];
+
+    let _: fn(ByteSize, Duration) -> ByteRate = ByteRate::from_bytes_per;
+    let _: [fn(&ByteRate) -> u64; 2] = [ByteRate::as_u64, ByteRate::as_bps];
+    let _: fn(&ByteRate) -> ByteSize = ByteRate::per_second;
+    let _: [fn(ByteRate, ByteRate) -> Option<ByteRate>; 2] =
+        [ByteRate::checked_add, ByteRate::checked_sub];
+    let _: [fn(ByteRate, ByteRate) -> ByteRate; 2] =
+        [ByteRate::saturating_add, ByteRate::saturating_sub];
+    let _: [fn(ByteRate, u64) -> Option<ByteRate>; 2] =
+        [ByteRate::checked_mul, ByteRate::checked_div];
+    let _: fn(ByteRate, Duration) -> Option<ByteSize> = ByteRate::checked_mul_duration;
+    let _: fn(ByteSize, Duration) -> Option<ByteRate> = ByteSize::checked_div_duration;
+    let _: fn(ByteSize, ByteRate) -> Option<Duration> = ByteSize::checked_div_rate;
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn eta() {
+    fn assert_eta_traits<T>()
+    where
+        T: Default + Hash + Ord + fmt::Display + From<Duration> + From<Option<Duration>>,
+    {
+    }
+
+    assert_traits::<Eta>();
+    assert_eta_traits::<Eta>();
+
+    let _: Eta = Eta(None);
+    let _: Option<Duration> = Eta(None).0;
+
+    let _: fn(ByteSize, ByteRate) -> Option<Duration> = ByteSize::eta_at;
+    let _: fn(ByteSize, ByteRate) -> Eta = Eta::new;
+}
+
+#[test]
+fn display_builder() {
+    fn assert_display_traits<T: Clone + fmt::Debug + fmt::Display + Send + Sync>() {}
+
+    assert_display_traits::<Display>();
+
+    let _: [fn(Display) -> Display; 16] = [
+        Display::iec,
+        Display::iec_short,
+        Display::si,
+        Display::si_short,
+        Display::jedec,
+        Display::coreutils,
+        Display::windows_explorer,
+        Display::iec_bits,
+        Display::si_bits,
+        Display::iec_bits_short,
+        Display::si_bits_short,
+        Display::long_units,
+        Display::trim_zeros,
+        Display::auto_precision,
+        Display::remainder,
+        Display::mixed_units,
+    ];
+
+    let _: fn(Display, SubUnitBits) -> Display = Display::sub_unit_bits;
+    let _: fn(Display, Unit) -> Display = Display::in_unit;
+    let _: fn(Display, Separator) -> Display = Display::separator;
+    let _: fn(Display, Exponent) -> Display = Display::engineering;
+    let _: fn(Display, UnitPreference) -> Display = Display::with_preference;
+    let _: fn(Display, usize) -> Display = Display::significant_figures;
+    let _: fn(Display, usize) -> Display = Display::max_components;
+    let _: fn(Display, char) -> Display = Display::group_separator;
+    let _: fn(Display, &'static dyn UnitLabels) -> Display = Display::labels;
+    let _: fn(Display, char) -> Display = Display::decimal_separator;
+    let _: fn(Display, Rounding) -> Display = Display::rounding;
+    let _: fn(Display, ExcessPrecision) -> Display = Display::excess_precision;
+}
+
+#[test]
+fn display_options() {
+    assert_traits::<ExcessPrecision>();
+    assert_traits::<SubUnitBits>();
+    assert_traits::<Rounding>();
+    assert_traits::<Separator>();
+    assert_traits::<Exponent>();
+    assert_traits::<Format>();
+
+    let _: [ExcessPrecision; 3] = [
+        ExcessPrecision::Pad,
+        ExcessPrecision::Trim,
+        ExcessPrecision::Error,
+    ];
+    let _: [SubUnitBits; 4] = [
+        SubUnitBits::Bits,
+        SubUnitBits::Fraction,
+        SubUnitBits::Bytes,
+        SubUnitBits::Error,
+    ];
+    let _: [Rounding; 4] = [
+        Rounding::Floor,
+        Rounding::Ceil,
+        Rounding::HalfUp,
+        Rounding::HalfEven,
+    ];
+    let _: [Separator; 3] = [
+        Separator::None,
+        Separator::Space,
+        Separator::NarrowNoBreakSpace,
+    ];
+    let _: [Exponent; 2] = [Exponent::E, Exponent::TimesTen];
+    let _: [Format; 11] = [
+        Format::Iec,
+        Format::IecShort,
+        Format::Si,
+        Format::SiShort,
+        Format::IecBits,
+        Format::SiBits,
+        Format::IecBitsShort,
+        Format::SiBitsShort,
+        Format::Jedec,
+        Format::Coreutils,
+        Format::WindowsExplorer,
+    ];
+
+    assert_eq!(ExcessPrecision::Pad, ExcessPrecision::default());
+    assert_eq!(SubUnitBits::Bits, SubUnitBits::default());
+    assert_eq!(Rounding::HalfEven, Rounding::default());
+}
+
+#[test]
+fn units() {
+    fn assert_unit_traits<T: Hash + FromStr<Err = UnitParseError>>() {}
+
+    assert_traits::<Unit>();
+    assert_unit_traits::<Unit>();
+
+    let _: [Unit; 13] = [
+        Unit::Byte,
+        Unit::KiloByte,
+        Unit::MegaByte,
+        Unit::GigaByte,
+        Unit::TeraByte,
+        Unit::PetaByte,
+        Unit::ExaByte,
+        Unit::KibiByte,
+        Unit::MebiByte,
+        Unit::GibiByte,
+        Unit::TebiByte,
+        Unit::PebiByte,
+        Unit::ExbiByte,
+    ];
+
+    #[cfg(feature = "std")]
+    {
+        fn assert_error<T: std::error::Error>() {}
+        assert_error::<UnitParseError>();
+        assert_error::<OutOfRangeError>();
+    }
+}
+
+#[test]
+fn newtypes() {
+    bytesize::byte_newtype! {
+        pub struct Unbounded;
+    }
+
+    bytesize::byte_newtype! {
+        pub struct Bounded(..ByteSize::kib(1));
+    }
+
+    let _: fn(Unbounded) -> ByteSize = Unbounded::get;
+    let _: fn(ByteSize) -> Unbounded = Unbounded::from;
+    let _: fn(ByteSize) -> Result<Bounded, OutOfRangeError> = Bounded::try_from;
+    let _: fn(&OutOfRangeError) -> ByteSize = OutOfRangeError::size;
+}
+
+#[test]
+fn parsing_behavior() {
+    // single-letter and SI symbols are decimal; only `i` suffixes are binary
+    for (input, expected) in [
+        ("1", ByteSize::b(1)),
+        ("1 B", ByteSize::b(1)),
+        ("1k", ByteSize::kb(1)),
+        ("1 KB", ByteSize::kb(1)),
+        ("1 kb", ByteSize::kb(1)),
+        ("1Ki", ByteSize::kib(1)),
+        ("1 KiB", ByteSize::kib(1)),
+        ("1 kib", ByteSize::kib(1)),
+        ("1.5 MiB", ByteSize::kib(1536)),
+        ("1 EiB", ByteSize::eib(1)),
+    ] {
+        assert_eq!(
+            expected,
+            input.parse::<ByteSize>().unwrap(),
+            "input: {input}"
+        );
+    }
+
+    for input in ["", "KiB", "1 KiBB", "-1 KiB", "1 XB", "1 mB", "1 µB"] {
+        assert!(input.parse::<ByteSize>().is_err(), "input: {input}");
+    }
+}
+
+#[test]
+fn format_options() {
+    assert_traits::<FormatOptions>();
+    assert_traits_clone::<FormatOptions>();
+
+    let _: fn() -> FormatOptions = FormatOptions::new;
+    let _: fn(FormatOptions, Format) -> FormatOptions = FormatOptions::format;
+    let _: fn(FormatOptions, usize) -> FormatOptions = FormatOptions::precision;
+    let _: fn(FormatOptions, Separator) -> FormatOptions = FormatOptions::separator;
+    let _: fn(FormatOptions, Rounding) -> FormatOptions = FormatOptions::rounding;
+    let _: fn(&ByteSize, &FormatOptions) -> String = ByteSize::to_string_with;
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cached_display() {
+    use std::sync::Arc;
+
+    use bytesize::CachedDisplay;
+
+    fn assert_pool_traits<T: fmt::Debug + Send + Sync>() {}
+    assert_pool_traits::<CachedDisplay>();
+
+    let _: fn(usize) -> CachedDisplay = CachedDisplay::new;
+    let _: fn(&CachedDisplay) -> usize = CachedDisplay::capacity;
+    let _: fn(&CachedDisplay) -> usize = CachedDisplay::len;
+    let _: fn(&CachedDisplay) -> bool = CachedDisplay::is_empty;
+    let _: fn(&CachedDisplay, ByteSize, &FormatOptions) -> Arc<str> = CachedDisplay::get;
+    let _: fn(&CachedDisplay) = CachedDisplay::clear;
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn file_sizes() {
+    use std::{fs::Metadata, io, path::PathBuf};
+
+    fn assert_from_metadata<T: for<'a> From<&'a Metadata>>() {}
+    assert_from_metadata::<ByteSize>();
+
+    let _: fn(&'static str) -> io::Result<ByteSize> = ByteSize::of_file;
+    let _: fn(PathBuf) -> io::Result<ByteSize> = ByteSize::of_file;
+    let _: fn(&'static str) -> io::Result<ByteSize> = ByteSize::of_dir;
+    let _: fn(PathBuf) -> io::Result<ByteSize> = ByteSize::of_dir;
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dir_size_options() {
+    use std::{io, path::PathBuf};
+
+    use bytesize::DirSizeOptions;
+
+    assert_traits_clone::<DirSizeOptions>();
+
+    let _: fn() -> DirSizeOptions = DirSizeOptions::new;
+    let _: [fn(DirSizeOptions) -> DirSizeOptions; 2] =
+        [DirSizeOptions::follow_symlinks, DirSizeOptions::block_size];
+    let _: fn(&DirSizeOptions, PathBuf) -> io::Result<ByteSize> = DirSizeOptions::size_of;
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn counting_io() {
+    use std::io::{BufRead, Read, Write};
+
+    use bytesize::{CountingReader, CountingWriter};
+
+    fn assert_reader<T: Read + BufRead + Clone + fmt::Debug + Send + Sync>() {}
+    fn assert_writer<T: Write + Clone + fmt::Debug + Send + Sync>() {}
+    assert_reader::<CountingReader<&[u8]>>();
+    assert_writer::<CountingWriter<Vec<u8>>>();
+
+    type Reader = CountingReader<&'static [u8]>;
+    let _: fn(&'static [u8]) -> Reader = Reader::new;
+    let _: fn(&Reader) -> ByteSize = Reader::count;
+    let _: fn(&Reader) -> &&[u8] = Reader::get_ref;
+    let _: fn(&mut Reader) -> &mut &'static [u8] = Reader::get_mut;
+    let _: fn(Reader) -> &'static [u8] = Reader::into_inner;
+
+    type Writer = CountingWriter<Vec<u8>>;
+    let _: fn(Vec<u8>) -> Writer = Writer::new;
+    let _: fn(&Writer) -> ByteSize = Writer::count;
+    let _: fn(&Writer) -> &Vec<u8> = Writer::get_ref;
+    let _: fn(&mut Writer) -> &mut Vec<u8> = Writer::get_mut;
+    let _: fn(Writer) -> Vec<u8> = Writer::into_inner;
+}
+
+#[cfg(all(feature = "std", feature = "unstable"))]
+#[test]
+fn counting_io_meter() {
+    use bytesize::{CountingReader, CountingWriter, ThroughputMeter};
+
+    type Reader = CountingReader<&'static [u8]>;
+    let _: fn(Reader, ThroughputMeter) -> Reader = Reader::with_meter;
+    let _: fn(&Reader) -> Option<&ThroughputMeter> = Reader::meter;
+    let _: fn(&Reader) -> Option<ByteRate> = Reader::rate;
+
+    type Writer = CountingWriter<Vec<u8>>;
+    let _: fn(Writer, ThroughputMeter) -> Writer = Writer::with_meter;
+    let _: fn(&Writer) -> Option<&ThroughputMeter> = Writer::meter;
+    let _: fn(&Writer) -> Option<ByteRate> = Writer::rate;
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn limited_io() {
+    use std::io::{self, BufRead, Read, Write};
+
+    use bytesize::{LimitExceeded, LimitedReader, LimitedWriter};
+
+    fn assert_reader<T: Read + BufRead + Clone + fmt::Debug + Send + Sync>() {}
+    fn assert_writer<T: Write + Clone + fmt::Debug + Send + Sync>() {}
+    fn assert_error<T: std::error::Error + Clone + PartialEq + Send + Sync + 'static>()
+    where
+        io::Error: From<T>,
+    {
+    }
+    assert_reader::<LimitedReader<&[u8]>>();
+    assert_writer::<LimitedWriter<Vec<u8>>>();
+    assert_error::<LimitExceeded>();
+
+    let _: fn(&LimitExceeded) -> ByteSize = LimitExceeded::limit;
+
+    type Reader = LimitedReader<&'static [u8]>;
+    let _: fn(&'static [u8], ByteSize) -> Reader = Reader::new;
+    let _: [fn(&Reader) -> ByteSize; 3] = [Reader::limit, Reader::count, Reader::remaining];
+    let _: fn(&Reader) -> Option<u8> = Reader::overflow_byte;
+    let _: fn(&Reader) -> &&[u8] = Reader::get_ref;
+    let _: fn(&mut Reader) -> &mut &'static [u8] = Reader::get_mut;
+    let _: fn(Reader) -> &'static [u8] = Reader::into_inner;
+
+    type Writer = LimitedWriter<Vec<u8>>;
+    let _: fn(Vec<u8>, ByteSize) -> Writer = Writer::new;
+    let _: [fn(&Writer) -> ByteSize; 3] = [Writer::limit, Writer::count, Writer::remaining];
+    let _: fn(&Writer) -> &Vec<u8> = Writer::get_ref;
+    let _: fn(&mut Writer) -> &mut Vec<u8> = Writer::get_mut;
+    let _: fn(Writer) -> Vec<u8> = Writer::into_inner;
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn tokio_counting_io() {
+    use std::pin::Pin;
+
+    use bytesize::tokio::{ByteCounter, CountingReader, CountingWriter};
+    use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+    fn assert_reader<T: AsyncRead + AsyncBufRead + fmt::Debug + Send + Sync>() {}
+    fn assert_writer<T: AsyncWrite + fmt::Debug + Send + Sync>() {}
+    assert_traits_clone::<ByteCounter>();
+    assert_reader::<CountingReader<&[u8]>>();
+    assert_writer::<CountingWriter<Vec<u8>>>();
+
+    let _: fn() -> ByteCounter = ByteCounter::new;
+    let _: fn(&ByteCounter) -> ByteSize = ByteCounter::get;
+    let _: fn(&ByteCounter, ByteSize) = ByteCounter::add;
+    let _: fn(&ByteCounter) -> ByteSize = ByteCounter::reset;
+
+    type Reader = CountingReader<&'static [u8]>;
+    let _: fn(&'static [u8]) -> Reader = Reader::new;
+    let _: fn(&'static [u8], ByteCounter) -> Reader = Reader::with_counter;
+    let _: fn(&Reader) -> ByteSize = Reader::count;
+    let _: fn(&Reader) -> &ByteCounter = Reader::counter;
+    let _: fn(&Reader) -> &&[u8] = Reader::get_ref;
+    let _: fn(&mut Reader) -> &mut &'static [u8] = Reader::get_mut;
+    let _: fn(Pin<&mut Reader>) -> Pin<&mut &'static [u8]> = Reader::get_pin_mut;
+    let _: fn(Reader) -> &'static [u8] = Reader::into_inner;
+
+    type Writer = CountingWriter<Vec<u8>>;
+    let _: fn(Vec<u8>) -> Writer = Writer::new;
+    let _: fn(Vec<u8>, ByteCounter) -> Writer = Writer::with_counter;
+    let _: fn(&Writer) -> ByteSize = Writer::count;
+    let _: fn(&Writer) -> &ByteCounter = Writer::counter;
+    let _: fn(&Writer) -> &Vec<u8> = Writer::get_ref;
+    let _: fn(&mut Writer) -> &mut Vec<u8> = Writer::get_mut;
+    let _: fn(Pin<&mut Writer>) -> Pin<&mut Vec<u8>> = Writer::get_pin_mut;
+    let _: fn(Writer) -> Vec<u8> = Writer::into_inner;
+}
+
+#[cfg(all(feature = "tokio", feature = "unstable"))]
+#[test]
+fn tokio_counting_io_meter() {
+    use bytesize::{
+        tokio::{CountingReader, CountingWriter},
+        ThroughputMeter,
+    };
+
+    type Reader = CountingReader<&'static [u8]>;
+    let _: fn(Reader, ThroughputMeter) -> Reader = Reader::with_meter;
+    let _: fn(&Reader) -> Option<&ThroughputMeter> = Reader::meter;
+    let _: fn(&Reader) -> Option<ByteRate> = Reader::rate;
+
+    type Writer = CountingWriter<Vec<u8>>;
+    let _: fn(Writer, ThroughputMeter) -> Writer = Writer::with_meter;
+    let _: fn(&Writer) -> Option<&ThroughputMeter> = Writer::meter;
+    let _: fn(&Writer) -> Option<ByteRate> = Writer::rate;
+}
+
+#[cfg(all(feature = "std", feature = "unstable"))]
+#[test]
+fn rate_limiter() {
+    use std::time::Instant;
+
+    use bytesize::RateLimiter;
+
+    fn assert_limiter_traits<T: Clone + fmt::Debug + Send + Sync>() {}
+    assert_limiter_traits::<RateLimiter>();
+
+    let _: fn(ByteRate) -> RateLimiter = RateLimiter::new;
+    let _: fn(ByteRate, Instant) -> RateLimiter = RateLimiter::starting_at;
+    let _: fn(RateLimiter, ByteSize) -> RateLimiter = RateLimiter::with_burst;
+    let _: fn(&RateLimiter) -> ByteRate = RateLimiter::rate;
+    let _: fn(&RateLimiter) -> ByteSize = RateLimiter::burst;
+    let _: fn(&mut RateLimiter, ByteSize) -> Duration = RateLimiter::acquire;
+    let _: fn(&mut RateLimiter, ByteSize, Instant) -> Duration = RateLimiter::acquire_at;
+}
+
+#[cfg(all(feature = "std", feature = "unstable"))]
+#[test]
+fn throughput_meter() {
+    use std::time::Instant;
+
+    use bytesize::ThroughputMeter;
+
+    assert_traits_clone::<ThroughputMeter>();
+
+    let _: Duration = ThroughputMeter::DEFAULT_WINDOW;
+    let _: Duration = ThroughputMeter::DEFAULT_HALF_LIFE;
+
+    let _: fn() -> ThroughputMeter = ThroughputMeter::new;
+    let _: fn(Instant) -> ThroughputMeter = ThroughputMeter::starting_at;
+    let _: [fn(ThroughputMeter, Duration) -> ThroughputMeter; 2] = [
+        ThroughputMeter::with_window,
+        ThroughputMeter::with_half_life,
+    ];
+    let _: fn(&ThroughputMeter) -> Instant = ThroughputMeter::start;
+    let _: [fn(&ThroughputMeter) -> Duration; 2] =
+        [ThroughputMeter::window, ThroughputMeter::half_life];
+    let _: fn(&ThroughputMeter) -> ByteSize = ThroughputMeter::total;
+    let _: fn(&mut ThroughputMeter, ByteSize) = ThroughputMeter::record;
+    let _: fn(&mut ThroughputMeter, ByteSize, Instant) = ThroughputMeter::record_at;
+    let _: [fn(&ThroughputMeter) -> ByteRate; 3] = [
+        ThroughputMeter::current,
+        ThroughputMeter::average,
+        ThroughputMeter::ewma,
+    ];
+    let _: [fn(&ThroughputMeter, Instant) -> ByteRate; 3] = [
+        ThroughputMeter::current_at,
+        ThroughputMeter::average_at,
+        ThroughputMeter::ewma_at,
+    ];
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_functions() {
+    use core::ffi::c_char;
+
+    use bytesize::ffi;
+
+    type FormatFn = unsafe extern "C" fn(u64, *mut c_char, usize) -> usize;
+
+    let _: unsafe extern "C" fn(*const c_char, *mut u64) -> bool = ffi::bytesize_parse;
+    let _: unsafe extern "C" fn(*const c_char, *mut c_char, usize) -> usize =
+        ffi::bytesize_parse_error;
+    let _: FormatFn = ffi::bytesize_format_iec;
+    let _: FormatFn = ffi::bytesize_format_si;
+    let _: FormatFn = ffi::bytesize_format_exact;
+    let _: usize = ffi::BYTESIZE_FORMAT_LEN;
+}
+
+#[cfg(feature = "opentelemetry")]
+#[test]
+fn opentelemetry() {
+    use bytesize::otel;
+    use opentelemetry::{Key, KeyValue, Value};
+
+    let _: fn(ByteSize, Key) -> KeyValue = ByteSize::to_key_value;
+    let _: fn(&Value) -> Option<ByteSize> = ByteSize::from_otel_value;
+    let _: fn(&'static [KeyValue; 1], &str) -> Option<ByteSize> = ByteSize::from_attributes;
+    let _: fn(ByteSize) -> Value = Value::from;
+
+    let _: [&str; 6] = [
+        otel::HTTP_REQUEST_BODY_SIZE,
+        otel::HTTP_RESPONSE_BODY_SIZE,
+        otel::MESSAGING_MESSAGE_BODY_SIZE,
+        otel::RPC_MESSAGE_COMPRESSED_SIZE,
+        otel::RPC_MESSAGE_UNCOMPRESSED_SIZE,
+        otel::FILE_SIZE,
+    ];
+}
+
+#[cfg(feature = "async-graphql")]
+#[test]
+fn async_graphql_scalars() {
+    use async_graphql::ScalarType;
+    use bytesize::async_graphql::HumanReadable;
+
+    fn assert_scalar<T: ScalarType + Copy + fmt::Debug + Default + PartialEq>() {}
+
+    assert_scalar::<ByteSize>();
+    assert_scalar::<HumanReadable>();
+    let _: HumanReadable = HumanReadable(ByteSize::b(0));
+    let _: fn(ByteSize) -> HumanReadable = HumanReadable::from;
+    let _: fn(HumanReadable) -> ByteSize = ByteSize::from;
+}
+
+#[cfg(feature = "bson")]
+#[test]
+fn bson_helpers() {
+    use bson::{Bson, Document};
+
+    let _: fn(&Bson) -> Result<ByteSize, String> = bytesize::bson::from_bson;
+    let _: fn(&Document, &str) -> Result<Option<ByteSize>, String> = bytesize::bson::get;
+    let _: Bson = ByteSize::kib(1).into();
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn clap_parser() {
+    use bytesize::clap::ByteSizeValueParser;
+
+    assert_traits_clone::<ByteSizeValueParser>();
+
+    let _: fn() -> ByteSizeValueParser = bytesize::clap::parser;
+    let _: fn() -> ByteSizeValueParser = ByteSizeValueParser::new;
+    let _: fn(ByteSizeValueParser) -> ByteSizeValueParser = ByteSizeValueParser::lenient;
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn proptest_strategies() {
+    use proptest::{arbitrary::any, strategy::Strategy};
+
+    fn assert_strategy<S: Strategy<Value = ByteSize>>(_: S) {}
+
+    assert_strategy(any::<ByteSize>());
+    assert_strategy(bytesize::proptest::up_to(ByteSize::kib(1)));
+    assert_strategy(bytesize::proptest::between(
+        ByteSize::b(1),
+        ByteSize::kib(1),
+    ));
+    assert_strategy(bytesize::proptest::realistic());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rand_sampling() {
+    use bytesize::rand::{LogUniform, UniformByteSize};
+    use rand::{distr::uniform::Error, rngs::SmallRng};
+
+    assert_traits::<UniformByteSize>();
+
+    let _: fn(&mut SmallRng, ops::Range<ByteSize>) -> ByteSize = ByteSize::random_range;
+    let _: fn(&mut SmallRng, ops::RangeTo<ByteSize>) -> ByteSize = ByteSize::random_log_range;
+    let _: fn(ByteSize, ByteSize) -> Result<LogUniform, Error> = LogUniform::new;
+    let _: fn(ByteSize, ByteSize) -> Result<LogUniform, Error> = LogUniform::new_inclusive;
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_helpers() {
+    use serde_json::{value::Serializer, Error, Value};
+
+    type SerializeFn = fn(&ByteSize, Serializer) -> Result<Value, Error>;
+    type DeserializeFn = fn(Value) -> Result<ByteSize, Error>;
+
+    let _: SerializeFn = bytesize::serde::string::serialize;
+    let _: DeserializeFn = bytesize::serde::string::deserialize;
+    let _: SerializeFn = bytesize::serde::flexible::serialize;
+    let _: DeserializeFn = bytesize::serde::flexible::deserialize;
+
+    let _: [SerializeFn; 12] = [
+        bytesize::serde::as_kb::serialize,
+        bytesize::serde::as_mb::serialize,
+        bytesize::serde::as_gb::serialize,
+        bytesize::serde::as_tb::serialize,
+        bytesize::serde::as_pb::serialize,
+        bytesize::serde::as_eb::serialize,
+        bytesize::serde::as_kib::serialize,
+        bytesize::serde::as_mib::serialize,
+        bytesize::serde::as_gib::serialize,
+        bytesize::serde::as_tib::serialize,
+        bytesize::serde::as_pib::serialize,
+        bytesize::serde::as_eib::serialize,
+    ];
+    let _: [DeserializeFn; 12] = [
+        bytesize::serde::as_kb::deserialize,
+        bytesize::serde::as_mb::deserialize,
+        bytesize::serde::as_gb::deserialize,
+        bytesize::serde::as_tb::deserialize,
+        bytesize::serde::as_pb::deserialize,
+        bytesize::serde::as_eb::deserialize,
+        bytesize::serde::as_kib::deserialize,
+        bytesize::serde::as_mib::deserialize,
+        bytesize::serde::as_gib::deserialize,
+        bytesize::serde::as_tib::deserialize,
+        bytesize::serde::as_pib::deserialize,
+        bytesize::serde::as_eib::deserialize,
+    ];
+}
+
+#[cfg(feature = "serde_with")]
+#[test]
+fn serde_with_adapters() {
+    use bytesize::serde_with::*;
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    fn assert_adapter<T>()
+    where
+        T: Copy
+            + fmt::Debug
+            + Default
+            + SerializeAs<ByteSize>
+            + for<'de> DeserializeAs<'de, ByteSize>,
+    {
+    }
+
+    assert_adapter::<HumanReadable>();
+    assert_adapter::<AsBytes>();
+    assert_adapter::<AsKb>();
+    assert_adapter::<AsMb>();
+    assert_adapter::<AsGb>();
+    assert_adapter::<AsTb>();
+    assert_adapter::<AsPb>();
+    assert_adapter::<AsEb>();
+    assert_adapter::<AsKib>();
+    assert_adapter::<AsMib>();
+    assert_adapter::<AsGib>();
+    assert_adapter::<AsTib>();
+    assert_adapter::<AsPib>();
+    assert_adapter::<AsEib>();
+}
+
+#[cfg(feature = "uniffi")]
+#[test]
+fn uniffi_functions() {
+    use bytesize::uniffi;
+
+    let _: fn(&str) -> Result<ByteSize, uniffi::Error> = uniffi::parse_size;
+    let _: fn(ByteSize, Option<String>) -> Result<String, uniffi::Error> = uniffi::format_size;
+    let _: fn(ByteSize) -> String = uniffi::format_size_exact;
+}
+
+#[test]
+fn unit_preference() {
+    fn assert_preference_traits<T>()
+    where
+        T: Default + Hash + fmt::Display + FromStr<Err = String>,
+    {
+    }
+
+    assert_traits::<UnitPreference>();
+    assert_preference_traits::<UnitPreference>();
+
+    let _: [fn() -> UnitPreference; 3] = [
+        UnitPreference::auto,
+        UnitPreference::iec,
+        UnitPreference::si,
+    ];
+    let _: fn(Unit) -> UnitPreference = UnitPreference::unit;
+    let _: fn(UnitPreference) -> UnitPreference = UnitPreference::short;
+}
+
+#[test]
+fn parse_options() {
+    assert_traits_clone::<ParseOptions>();
+
+    let _: fn() -> ParseOptions = ParseOptions::new;
+    let _: fn(ParseOptions) -> ParseOptions = ParseOptions::lenient;
+    let _: fn(&ParseOptions, &str) -> Result<ByteSize, String> = ParseOptions::parse;
+}
+
+// the `display-si-default` feature's output is pinned in `display_si_default.rs`
+#[cfg(not(feature = "display-si-default"))]
+#[test]
+fn display_behavior() {
+    assert_eq!("0 B", ByteSize(0).to_string());
+    assert_eq!("1023 B", ByteSize(1023).to_string());
+    assert_eq!("1.0 KiB", ByteSize::kib(1).to_string());
+    assert_eq!("1.5 MiB", ByteSize::kib(1536).to_string());
+    assert_eq!("1.0 kB", ByteSize::kb(1).display().si().to_string());
+    assert_eq!(
+        "1.5M",
+        ByteSize::kib(1536).display().iec_short().to_string()
+    );
+    assert_eq!("1.0 KiB (1024 bytes)", format!("{:?}", ByteSize::kib(1)));
+}
@@ -0,0 +1,82 @@
+//! Running the `bytesize` binary.
+
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write as _,
+    process::{Command, Output, Stdio},
+};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bytesize"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+#[track_caller]
+fn assert_stdout(expected: &str, args: &[&str]) {
+    let output = run(args, "");
+    assert!(output.status.success(), "args: {args:?}");
+    assert_eq!(expected, String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn convert() {
+    assert_stdout("1.5 GiB\n", &["1.5GiB"]);
+    assert_stdout("1610.6 MB\n", &["1.5GiB", "--to", "mb"]);
+    assert_stdout("1610.6 MB\n", &["--to=MB", "1.5GiB"]);
+    assert_stdout("1.6 GB\n", &["--si", "1.5GiB"]);
+    assert_stdout("3000000000000\n", &["--parse", "3 TB", "--raw"]);
+    assert_stdout("1.0 KiB\n2.0 KiB\n", &["1KiB", "2KiB"]);
+    assert_stdout("5120 B\n", &["--to", "b", "--", "5 KiB"]);
+}
+
+#[test]
+fn stdin() {
+    let output = run(&["--raw"], "1 KiB\n\n2 kB\n");
+    assert!(output.status.success());
+    assert_eq!("1024\n2000\n", String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn errors() {
+    let output = run(&["1 KiB", "nope", "2 KiB"], "");
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "1.0 KiB\n2.0 KiB\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+    assert!(String::from_utf8(output.stderr).unwrap().contains("nope"));
+
+    for args in [
+        &["--to"][..],
+        &["--to", "parsecs", "1"],
+        &["--raw", "--to", "mb", "1"],
+        &["--frobnicate"],
+    ] {
+        let output = run(args, "");
+        assert_eq!(Some(2), output.status.code(), "args: {args:?}");
+    }
+}
+
+#[test]
+fn help() {
+    let output = run(&["--help"], "");
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Usage: bytesize"));
+}
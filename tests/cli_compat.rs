@@ -0,0 +1,123 @@
+//! Parsing captured output of command-line tools.
+
+#![cfg(feature = "cli-compat")]
+
+use bytesize::{cli, ByteSize};
+
+#[test]
+fn df() {
+    let output = "\
+Filesystem      Size  Used Avail Use% Mounted on
+udev            7.7G     0  7.7G   0% /dev
+tmpfs           1.6G  2.1M  1.6G   1% /run
+/dev/nvme0n1p2  468G  112G  333G  26% /
+/dev/sdb1       932G  501G  431G  54% /media/backup drive
+";
+
+    let entries = output
+        .lines()
+        .skip(1)
+        .map(cli::parse_df_line)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(4, entries.len());
+
+    assert_eq!("udev", entries[0].filesystem);
+    assert_eq!(ByteSize(0), entries[0].used);
+
+    assert_eq!(ByteSize(2_202_009), entries[1].used);
+
+    assert_eq!("/dev/nvme0n1p2", entries[2].filesystem);
+    assert_eq!(ByteSize::gib(468), entries[2].size);
+    assert_eq!(ByteSize::gib(112), entries[2].used);
+    assert_eq!(ByteSize::gib(333), entries[2].available);
+    assert_eq!("/", entries[2].mounted_on);
+
+    assert_eq!("/media/backup drive", entries[3].mounted_on);
+}
+
+#[test]
+fn du() {
+    let output = "\
+4.0K\t./.git/branches
+1.2M\t./target/debug/build
+48K\t./src
+0\t./empty
+2.3G\t./my documents
+";
+
+    let entries = output
+        .lines()
+        .map(cli::parse_du_line)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(ByteSize::kib(4), entries[0].size);
+    assert_eq!("./.git/branches", entries[0].path);
+    assert_eq!(ByteSize(1_258_291), entries[1].size);
+    assert_eq!(ByteSize::kib(48), entries[2].size);
+    assert_eq!(ByteSize(0), entries[3].size);
+    assert_eq!("./my documents", entries[4].path);
+
+    let total = entries.iter().map(|entry| entry.size).sum::<ByteSize>();
+    assert!(total > ByteSize::gib(2));
+}
+
+#[test]
+fn free() {
+    let output = "\
+               total        used        free      shared  buff/cache   available
+Mem:            15Gi       4.2Gi       8.1Gi       312Mi       3.0Gi        10Gi
+Swap:          2.0Gi          0B       2.0Gi
+";
+
+    let mut lines = output.lines().skip(1);
+
+    let mem = cli::parse_free_line(lines.next().unwrap()).unwrap();
+    assert_eq!("Mem", mem.label);
+    assert_eq!(ByteSize::gib(15), mem.total);
+    assert_eq!(ByteSize(4_509_715_660), mem.used);
+    assert_eq!(Some(ByteSize::mib(312)), mem.shared);
+    assert_eq!(Some(ByteSize::gib(3)), mem.buff_cache);
+    assert_eq!(Some(ByteSize::gib(10)), mem.available);
+
+    let swap = cli::parse_free_line(lines.next().unwrap()).unwrap();
+    assert_eq!("Swap", swap.label);
+    assert_eq!(ByteSize::gib(2), swap.total);
+    assert_eq!(ByteSize(0), swap.used);
+    assert_eq!(ByteSize::gib(2), swap.free);
+    assert_eq!(None, swap.shared);
+}
+
+#[test]
+fn ls() {
+    let output = "\
+total 1.1M
+drwxr-xr-x  2 alice staff 4.0K Mar  4 09:15 assets
+-rw-r--r--  1 alice staff  847 Mar  4 09:15 README.md
+-rw-r--r--  1 alice staff 1.1M Dec 31  2023 release notes.pdf
+lrwxrwxrwx  1 alice staff   11 Mar  4 09:15 latest -> release.pdf
+";
+
+    let entries = output
+        .lines()
+        .skip(1)
+        .map(cli::parse_ls_line)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(ByteSize::kib(4), entries[0].size);
+    assert_eq!("assets", entries[0].name);
+    assert_eq!(ByteSize(847), entries[1].size);
+    assert_eq!(ByteSize(1_153_433), entries[2].size);
+    assert_eq!("release notes.pdf", entries[2].name);
+    assert_eq!("latest -> release.pdf", entries[3].name);
+}
+
+#[test]
+fn si_output() {
+    // `--si` output uses 1000-based units, which the `FromStr` impl handles
+    assert_eq!(ByteSize::kb(4), "4.0k".parse::<ByteSize>().unwrap());
+    assert_eq!(ByteSize::gb(21), "21G".parse::<ByteSize>().unwrap());
+}
@@ -0,0 +1,41 @@
+//! Changing the process-wide default format.
+//!
+//! Kept in its own test binary since the default is global state.
+
+use bytesize::{ByteSize, Format};
+
+#[test]
+fn default_format() {
+    // the `display-si-default` feature's starting point is tested in `display_si_default.rs`
+    if cfg!(not(feature = "display-si-default")) {
+        assert_eq!(Format::Iec, bytesize::default_format());
+        assert_eq!("1.5 KiB", ByteSize(1536).to_string());
+    }
+
+    for (format, expected) in [
+        (Format::Si, "1.5 kB"),
+        (Format::SiShort, "1.5k"),
+        (Format::IecShort, "1.5K"),
+        (Format::Coreutils, "1.5K"),
+        (Format::SiBitsShort, "12.3k"),
+        (Format::Iec, "1.5 KiB"),
+    ] {
+        bytesize::set_default_format(format);
+        assert_eq!(format, bytesize::default_format());
+        assert_eq!(expected, ByteSize(1536).to_string(), "format: {format:?}");
+    }
+
+    bytesize::set_default_format(Format::Si);
+
+    assert_eq!("1.5 kB (1536 bytes)", format!("{:?}", ByteSize(1536)));
+    assert_eq!("|  1.54 kB|", format!("|{:>9.2}|", ByteSize(1536)));
+
+    // explicitly styled output is unaffected
+    assert_eq!("1.5 KiB", ByteSize(1536).display().to_string());
+    assert_eq!(
+        "1.5G",
+        ByteSize::mib(1536).display().iec_short().to_string()
+    );
+
+    bytesize::set_default_format(Format::Iec);
+}
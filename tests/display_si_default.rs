@@ -0,0 +1,21 @@
+//! Default output with the `display-si-default` crate feature.
+
+#![cfg(feature = "display-si-default")]
+
+use bytesize::{ByteSize, Format};
+
+#[test]
+fn si_default() {
+    assert_eq!(Format::Si, bytesize::default_format());
+
+    assert_eq!("1.5 kB", ByteSize(1536).to_string());
+    assert_eq!("1.5 kB (1536 bytes)", format!("{:?}", ByteSize(1536)));
+    assert_eq!("|  1.54 kB|", format!("|{:>9.2}|", ByteSize(1536)));
+
+    // explicitly styled output is unaffected
+    assert_eq!("1.5 KiB", ByteSize(1536).display().to_string());
+    assert_eq!("1.5 KiB", ByteSize(1536).display().iec().to_string());
+
+    bytesize::set_default_format(Format::Iec);
+    assert_eq!("1.5 KiB", ByteSize(1536).to_string());
+}
@@ -0,0 +1,40 @@
+//! Snapshot of the public API with default features.
+//!
+//! Catches accidental additions, removals, and signature changes that `api_guard` doesn't name,
+//! like new trait impls. Items behind the `unstable` feature aren't part of the snapshot.
+//!
+//! Needs a nightly toolchain for rustdoc's JSON output, so it's ignored by default. Run it with
+//! `just public-api`, and update the snapshot after an intended change with
+//! `UPDATE_SNAPSHOTS=yes just public-api`.
+
+use std::{path::Path, process::Command};
+
+#[test]
+#[ignore = "needs a nightly toolchain, run with `just public-api`"]
+fn public_api() {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("public-api");
+
+    let status = Command::new("cargo")
+        .args(["+nightly", "rustdoc", "--lib", "--quiet", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args([
+            "--",
+            "-Z",
+            "unstable-options",
+            "--output-format=json",
+            "--cap-lints=allow",
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success(), "rustdoc failed: {status}");
+
+    let api = public_api::Builder::from_rustdoc_json(target_dir.join("doc/bytesize.json"))
+        .omit_blanket_impls(true)
+        .omit_auto_trait_impls(true)
+        .build()
+        .unwrap();
+
+    api.assert_eq_or_update("./tests/public-api.txt");
+}